@@ -7,6 +7,7 @@
 //! - Can chain to other robots for complex behavior
 
 mod robot;
+mod thought;
 mod widget_impl;
 
-pub use robot::{Action, Robot, RobotState};
+pub use robot::{Action, Robot, RobotState, StopReason};
@@ -4,9 +4,10 @@ mod mutators;
 mod ops;
 mod types;
 
+use tt_rs_box::ToonBox;
 use tt_rs_core::WidgetId;
 
-pub use types::{Action, RobotState};
+pub use types::{Action, RobotState, StopReason};
 
 /// A robot widget that can be trained to automate tasks.
 ///
@@ -17,12 +18,16 @@ pub struct Robot {
     pub(crate) id: WidgetId,
     /// Current state of the robot.
     pub(crate) state: RobotState,
-    /// The pattern this robot expects (optional - starts empty).
-    pub(crate) pattern: Option<WidgetId>,
+    /// The box pattern in the thought bubble (set when training starts).
+    pub(crate) pattern: Option<ToonBox>,
     /// Recorded actions to perform.
     pub(crate) actions: Vec<Action>,
     /// Next robot in chain (optional).
     pub(crate) next_robot: Option<WidgetId>,
+    /// Why the last run stopped, shown until the next run.
+    pub(crate) stop_reason: Option<StopReason>,
+    /// Step to resume training from when next given a box.
+    pub(crate) retrain_at: Option<usize>,
 }
 
 impl Robot {
@@ -34,12 +39,14 @@ impl Robot {
             pattern: None,
             actions: Vec::new(),
             next_robot: None,
+            stop_reason: None,
+            retrain_at: None,
         }
     }
 
     /// Creates a robot with specified values.
     pub(crate) fn new_with(
-        pattern: Option<WidgetId>,
+        pattern: Option<ToonBox>,
         actions: Vec<Action>,
         next: Option<WidgetId>,
     ) -> Self {
@@ -49,6 +56,8 @@ impl Robot {
             pattern,
             actions,
             next_robot: next,
+            stop_reason: None,
+            retrain_at: None,
         }
     }
 }
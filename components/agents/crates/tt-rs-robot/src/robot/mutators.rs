@@ -1,8 +1,9 @@
 //! Robot mutation methods.
 
+use tt_rs_box::ToonBox;
 use tt_rs_core::WidgetId;
 
-use super::{Action, Robot, RobotState};
+use super::{Action, Robot, RobotState, StopReason};
 
 impl Robot {
     /// Starts training mode - robot begins recording actions.
@@ -11,6 +12,18 @@ impl Robot {
         self.actions.clear();
     }
 
+    /// Resumes training after the first `keep` actions, dropping the rest.
+    pub fn resume_training(&mut self, keep: usize) {
+        self.state = RobotState::Training;
+        self.actions.truncate(keep);
+        self.retrain_at = None;
+    }
+
+    /// Sets the step to resume training from when next given a box.
+    pub fn set_retrain_at(&mut self, step: Option<usize>) {
+        self.retrain_at = step;
+    }
+
     /// Stops training mode - robot saves recorded actions.
     pub fn stop_training(&mut self) {
         self.state = RobotState::Idle;
@@ -23,18 +36,49 @@ impl Robot {
         }
     }
 
-    /// Sets the pattern this robot expects.
-    pub fn set_pattern(&mut self, pattern_id: WidgetId) {
-        self.pattern = Some(pattern_id);
+    /// Replaces the recorded actions (e.g. when loading a saved robot).
+    pub fn set_actions(&mut self, actions: Vec<Action>) {
+        self.actions = actions;
+    }
+
+    /// Removes the action at `index`.
+    pub fn remove_action(&mut self, index: usize) -> Option<Action> {
+        (index < self.actions.len()).then(|| self.actions.remove(index))
+    }
+
+    /// Moves the action at `from` to `to`, shifting the ones between.
+    pub fn move_action(&mut self, from: usize, to: usize) -> bool {
+        if from >= self.actions.len() || to >= self.actions.len() {
+            return false;
+        }
+        let action = self.actions.remove(from);
+        self.actions.insert(to, action);
+        true
+    }
+
+    /// Sets the box pattern this robot expects.
+    pub fn set_pattern(&mut self, pattern: ToonBox) {
+        self.pattern = Some(pattern);
+    }
+
+    /// Sets the robot that gets boxes this robot doesn't match.
+    pub fn set_next_robot(&mut self, next: Option<WidgetId>) {
+        self.next_robot = next;
     }
 
     /// Starts working mode - robot executes actions.
     pub fn start_working(&mut self) {
         if !self.actions.is_empty() {
             self.state = RobotState::Working;
+            self.stop_reason = None;
         }
     }
 
+    /// Records why the last run stopped.
+    pub fn set_stop_reason(&mut self, reason: StopReason) {
+        self.stop_reason = Some(reason);
+    }
+
     /// Stops working mode.
     pub fn stop_working(&mut self) {
         self.state = RobotState::Idle;
@@ -1,8 +1,9 @@
 //! Robot accessor methods.
 
+use tt_rs_box::ToonBox;
 use tt_rs_core::WidgetId;
 
-use super::{Action, Robot, RobotState};
+use super::{Action, Robot, RobotState, StopReason};
 
 impl Robot {
     /// Returns the robot's state.
@@ -15,13 +16,28 @@ impl Robot {
         &self.actions
     }
 
-    /// Returns the pattern this robot expects.
-    pub fn pattern(&self) -> Option<WidgetId> {
-        self.pattern
+    /// Returns the box pattern this robot expects.
+    pub fn pattern(&self) -> Option<&ToonBox> {
+        self.pattern.as_ref()
+    }
+
+    /// Returns why the last run stopped, if it has run.
+    pub fn stop_reason(&self) -> Option<StopReason> {
+        self.stop_reason
+    }
+
+    /// Returns the robot that gets boxes this robot doesn't match.
+    pub fn next_robot(&self) -> Option<WidgetId> {
+        self.next_robot
+    }
+
+    /// Returns the step training resumes from when next given a box.
+    pub fn retrain_at(&self) -> Option<usize> {
+        self.retrain_at
     }
 
     /// Creates a copy of this robot with a new ID.
     pub fn copy_robot(&self) -> Robot {
-        Robot::new_with(self.pattern, self.actions.clone(), self.next_robot)
+        Robot::new_with(self.pattern.clone(), self.actions.clone(), self.next_robot)
     }
 }
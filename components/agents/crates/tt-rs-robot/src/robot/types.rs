@@ -1,5 +1,7 @@
 //! Robot types and enums.
 
+use std::fmt;
+
 /// The state a robot can be in.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum RobotState {
@@ -12,12 +14,33 @@ pub enum RobotState {
     Working,
 }
 
+/// Why a robot's run loop stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    /// The box no longer matches the robot's pattern.
+    PatternFailed,
+    /// The per-run step budget ran out.
+    BudgetExhausted,
+    /// The pattern is waiting on an empty nest.
+    WaitingOnNest,
+    /// A pass left everything as an earlier pass did, so it would
+    /// never stop.
+    NoProgress,
+    /// The robot set off a bomb, ending its process.
+    Exploded,
+    /// The pattern is waiting on an empty nest no bird can deliver to.
+    NoSenders,
+    /// The pattern was waiting on a nest that has since gone (vacuumed,
+    /// blown up or carried off).
+    NestGone,
+}
+
 /// An action that a robot can perform.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Action {
     /// Pick up a widget from a location.
     PickUp {
-        /// Path to the widget (e.g., "hole:0" for first hole).
+        /// Path to the widget (e.g., "input/hole:0" for the input's first hole).
         path: String,
     },
     /// Drop the held widget at a location.
@@ -25,7 +48,7 @@ pub enum Action {
         /// Path to the target location.
         path: String,
     },
-    /// Copy a widget (using magic wand).
+    /// Copy a widget or box (using magic wand).
     Copy {
         /// Path to the widget to copy.
         path: String,
@@ -46,4 +69,76 @@ pub enum Action {
         /// Path to the target number.
         target_path: String,
     },
+    /// Create a new empty box (dragged off the box stack).
+    NewBox {
+        /// Number of holes in the new box.
+        holes: usize,
+    },
+    /// Split a box by dropping it on a number.
+    SplitBox {
+        /// Path to the box to split.
+        path: String,
+        /// The number's value (negative counts holes from the right, 0 copies).
+        at: i64,
+    },
+    /// Take the top message out of a nest.
+    TakeFromNest {
+        /// Path to the nest.
+        path: String,
+    },
+    /// Set off a bomb, ending the robot's process.
+    SetOffBomb {
+        /// Path to the bomb.
+        path: String,
+    },
+}
+
+impl Action {
+    /// The path the action works on, if any.
+    pub fn path(&self) -> Option<&str> {
+        match self {
+            Action::PickUp { path }
+            | Action::Drop { path }
+            | Action::Copy { path }
+            | Action::Remove { path }
+            | Action::SplitBox { path, .. }
+            | Action::TakeFromNest { path }
+            | Action::SetOffBomb { path } => Some(path),
+            Action::ApplyArithmetic { target_path, .. } => Some(target_path),
+            Action::NewBox { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Action::PickUp { path } => write!(f, "Pick up {}", path),
+            Action::Drop { path } => write!(f, "Drop on {}", path),
+            Action::Copy { path } => write!(f, "Copy {}", path),
+            Action::Remove { path } => write!(f, "Vacuum up {}", path),
+            Action::ApplyArithmetic {
+                operator,
+                numerator,
+                denominator,
+                target_path,
+            } if *denominator == 1 => {
+                write!(f, "Apply {}{} to {}", operator, numerator, target_path)
+            }
+            Action::ApplyArithmetic {
+                operator,
+                numerator,
+                denominator,
+                target_path,
+            } => write!(
+                f,
+                "Apply {}{}/{} to {}",
+                operator, numerator, denominator, target_path
+            ),
+            Action::NewBox { holes } => write!(f, "Make a box with {} holes", holes),
+            Action::SplitBox { path, at } => write!(f, "Split {} at {}", path, at),
+            Action::TakeFromNest { path } => write!(f, "Take a message from {}", path),
+            Action::SetOffBomb { path } => write!(f, "Set off the bomb at {}", path),
+        }
+    }
 }
@@ -0,0 +1,68 @@
+//! Thought bubble rendering.
+//!
+//! The robot's box pattern, drawn small. Each part carries its hole path
+//! (`data-pattern-path`, e.g. "" for the box, "0/1" for hole 1 of the box
+//! in hole 0) so the vacuum can erase exactly the part it's dropped on.
+
+use tt_rs_box::ToonBox;
+use tt_rs_core::Widget;
+use yew::prelude::*;
+
+/// Renders a robot's pattern as a thought bubble.
+pub fn render_thought(pattern: &ToonBox) -> Html {
+    html! {
+        <div class="robot-pattern robot-thought">{ render_box(pattern, String::new()) }</div>
+    }
+}
+
+fn render_box(b: &ToonBox, path: String) -> Html {
+    let body = if b.is_erased() {
+        let label = if b.is_empty() {
+            "?".to_string()
+        } else {
+            format!("?[{}]", b.len())
+        };
+        html! { { label } }
+    } else {
+        b.holes()
+            .map(|hole| match hole.content() {
+                Some(w) => html! {
+                    <span class="thought-hole">{ render_part(w, hole_path(&path, hole.index())) }</span>
+                },
+                None => html! { <span class="thought-hole">{ "\u{00A0}" }</span> },
+            })
+            .collect()
+    };
+    html! {
+        <span class="thought-part thought-box" data-pattern-path={path}>{ body }</span>
+    }
+}
+
+fn render_part(w: &dyn Widget, path: String) -> Html {
+    if let Some(b) = w.as_any().downcast_ref::<ToonBox>() {
+        return render_box(b, path);
+    }
+    html! {
+        <span class="thought-part" data-pattern-path={path}>{ label(w) }</span>
+    }
+}
+
+/// "number 3" -> "3", "text \"hi\"" -> "\"hi\"", erased widgets -> "?".
+fn label(w: &dyn Widget) -> String {
+    let description = w.description();
+    if description.starts_with("erased") {
+        return "?".to_string();
+    }
+    description
+        .strip_prefix(w.type_name())
+        .map(|rest| rest.trim_start().to_string())
+        .unwrap_or(description)
+}
+
+fn hole_path(path: &str, index: usize) -> String {
+    if path.is_empty() {
+        index.to_string()
+    } else {
+        format!("{}/{}", path, index)
+    }
+}
@@ -1,6 +1,8 @@
 //! Widget trait implementation for Robot.
 
-use crate::robot::{Robot, RobotState};
+use crate::robot::{Robot, RobotState, StopReason};
+use crate::thought::render_thought;
+use std::any::Any;
 use tt_rs_core::{MatchResult, Widget, WidgetId};
 use yew::prelude::*;
 
@@ -13,6 +15,10 @@ impl Widget for Robot {
         self.id
     }
 
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
     fn copy(&self) -> Box<dyn Widget> {
         Box::new(self.copy_robot())
     }
@@ -20,35 +26,67 @@ impl Widget for Robot {
     fn matches(&self, other: &dyn Widget) -> MatchResult {
         if other.type_name() == "robot" {
             // Robots match other robots in the same state
-            MatchResult::Match
+            MatchResult::matched(other.id())
         } else {
             MatchResult::NoMatch
         }
     }
 
     fn render(&self) -> Html {
-        let state_class = match self.state {
-            RobotState::Idle => "idle",
-            RobotState::Training => "training",
-            RobotState::Working => "working",
+        let state_class = match (self.state, self.stop_reason) {
+            (
+                RobotState::Idle,
+                Some(
+                    StopReason::BudgetExhausted
+                    | StopReason::NoProgress
+                    | StopReason::Exploded
+                    | StopReason::NoSenders
+                    | StopReason::NestGone,
+                ),
+            ) => "idle stopped",
+            (RobotState::Idle, _) => "idle",
+            (RobotState::Training, _) => "training",
+            (RobotState::Working, _) => "working",
         };
 
-        let state_indicator = match self.state {
-            RobotState::Idle => "Idle",
-            RobotState::Training => "Training...",
-            RobotState::Working => "Working!",
+        let retrain_note = self
+            .retrain_at
+            .map(|step| format!("Retrain from step {}", step + 1));
+        let state_indicator = match (self.state, self.stop_reason) {
+            (RobotState::Idle, _) if retrain_note.is_some() => "Give me a box",
+            (RobotState::Idle, Some(StopReason::PatternFailed)) => "Done",
+            (RobotState::Idle, Some(StopReason::BudgetExhausted)) => "Stopped: too many steps",
+            (RobotState::Idle, Some(StopReason::WaitingOnNest)) => "Waiting...",
+            (RobotState::Idle, Some(StopReason::NoProgress)) => "Stopped: going in circles",
+            (RobotState::Idle, Some(StopReason::Exploded)) => "Stopped: boom!",
+            (RobotState::Idle, Some(StopReason::NoSenders)) => "Stopped: no birds left",
+            (RobotState::Idle, Some(StopReason::NestGone)) => "Stopped: nest gone",
+            (RobotState::Idle, None) => "Idle",
+            (RobotState::Training, _) => "Training...",
+            (RobotState::Working, _) => "Working!",
         };
 
         let action_count = self.actions.len();
+        let team_class = if self.next_robot.is_some() {
+            " team"
+        } else {
+            ""
+        };
 
         html! {
-            <div class={format!("widget robot {}", state_class)} data-widget-id={self.id.to_string()}>
+            <div class={format!("widget robot {}{}", state_class, team_class)} data-widget-id={self.id.to_string()}>
                 <img src="images/tt-robot.svg" alt="robot" class="robot-image" />
                 <div class="robot-info">
                     <span class="robot-status">{ state_indicator }</span>
                     if action_count > 0 {
                         <span class="robot-actions">{ format!("({} actions)", action_count) }</span>
                     }
+                    if let Some(note) = retrain_note {
+                        <span class="robot-actions">{ note }</span>
+                    }
+                    if let Some(pattern) = &self.pattern {
+                        { render_thought(pattern) }
+                    }
                 </div>
             </div>
         }
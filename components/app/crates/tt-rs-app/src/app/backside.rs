@@ -0,0 +1,118 @@
+//! Robot backside: the program a robot learned.
+//!
+//! Flipping a robot shows its thought-bubble pattern, its actions in
+//! plain language and the team it leads. While the robot is idle its
+//! actions can be deleted, reordered, or retrained from a step.
+
+use tt_rs_core::{Widget, WidgetId};
+use tt_rs_engine::robot_exec;
+use tt_rs_robot::{Robot, RobotState};
+use yew::prelude::*;
+
+use super::callbacks::Callbacks;
+use crate::ops::ProgramEdit;
+use crate::state::AppState;
+use crate::widget_item::WidgetItem;
+
+/// Renders the backside panel of the flipped robot, if any.
+pub fn render_backside(state: &AppState, cbs: &Callbacks) -> Html {
+    let Some(id) = state.robot_backside else {
+        return html! {};
+    };
+    let Some(WidgetItem::Robot(robot)) = state.engine.widgets.get(&id) else {
+        return html! {};
+    };
+    let on_close = {
+        let cb = cbs.on_backside_close.clone();
+        Callback::from(move |_: MouseEvent| cb.emit(()))
+    };
+
+    html! {
+        <div class="robot-backside">
+            <div class="robot-backside-header">
+                <span>{ format!("Robot {} (backside)", id) }</span>
+                <button class="robot-backside-close" onclick={on_close}>{ "×" }</button>
+            </div>
+            <h4>{ "Thought bubble" }</h4>
+            { render_pattern(robot) }
+            <h4>{ "Program" }</h4>
+            { render_program(robot, cbs) }
+            <h4>{ "Team" }</h4>
+            { render_team(state, id, cbs) }
+        </div>
+    }
+}
+
+fn render_pattern(robot: &Robot) -> Html {
+    match robot.pattern() {
+        Some(pattern) => html! {
+            <div class="robot-backside-pattern">{ pattern.render() }</div>
+        },
+        None => html! {
+            <p class="robot-backside-note">{ "No box: runs when clicked." }</p>
+        },
+    }
+}
+
+fn render_program(robot: &Robot, cbs: &Callbacks) -> Html {
+    if robot.actions().is_empty() {
+        return html! { <p class="robot-backside-note">{ "Not trained yet." }</p> };
+    }
+    let editable = robot.state() == RobotState::Idle;
+    let last = robot.actions().len() - 1;
+    let steps = robot_exec::describe_program(robot.actions());
+    let edit_button =
+        |label: &'static str, title: &'static str, edit: ProgramEdit, enabled: bool| {
+            let cb = cbs.on_program_edit.clone();
+            let onclick = Callback::from(move |_: MouseEvent| cb.emit(edit));
+            html! { <button {title} {onclick} disabled={!editable || !enabled}>{ label }</button> }
+        };
+
+    html! {
+        <>
+            if let Some(step) = robot.retrain_at() {
+                <p class="robot-backside-note">
+                    { format!("Give the robot a box to retrain from step {}.", step + 1) }
+                </p>
+            }
+            <ol class="robot-backside-program">
+                { for steps.into_iter().enumerate().map(|(i, step)| html! {
+                    <li>
+                        <span class="robot-backside-action">{ step }</span>
+                        { edit_button("↑", "Move up", ProgramEdit::Move { from: i, to: i.saturating_sub(1) }, i > 0) }
+                        { edit_button("↓", "Move down", ProgramEdit::Move { from: i, to: i + 1 }, i < last) }
+                        { edit_button("✕", "Delete this step", ProgramEdit::Delete(i), true) }
+                        { edit_button("Retrain", "Keep the steps before this one and train the rest again", ProgramEdit::RetrainFrom(i), true) }
+                    </li>
+                }) }
+            </ol>
+        </>
+    }
+}
+
+/// The team from this robot down: who gets the boxes it doesn't match.
+fn render_team(state: &AppState, id: WidgetId, cbs: &Callbacks) -> Html {
+    let members = robot_exec::team(&state.engine, id);
+    if members.len() < 2 {
+        return html! { <p class="robot-backside-note">{ "Works alone." }</p> };
+    }
+    html! {
+        <ol class="robot-backside-team">
+            { for members.into_iter().map(|member| {
+                let actions = match state.engine.widgets.get(&member) {
+                    Some(WidgetItem::Robot(r)) => r.actions().len(),
+                    _ => 0,
+                };
+                let cb = cbs.on_robot_flip.clone();
+                let onclick = Callback::from(move |_: MouseEvent| cb.emit(member));
+                html! {
+                    <li>
+                        <button class="robot-backside-member" {onclick} disabled={member == id}>
+                            { format!("Robot {} ({} actions)", member, actions) }
+                        </button>
+                    </li>
+                }
+            }) }
+        </ol>
+    }
+}
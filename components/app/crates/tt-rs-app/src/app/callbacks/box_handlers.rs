@@ -6,7 +6,7 @@ use tt_rs_core::WidgetId;
 use tt_rs_drag::{DragEndEvent, DragStartEvent, DropEvent};
 use yew::prelude::*;
 
-use crate::ops::handle_box_drop;
+use crate::ops::{handle_box_drop, handle_pointer_click};
 use crate::state::AppState;
 
 pub fn create_box_drag_start(
@@ -36,6 +36,7 @@ pub fn create_box_drop(
 ) -> Callback<DropEvent> {
     Callback::from(move |e: DropEvent| {
         let mut s = (*state).clone();
+        handle_pointer_click(&mut s, &e);
         handle_box_drop(&mut s, &e, p.borrow_mut().take());
         state.set(s);
         // Box operations modify content
@@ -7,11 +7,13 @@ use std::cell::RefCell;
 use std::rc::Rc;
 use tt_rs_core::WidgetId;
 use tt_rs_drag::{CopySourceClickEvent, DragEndEvent, DragStartEvent, DropEvent, Position};
-use tt_rs_ui::{SaveFormData, UserLevel};
+use tt_rs_ui::{RobotSpeed, SaveFormData, UserLevel};
 use yew::prelude::*;
 
 use super::PendingAction;
 use crate::demo_runner::{resolve_steps, DemoState, WORKSPACE_OFFSET_Y};
+use crate::ops::{edit_robot_program, leave_house, toggle_sensors, ProgramEdit};
+use crate::robot_watch;
 use crate::routing::{set_route, Route};
 use crate::state::{default_notes_for_level, AppState};
 
@@ -37,6 +39,18 @@ pub struct Callbacks {
     pub on_text_pane_change: Callback<String>,
     pub on_text_pane_resize: Callback<(f64, f64)>,
     pub on_text_pane_move: Callback<Position>,
+    // Robot watch callbacks
+    pub on_robot_speed: Callback<RobotSpeed>,
+    pub on_robot_pause: Callback<()>,
+    pub on_robot_step: Callback<()>,
+    // Robot backside callbacks
+    pub on_robot_flip: Callback<WidgetId>,
+    pub on_backside_close: Callback<()>,
+    pub on_program_edit: Callback<ProgramEdit>,
+    // House callbacks
+    pub on_house_leave: Callback<()>,
+    // Sensor callbacks
+    pub on_sensors_toggle: Callback<()>,
     // Tutorial action callbacks
     pub on_show_me: Option<Callback<()>>,
     pub on_reset: Option<Callback<()>>,
@@ -181,6 +195,85 @@ pub fn create_callbacks(cfg: CallbackConfig) -> Callbacks {
                 // Moving doesn't make it dirty - it's layout, not content
             })
         },
+        on_robot_speed: {
+            let s = state.clone();
+            Callback::from(move |speed: RobotSpeed| {
+                let mut new_state = (*s).clone();
+                new_state.robot_watch.speed = speed;
+                // Instant runs have no controls, so they can't stay paused
+                if speed == RobotSpeed::Instant {
+                    new_state.robot_watch.paused = false;
+                }
+                s.set(new_state);
+            })
+        },
+        on_robot_pause: {
+            let s = state.clone();
+            Callback::from(move |_| {
+                let mut new_state = (*s).clone();
+                new_state.robot_watch.paused = !new_state.robot_watch.paused;
+                s.set(new_state);
+            })
+        },
+        on_robot_step: {
+            let s = state.clone();
+            let dirty = dirty.clone();
+            Callback::from(move |_| {
+                let mut new_state = (*s).clone();
+                robot_watch::advance(&mut new_state);
+                s.set(new_state);
+                dirty.set(true);
+            })
+        },
+        on_robot_flip: {
+            let s = state.clone();
+            Callback::from(move |id: WidgetId| {
+                let mut new_state = (*s).clone();
+                new_state.robot_backside = (new_state.robot_backside != Some(id)).then_some(id);
+                s.set(new_state);
+            })
+        },
+        on_backside_close: {
+            let s = state.clone();
+            Callback::from(move |_| {
+                let mut new_state = (*s).clone();
+                new_state.robot_backside = None;
+                s.set(new_state);
+            })
+        },
+        on_program_edit: {
+            let s = state.clone();
+            let dirty = dirty.clone();
+            Callback::from(move |edit: ProgramEdit| {
+                let mut new_state = (*s).clone();
+                let Some(id) = new_state.robot_backside else {
+                    return;
+                };
+                if edit_robot_program(&mut new_state, id, edit) {
+                    s.set(new_state);
+                    dirty.set(true);
+                }
+            })
+        },
+        on_house_leave: {
+            let s = state.clone();
+            Callback::from(move |_| {
+                let mut new_state = (*s).clone();
+                if leave_house(&mut new_state) {
+                    s.set(new_state);
+                }
+            })
+        },
+        on_sensors_toggle: {
+            let s = state.clone();
+            let dirty = dirty.clone();
+            Callback::from(move |_| {
+                let mut new_state = (*s).clone();
+                toggle_sensors(&mut new_state);
+                s.set(new_state);
+                dirty.set(true);
+            })
+        },
         // Show Me callback - only present if demo_steps exist
         on_show_me: if state.demo_steps.is_empty() {
             None
@@ -1,90 +1,104 @@
 //! Widget-related callback handlers.
 
-use tt_rs_bird::Bird;
 use tt_rs_core::{Widget, WidgetId};
 use tt_rs_drag::{CopySourceClickEvent, DropEvent, Position};
+use tt_rs_engine::ops;
 use yew::prelude::*;
 
 use crate::ops::{
-    handle_bird_drop, handle_box_hole_drop, handle_drop_on_bird, handle_dropzone_drop,
-    handle_nest_click, handle_nest_drop, handle_number_on_number, handle_robot_click,
-    handle_scales_drop, handle_vacuum_drop, handle_wand_drop,
+    handle_bird_drop, handle_bomb_click, handle_box_hole_drop, handle_drop_on_bird,
+    handle_drop_on_house, handle_drop_on_truck, handle_dropzone_drop, handle_house_click,
+    handle_nest_click, handle_nest_drop, handle_number_on_number, handle_pointer_click,
+    handle_robot_click, handle_robot_on_robot, handle_scales_drop, handle_sensor_click,
+    handle_vacuum_drop, handle_wand_drop,
 };
 use crate::state::AppState;
 use crate::widget_item::WidgetItem;
 
 /// Handle copying from copy sources.
-/// Special case: copying a Nest "hatches" a paired Bird.
+/// Special case: copying a Nest "hatches" a paired Bird, and a Sensor
+/// comes with a nest of its own.
 pub fn create_copy_source(
     state: UseStateHandle<AppState>,
     dirty: UseStateHandle<bool>,
 ) -> Callback<CopySourceClickEvent> {
     Callback::from(move |e: CopySourceClickEvent| {
         let mut s = (*state).clone();
-        let source = s.widgets.get(&e.source_id).cloned();
+        let source = s.engine.widgets.get(&e.source_id).cloned();
         let mut made_change = false;
 
         match source {
             Some(WidgetItem::Number(n)) => {
                 let copy = n.copy_number();
-                s.positions.insert(copy.id(), e.position);
-                s.widgets.insert(copy.id(), WidgetItem::Number(copy));
+                s.engine.positions.insert(copy.id(), e.position);
+                s.engine.widgets.insert(copy.id(), WidgetItem::Number(copy));
                 made_change = true;
             }
             Some(WidgetItem::Nest(n)) => {
                 // ToonTalk "hatching": copying a nest creates nest + paired bird
-                let nest = n.copy_nest();
-                let nest_id = nest.id();
-                let bird = Bird::with_nest(nest_id, nest.color().into());
-                let bird_id = bird.id();
-
-                // Position nest at click location
-                s.positions.insert(nest_id, e.position);
-                s.widgets.insert(nest_id, WidgetItem::Nest(nest));
-
-                // Position bird next to nest
-                s.positions
-                    .insert(bird_id, Position::new(e.position.x + 60.0, e.position.y));
-                s.widgets.insert(bird_id, WidgetItem::Bird(bird));
-
-                log::info!("Hatched: Nest {} with Bird {}", nest_id, bird_id);
+                ops::hatch_nest(&mut s.engine, n.copy_nest(), e.position);
                 made_change = true;
             }
             Some(WidgetItem::Bird(b)) => {
                 // Copying a bird alone creates an unpaired bird (sink)
                 let copy = b.copy_bird();
-                s.positions.insert(copy.id(), e.position);
-                s.widgets.insert(copy.id(), WidgetItem::Bird(copy));
+                s.engine.positions.insert(copy.id(), e.position);
+                s.engine.widgets.insert(copy.id(), WidgetItem::Bird(copy));
                 made_change = true;
             }
             Some(WidgetItem::Scales(sc)) => {
                 let copy = sc.copy_scales();
-                s.positions.insert(copy.id(), e.position);
-                s.widgets.insert(copy.id(), WidgetItem::Scales(copy));
+                s.engine.positions.insert(copy.id(), e.position);
+                s.engine.widgets.insert(copy.id(), WidgetItem::Scales(copy));
                 made_change = true;
             }
             Some(WidgetItem::Vacuum(v)) => {
                 let copy = v.copy_vacuum();
-                s.positions.insert(copy.id(), e.position);
-                s.widgets.insert(copy.id(), WidgetItem::Vacuum(copy));
+                s.engine.positions.insert(copy.id(), e.position);
+                s.engine.widgets.insert(copy.id(), WidgetItem::Vacuum(copy));
                 made_change = true;
             }
             Some(WidgetItem::Wand(w)) => {
                 let copy = w.copy_wand();
-                s.positions.insert(copy.id(), e.position);
-                s.widgets.insert(copy.id(), WidgetItem::Wand(copy));
+                s.engine.positions.insert(copy.id(), e.position);
+                s.engine.widgets.insert(copy.id(), WidgetItem::Wand(copy));
                 made_change = true;
             }
             Some(WidgetItem::Robot(r)) => {
                 let copy = r.copy_robot();
-                s.positions.insert(copy.id(), e.position);
-                s.widgets.insert(copy.id(), WidgetItem::Robot(copy));
+                s.engine.positions.insert(copy.id(), e.position);
+                s.engine.widgets.insert(copy.id(), WidgetItem::Robot(copy));
                 made_change = true;
             }
             Some(WidgetItem::Text(t)) => {
                 let copy = t.copy_text();
-                s.positions.insert(copy.id(), e.position);
-                s.widgets.insert(copy.id(), WidgetItem::Text(copy));
+                s.engine.positions.insert(copy.id(), e.position);
+                s.engine.widgets.insert(copy.id(), WidgetItem::Text(copy));
+                made_change = true;
+            }
+            Some(WidgetItem::House(h)) => {
+                // A new house comes with its own furnished workspace
+                let copy = h.copy_house();
+                let id = copy.id();
+                s.engine.positions.insert(id, e.position);
+                s.engine.widgets.insert(id, WidgetItem::House(copy));
+                s.city.build(id);
+                made_change = true;
+            }
+            Some(WidgetItem::Truck(t)) => {
+                let copy = t.copy_truck();
+                s.engine.positions.insert(copy.id(), e.position);
+                s.engine.widgets.insert(copy.id(), WidgetItem::Truck(copy));
+                made_change = true;
+            }
+            Some(WidgetItem::Bomb(b)) => {
+                let copy = b.copy_bomb();
+                s.engine.positions.insert(copy.id(), e.position);
+                s.engine.widgets.insert(copy.id(), WidgetItem::Bomb(copy));
+                made_change = true;
+            }
+            Some(WidgetItem::Sensor(sensor)) => {
+                ops::hatch_sensor(&mut s.engine, sensor.kind(), e.position);
                 made_change = true;
             }
             Some(WidgetItem::DropZone(_)) => {
@@ -105,7 +119,7 @@ pub fn create_move(
 ) -> Callback<(WidgetId, Position)> {
     Callback::from(move |(id, pos): (WidgetId, Position)| {
         let mut s = (*state).clone();
-        s.positions.insert(id, pos);
+        s.engine.positions.insert(id, pos);
         state.set(s);
         // Moving widgets doesn't make workspace dirty - it's layout, not content
     })
@@ -120,12 +134,21 @@ pub fn create_widget_drop(
         let id = e.widget_id;
         let (mx, my) = (e.mouse_position.x, e.mouse_position.y);
 
+        // Report clicks to pointer sensors before the click does its work
+        let sensed = handle_pointer_click(&mut s, &e);
+
         // These operations modify content, so they make the workspace dirty
         if handle_robot_click(&mut s, id, &e)
+            || handle_robot_on_robot(&mut s, id, mx, my)  // Drop robot ON robot to form a team
             || handle_nest_click(&mut s, id, &e)  // Click on nest to take message
+            || handle_house_click(&mut s, id, &e)  // Click on house to go in
+            || handle_bomb_click(&mut s, id, &e)  // Click on bomb to set it off
+            || handle_sensor_click(&mut s, id, &e)  // Click on sensor to pause or resume it
             || handle_vacuum_drop(&mut s, id, mx, my, &e)
             || handle_wand_drop(&mut s, id, mx, my, &e)
             || handle_drop_on_bird(&mut s, id, mx, my)  // Drop widget ON bird for delivery
+            || handle_drop_on_house(&mut s, id, mx, my)  // Drop widget ON house to carry it in
+            || handle_drop_on_truck(&mut s, id, mx, my)  // Drop robot ON truck to load it
             || handle_bird_drop(&mut s, id, mx, my)
             || handle_nest_drop(&mut s, id, mx, my)
             || handle_scales_drop(&mut s, id, mx, my)
@@ -136,9 +159,12 @@ pub fn create_widget_drop(
             state.set(s);
             dirty.set(true);
         } else {
-            s.positions.insert(id, e.position);
+            s.engine.positions.insert(id, e.position);
             state.set(s);
-            // Just moving doesn't make it dirty
+            // Just moving doesn't make it dirty, but a reported click does
+            if sensed {
+                dirty.set(true);
+            }
         }
     })
 }
@@ -0,0 +1,45 @@
+//! House bar: which house is shown, and which houses are at work.
+//!
+//! Shown only while inside a house or while robots work in houses out of
+//! sight; going in is a click on a house, going out is the Leave button.
+
+use yew::prelude::*;
+
+use super::callbacks::Callbacks;
+use crate::state::AppState;
+
+/// Renders the house bar, if there is anything to say.
+pub fn render_house_bar(state: &AppState, cbs: &Callbacks) -> Html {
+    let city = &state.city;
+    let path = city.path();
+    let running = city.running_houses();
+    if path.is_empty() && running.is_empty() {
+        return html! {};
+    }
+
+    let where_label = std::iter::once("Outdoors".to_string())
+        .chain(path.iter().map(|id| format!("House {}", id)))
+        .collect::<Vec<_>>()
+        .join(" › ");
+    let running_label = running
+        .iter()
+        .map(|id| format!("House {}", id))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let on_leave = {
+        let cb = cbs.on_house_leave.clone();
+        Callback::from(move |_: MouseEvent| cb.emit(()))
+    };
+
+    html! {
+        <div class="house-bar">
+            <span class="house-bar-path">{ where_label }</span>
+            if !running.is_empty() {
+                <span class="house-bar-running">{ format!("Working: {}", running_label) }</span>
+            }
+            if !path.is_empty() {
+                <button class="house-bar-leave" onclick={on_leave}>{ "Leave house" }</button>
+            }
+        </div>
+    }
+}
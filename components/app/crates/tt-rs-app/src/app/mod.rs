@@ -1,16 +1,19 @@
 //! Main application component.
 
+mod backside;
 mod callbacks;
+mod house_bar;
 mod render;
 
 use std::cell::RefCell;
+use std::collections::HashSet;
 use std::rc::Rc;
 use tt_rs_core::WidgetId;
 use tt_rs_ui::{ConfirmDialog, DemoCursor, TooltipLayerProvider, UserLevel, WorkspaceMetadata};
 use wasm_bindgen::{closure::Closure, JsCast};
 use yew::prelude::*;
 
-use crate::demo_runner::DemoState;
+use crate::demo_runner::{DemoState, WORKSPACE_OFFSET_Y};
 use crate::routing::{current_route, Route};
 use crate::state::AppState;
 use crate::widget_item::WidgetItem;
@@ -68,6 +71,23 @@ fn load_route(route: &Route) -> Option<AppState> {
     }
 }
 
+/// Returns true if a key press is one for the keyboard sensors: not
+/// typed into a text field, not a shortcut, and not a lone modifier key.
+fn is_sensed_key(e: &web_sys::KeyboardEvent) -> bool {
+    let editing = e
+        .target()
+        .and_then(|t| t.dyn_into::<web_sys::HtmlElement>().ok())
+        .is_some_and(|el| {
+            matches!(el.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT")
+                || el.is_content_editable()
+        });
+    let modifier = matches!(
+        e.key().as_str(),
+        "Shift" | "Control" | "Alt" | "Meta" | "CapsLock"
+    );
+    !editing && !modifier && !e.ctrl_key() && !e.meta_key() && !e.alt_key()
+}
+
 /// Main application component.
 #[function_component(App)]
 pub fn app() -> Html {
@@ -81,6 +101,14 @@ pub fn app() -> Html {
     let user_level = use_state(UserLevel::default);
     let dragged_box_id = use_mut_ref(|| None::<WidgetId>);
     let pending_new_box = use_mut_ref(|| None::<usize>);
+    // Key presses waiting for the keyboard sensors
+    let pending_keys = use_mut_ref(Vec::<String>::new);
+    // Set when the watched robot's next step is due
+    let robot_step_due = use_mut_ref(|| false);
+    // Set when birds away are due to fly their next step
+    let flight_step_due = use_mut_ref(|| false);
+    // Re-renders so the queued work effect sees the work (see below)
+    let work_queued = use_force_update();
 
     // Track whether the workspace has been modified
     let dirty = use_state(|| false);
@@ -156,7 +184,7 @@ pub fn app() -> Html {
                                             let mut new_app_state =
                                                 (*app_state_for_timeout).clone();
                                             let pos = tt_rs_drag::Position::new(*x, *y);
-                                            new_app_state.positions.insert(widget_id, pos);
+                                            new_app_state.engine.positions.insert(widget_id, pos);
                                             app_state_for_timeout.set(new_app_state);
                                             log::info!(
                                                 "Demo MoveTo: moved widget {:?} to ({}, {})",
@@ -227,6 +255,87 @@ pub fn app() -> Html {
         });
     }
 
+    // Watched robot effect - performs one robot action per tick
+    {
+        let due = robot_step_due.clone();
+        let work_queued = work_queued.clone();
+        // Robots in other houses change nothing shown, so also watch the city
+        let deps = (
+            state.robot_watch.clone(),
+            state.city.is_running(),
+            state.city.ticks(),
+        );
+        use_effect_with(deps, move |(watch, elsewhere, _)| {
+            let cleanup_handle: Rc<RefCell<Option<i32>>> = Rc::new(RefCell::new(None));
+
+            if watch.is_playing(*elsewhere) {
+                let window = web_sys::window().unwrap();
+                let closure = Closure::once(Box::new(move || {
+                    *due.borrow_mut() = true;
+                    work_queued.force_update();
+                }) as Box<dyn FnOnce()>);
+
+                let handle = window
+                    .set_timeout_with_callback_and_timeout_and_arguments_0(
+                        closure.as_ref().unchecked_ref(),
+                        watch.speed.delay_ms() as i32,
+                    )
+                    .unwrap();
+                closure.forget();
+
+                *cleanup_handle.borrow_mut() = Some(handle);
+            }
+
+            move || {
+                if let Some(h) = cleanup_handle.borrow_mut().take() {
+                    if let Some(window) = web_sys::window() {
+                        window.clear_timeout_with_handle(h);
+                    }
+                }
+            }
+        });
+    }
+
+    // Bird flight effect - moves birds that are away one step per tick
+    {
+        let due = flight_step_due.clone();
+        let work_queued = work_queued.clone();
+        let deps = (
+            crate::robot_watch::is_flying(&state),
+            state.engine.flights.clone(),
+            state.city.flight_steps(),
+        );
+        use_effect_with(deps, move |(flying, _, _)| {
+            let cleanup_handle: Rc<RefCell<Option<i32>>> = Rc::new(RefCell::new(None));
+
+            if *flying {
+                let window = web_sys::window().unwrap();
+                let closure = Closure::once(Box::new(move || {
+                    *due.borrow_mut() = true;
+                    work_queued.force_update();
+                }) as Box<dyn FnOnce()>);
+
+                let handle = window
+                    .set_timeout_with_callback_and_timeout_and_arguments_0(
+                        closure.as_ref().unchecked_ref(),
+                        crate::robot_watch::FLIGHT_STEP_MS as i32,
+                    )
+                    .unwrap();
+                closure.forget();
+
+                *cleanup_handle.borrow_mut() = Some(handle);
+            }
+
+            move || {
+                if let Some(h) = cleanup_handle.borrow_mut().take() {
+                    if let Some(window) = web_sys::window() {
+                        window.clear_timeout_with_handle(h);
+                    }
+                }
+            }
+        });
+    }
+
     // Set up hashchange listener for URL navigation
     {
         let state = state.clone();
@@ -253,10 +362,14 @@ pub fn app() -> Html {
     }
 
     // Set up keydown listener using use_effect_with directly in the component
-    // This ensures the hook is properly registered with Yew's hook system
+    // This ensures the hook is properly registered with Yew's hook system.
+    // While a box is dragged, digits set its hole count; otherwise key
+    // presses are queued for the keyboard sensors (see the effect below).
     {
         let dragged = dragged_box_id.clone();
         let pending = pending_new_box.clone();
+        let keys = pending_keys.clone();
+        let work_queued = work_queued.clone();
         use_effect_with((), move |_| {
             let window = web_sys::window().unwrap();
             let cb = Closure::wrap(Box::new(move |e: web_sys::KeyboardEvent| {
@@ -265,6 +378,9 @@ pub fn app() -> Html {
                         *pending.borrow_mut() = Some(c.to_digit(10).unwrap() as usize);
                         e.prevent_default();
                     }
+                } else if is_sensed_key(&e) {
+                    keys.borrow_mut().push(e.key());
+                    work_queued.force_update();
                 }
             }) as Box<dyn FnMut(_)>);
             window
@@ -279,6 +395,46 @@ pub fn app() -> Html {
         });
     }
 
+    // Queued work effect - feeds queued key presses to the shown
+    // workspace's keyboard sensors, takes the watched robot's due step
+    // and flies birds that are away. The listener and timers above
+    // can't see the current state (a snapshot would undo whatever the
+    // user did meanwhile), so they queue their work and re-render; this
+    // runs with the fresh state.
+    {
+        let app_state = state.clone();
+        let dirty = dirty.clone();
+        let keys = pending_keys.clone();
+        let robot_step_due = robot_step_due.clone();
+        let flight_step_due = flight_step_due.clone();
+        use_effect(move || {
+            let keys: Vec<String> = keys.borrow_mut().drain(..).collect();
+            let robot_step = robot_step_due.replace(false);
+            let flight_step = flight_step_due.replace(false);
+            if !keys.is_empty() || robot_step || flight_step {
+                let mut new_state = (*app_state).clone();
+                let mut changed = robot_step;
+                for key in &keys {
+                    changed |= crate::ops::handle_key_press(&mut new_state, key);
+                }
+                if robot_step {
+                    crate::robot_watch::advance(&mut new_state);
+                }
+                if flight_step {
+                    crate::robot_watch::fly(&mut new_state);
+                }
+                if changed {
+                    app_state.set(new_state);
+                    dirty.set(true);
+                } else if flight_step {
+                    // A bird landing doesn't make the workspace dirty
+                    app_state.set(new_state);
+                }
+            }
+            || ()
+        });
+    }
+
     let cbs = callbacks::create_callbacks(callbacks::CallbackConfig {
         state: state.clone(),
         help_open: help_open.clone(),
@@ -381,6 +537,15 @@ pub fn app() -> Html {
     // Get demo state values for rendering
     let ds = (*demo_state).clone();
 
+    // The watched robot's hand points at its next action's target
+    let robot_cursor = crate::robot_watch::target_center(&state).map(|(x, y)| {
+        let holding = state
+            .robot_watch
+            .watched()
+            .is_some_and(|run| run.is_holding());
+        (x, y + WORKSPACE_OFFSET_Y, holding)
+    });
+
     html! {
         <TooltipLayerProvider>
             { render::render_app(&state, *help_open, *workspace_open, *user_level, &cbs, &planes, &workspaces) }
@@ -403,6 +568,15 @@ pub fn app() -> Html {
                     transition_duration={ds.transition_ms}
                 />
             }
+            // Show the watched robot's hand at its next target
+            if let Some((x, y, holding)) = robot_cursor {
+                <DemoCursor
+                    x={x}
+                    y={y}
+                    is_dragging={holding}
+                    transition_duration={state.robot_watch.speed.delay_ms()}
+                />
+            }
         </TooltipLayerProvider>
     }
 }
@@ -417,7 +591,7 @@ pub struct ZPlanes<'a> {
     pub dropzones: WidgetRefs<'a>,
     /// Plane 1: Values (numbers, text)
     pub values: WidgetRefs<'a>,
-    /// Plane 2: Agents and comparison (robot, bird, nest, scales)
+    /// Plane 2: Agents and comparison (robot, bird, nest, house, truck, bomb, scales)
     pub agents: WidgetRefs<'a>,
     /// Plane 3: Tools (vacuum, wand) - highest z-index for regular widgets
     pub tools: WidgetRefs<'a>,
@@ -426,20 +600,41 @@ pub struct ZPlanes<'a> {
 fn partition_into_planes(state: &AppState, level: UserLevel) -> ZPlanes<'_> {
     let is_visible = |w: &WidgetItem| -> bool {
         match level {
-            UserLevel::Tt1 => !matches!(w, WidgetItem::Bird(_) | WidgetItem::Nest(_)),
+            UserLevel::Tt1 => !matches!(
+                w,
+                WidgetItem::Bird(_)
+                    | WidgetItem::Nest(_)
+                    | WidgetItem::House(_)
+                    | WidgetItem::Truck(_)
+                    | WidgetItem::Bomb(_)
+                    | WidgetItem::Sensor(_)
+            ),
             UserLevel::Tt2 => true,
         }
     };
 
-    let not_in_box = |id: &WidgetId| !state.widget_in_box.contains_key(id);
+    let not_in_box = |id: &WidgetId| !state.engine.widget_in_box.contains_key(id);
+    // Team followers are drawn as a stack behind their lead robot
+    let followers: HashSet<WidgetId> = state
+        .engine
+        .widgets
+        .values()
+        .filter_map(|w| match w {
+            WidgetItem::Robot(r) => r.next_robot(),
+            _ => None,
+        })
+        .collect();
+    let not_follower = |id: &WidgetId| !followers.contains(id);
 
     let copy_sources: Vec<_> = state
+        .engine
         .widgets
         .iter()
         .filter(|(id, w)| not_in_box(id) && is_visible(w) && w.is_copy_source())
         .collect();
 
     let dropzones: Vec<_> = state
+        .engine
         .widgets
         .iter()
         .filter(|(id, w)| {
@@ -451,6 +646,7 @@ fn partition_into_planes(state: &AppState, level: UserLevel) -> ZPlanes<'_> {
         .collect();
 
     let values: Vec<_> = state
+        .engine
         .widgets
         .iter()
         .filter(|(id, w)| {
@@ -462,10 +658,12 @@ fn partition_into_planes(state: &AppState, level: UserLevel) -> ZPlanes<'_> {
         .collect();
 
     let agents: Vec<_> = state
+        .engine
         .widgets
         .iter()
         .filter(|(id, w)| {
             not_in_box(id)
+                && not_follower(id)
                 && is_visible(w)
                 && !w.is_copy_source()
                 && matches!(
@@ -473,12 +671,17 @@ fn partition_into_planes(state: &AppState, level: UserLevel) -> ZPlanes<'_> {
                     WidgetItem::Robot(_)
                         | WidgetItem::Bird(_)
                         | WidgetItem::Nest(_)
+                        | WidgetItem::House(_)
+                        | WidgetItem::Truck(_)
+                        | WidgetItem::Bomb(_)
+                        | WidgetItem::Sensor(_)
                         | WidgetItem::Scales(_)
                 )
         })
         .collect();
 
     let tools: Vec<_> = state
+        .engine
         .widgets
         .iter()
         .filter(|(id, w)| {
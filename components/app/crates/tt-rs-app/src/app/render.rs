@@ -6,10 +6,11 @@
 //! Widgets within each plane have pointer-events: auto to receive events.
 
 use tt_rs_core::WidgetId;
-use tt_rs_drag::{CopySource, Draggable, DropEvent, Position};
+use tt_rs_drag::{CopySource, Draggable, Position};
 use tt_rs_ui::{
-    Footer, HelpButton, HelpPanel, TextPane, Tooltip, TooltipLayer, TooltipPosition, UserLevel,
-    UserLevelSelector, WorkspaceButton, WorkspaceMenu, WorkspaceMetadata,
+    Footer, HelpButton, HelpPanel, RobotSpeed, RobotWatchPanel, TextPane, Tooltip, TooltipLayer,
+    TooltipPosition, UserLevel, UserLevelSelector, WorkspaceButton, WorkspaceMenu,
+    WorkspaceMetadata,
 };
 use wasm_bindgen::JsCast;
 use yew::prelude::*;
@@ -18,7 +19,7 @@ use super::callbacks::Callbacks;
 use super::ZPlanes;
 use crate::box_state::render_box;
 use crate::state::AppState;
-use crate::widget_item::WidgetItem;
+use crate::widget_item::{render as render_widget, tooltip_info, WidgetItem};
 
 /// Z-plane indices for guaranteed stacking order.
 /// Higher numbers appear on top.
@@ -46,6 +47,7 @@ pub fn render_app(
             <div class="workspace-header">
                 <span class="header-title">{"tt-rs - Visual Programming Environment"}</span>
                 <WorkspaceButton on_click={cbs.on_workspace_open.clone()} />
+                { render_sensors_switch(state, user_level, cbs) }
                 <UserLevelSelector level={user_level} on_change={cbs.on_level_change.clone()} />
             </div>
             <HelpButton on_click={cbs.on_help_open.clone()} />
@@ -61,19 +63,22 @@ pub fn render_app(
                 current_level={user_level}
                 workspaces={workspaces.to_vec()}
             />
+            { render_robot_watch(state, cbs) }
+            { super::backside::render_backside(state, cbs) }
+            { super::house_bar::render_house_bar(state, cbs) }
             <div class="workspace-content">
                 // Z-plane 0: Copy source stacks (lowest)
                 { render_z_plane(Z_PLANE_STACKS, render_copy_sources(&planes.copy_sources, state, &cbs.on_copy_source_click, &cbs.on_move)) }
                 // Z-plane 50: Drop zones (below draggable items)
-                { render_z_plane(Z_PLANE_DROPZONES, render_widgets(&planes.dropzones, state, &cbs.on_move, &cbs.on_drop)) }
+                { render_z_plane(Z_PLANE_DROPZONES, render_widgets(&planes.dropzones, state, cbs)) }
                 // Z-plane 100: Boxes
                 { render_z_plane(Z_PLANE_BOXES, render_boxes(state, cbs)) }
                 // Z-plane 200: Values (numbers, text)
-                { render_z_plane(Z_PLANE_VALUES, render_widgets(&planes.values, state, &cbs.on_move, &cbs.on_drop)) }
-                // Z-plane 300: Agents (robot, bird, nest, scales)
-                { render_z_plane(Z_PLANE_AGENTS, render_widgets(&planes.agents, state, &cbs.on_move, &cbs.on_drop)) }
+                { render_z_plane(Z_PLANE_VALUES, render_widgets(&planes.values, state, cbs)) }
+                // Z-plane 300: Agents (robot, bird, nest, house, truck, bomb, sensor, scales)
+                { render_z_plane(Z_PLANE_AGENTS, render_widgets(&planes.agents, state, cbs)) }
                 // Z-plane 400: Tools (vacuum, wand)
-                { render_z_plane(Z_PLANE_TOOLS, render_widgets(&planes.tools, state, &cbs.on_move, &cbs.on_drop)) }
+                { render_z_plane(Z_PLANE_TOOLS, render_widgets(&planes.tools, state, cbs)) }
                 // Z-plane 450: Text pane (workspace notes)
                 { render_z_plane(Z_PLANE_TEXT_PANE, render_text_pane(state, cbs)) }
                 // Z-plane 500: Tooltips (highest)
@@ -84,6 +89,66 @@ pub fn render_app(
     }
 }
 
+/// Renders the switch that turns the shown workspace's sensors on or off.
+/// Sensors only exist at tt2, so neither does the switch.
+fn render_sensors_switch(state: &AppState, user_level: UserLevel, cbs: &Callbacks) -> Html {
+    if user_level == UserLevel::Tt1 {
+        return html! {};
+    }
+    let on = state.engine.sensors_on;
+    let onclick = {
+        let cb = cbs.on_sensors_toggle.clone();
+        Callback::from(move |_: MouseEvent| cb.emit(()))
+    };
+    html! {
+        <button class={classes!("sensors-switch", (!on).then_some("off"))}
+                title="Turn the sensors in this workspace on or off"
+                {onclick}>
+            { if on { "Sensors: on" } else { "Sensors: off" } }
+        </button>
+    }
+}
+
+/// Renders the robot speed slider, the robot processes and the watched
+/// run's actions.
+fn render_robot_watch(state: &AppState, cbs: &Callbacks) -> Html {
+    let watch = &state.robot_watch;
+    let (actions, current) = match watch.watched() {
+        Some(run) if watch.speed != RobotSpeed::Instant => (
+            run.actions()
+                .iter()
+                .map(|a| AttrValue::from(a.to_string()))
+                .collect(),
+            run.position(),
+        ),
+        _ => (Vec::new(), 0),
+    };
+    let processes = watch
+        .scheduler
+        .processes()
+        .iter()
+        .map(|p| {
+            let label = match p.input() {
+                Some(box_id) => format!("Robot {} on box {}", p.lead(), box_id),
+                None => format!("Robot {}", p.lead()),
+            };
+            (AttrValue::from(label), AttrValue::from(p.state().name()))
+        })
+        .collect::<Vec<_>>();
+    html! {
+        <RobotWatchPanel
+            speed={watch.speed}
+            on_speed={cbs.on_robot_speed.clone()}
+            actions={actions}
+            current={current}
+            processes={processes}
+            paused={watch.paused}
+            on_pause={cbs.on_robot_pause.clone()}
+            on_step={cbs.on_robot_step.clone()}
+        />
+    }
+}
+
 /// Renders a z-plane container with the given z-index.
 /// The plane itself is transparent and doesn't capture mouse events,
 /// but its children (the widgets) do.
@@ -100,12 +165,12 @@ fn render_z_plane(z_index: i32, children: Html) -> Html {
 }
 
 fn render_boxes(state: &AppState, cbs: &Callbacks) -> Html {
-    state.boxes.iter().map(|(id, b)| {
-        let pos = state.positions.get(id).copied().unwrap_or_default();
+    state.engine.boxes.iter().map(|(id, b)| {
+        let pos = state.engine.positions.get(id).copied().unwrap_or_default();
         html! {
             <Draggable widget_id={*id} position={pos} on_move={cbs.on_move.clone()} on_drag_start={cbs.on_box_drag_start.clone()} on_drag_end={cbs.on_box_drag_end.clone()} on_drop={cbs.on_box_drop.clone()}>
                 <Tooltip title="Box" description="A container with holes for storing items." hint="Drag items into holes. Drop on number to split. Press 0-9 while dragging to create copy with that many holes." position={TooltipPosition::Right}>
-                    { render_box(b, &state.widgets) }
+                    { render_box(b, &state.engine.widgets) }
                 </Tooltip>
             </Draggable>
         }
@@ -119,33 +184,49 @@ fn render_copy_sources(
     on_move: &Callback<(WidgetId, Position)>,
 ) -> Html {
     srcs.iter().map(|(id, w)| {
-        let pos = state.positions.get(id).copied().unwrap_or_default();
-        let tip = w.tooltip_info();
+        let pos = state.engine.positions.get(id).copied().unwrap_or_default();
+        let tip = tooltip_info(w);
         html! {
             <CopySource widget_id={**id} position={pos} on_click={on_click.clone()} on_move={on_move.clone()}>
-                <Tooltip title={tip.title} description={tip.description} hint={tip.hint} position={TooltipPosition::Right}>{ w.render() }</Tooltip>
+                <Tooltip title={tip.title} description={tip.description} hint={tip.hint} position={TooltipPosition::Right}>{ render_widget(w) }</Tooltip>
             </CopySource>
         }
     }).collect()
 }
 
-fn render_widgets(
-    ws: &[(&WidgetId, &WidgetItem)],
-    state: &AppState,
-    on_move: &Callback<(WidgetId, Position)>,
-    on_drop: &Callback<DropEvent>,
-) -> Html {
+fn render_widgets(ws: &[(&WidgetId, &WidgetItem)], state: &AppState, cbs: &Callbacks) -> Html {
     ws.iter().map(|(id, w)| {
-        let pos = state.positions.get(id).copied().unwrap_or_default();
-        let tip = w.tooltip_info();
+        let pos = state.engine.positions.get(id).copied().unwrap_or_default();
+        let tip = tooltip_info(w);
         html! {
-            <Draggable widget_id={**id} position={pos} on_move={on_move.clone()} on_drop={on_drop.clone()}>
-                <Tooltip title={tip.title} description={tip.description} hint={tip.hint} position={TooltipPosition::Right}>{ w.render() }</Tooltip>
+            <Draggable widget_id={**id} position={pos} on_move={cbs.on_move.clone()} on_drop={cbs.on_drop.clone()}>
+                <Tooltip title={tip.title} description={tip.description} hint={tip.hint} position={TooltipPosition::Right}>{ render_widget(w) }</Tooltip>
+                if w.is_robot() {
+                    { render_flip_button(**id, &cbs.on_robot_flip) }
+                }
             </Draggable>
         }
     }).collect()
 }
 
+/// Button that turns a robot around to show its backside.
+/// It swallows mouse-down so pressing it doesn't start a drag.
+fn render_flip_button(id: WidgetId, on_flip: &Callback<WidgetId>) -> Html {
+    let on_mouse_down = Callback::from(|e: MouseEvent| e.stop_propagation());
+    let on_click = {
+        let on_flip = on_flip.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.stop_propagation();
+            on_flip.emit(id);
+        })
+    };
+    html! {
+        <button class="robot-flip" title="Flip the robot to see its program" onmousedown={on_mouse_down} onclick={on_click}>
+            { "↻" }
+        </button>
+    }
+}
+
 /// Renders the draggable text pane for workspace notes.
 fn render_text_pane(state: &AppState, cbs: &Callbacks) -> Html {
     let pos = state.text_pane_position;
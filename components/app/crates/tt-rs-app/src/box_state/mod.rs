@@ -1,7 +1,5 @@
-//! Box state management.
+//! Box rendering (box state itself lives in the engine).
 
 mod render;
-mod state;
 
 pub use render::render_box;
-pub use state::BoxState;
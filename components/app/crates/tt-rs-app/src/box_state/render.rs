@@ -2,9 +2,9 @@
 
 use std::collections::HashMap;
 use tt_rs_core::WidgetId;
+use tt_rs_engine::BoxState;
 use yew::prelude::*;
 
-use super::BoxState;
 use crate::widget_item::{render_small, WidgetItem};
 
 /// Render a box with its contents.
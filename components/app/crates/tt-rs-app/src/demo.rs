@@ -4,20 +4,24 @@
 //! - Col 1: Number stacks (+1, +5, -1, *2, /2)
 //! - Col 2: Boxes (2-hole, 3-hole)
 //! - Col 3: tt1 tools (0, Scales, Vacuum, Wand, Robot)
-//! - Col 4: tt2 tools (Nest) - only visible in tt2 mode
+//! - Col 4: tt2 tools (Nest, House, Truck, Bomb, Sensors) - only visible in tt2 mode
 //! - Col 5+: Reserved for future tt3, tt4 tools
 
 use std::collections::HashMap;
+use tt_rs_bomb::Bomb;
 use tt_rs_core::WidgetId;
 use tt_rs_drag::Position;
+use tt_rs_engine::{BoxState, EngineState};
+use tt_rs_house::House;
 use tt_rs_nest::Nest;
 use tt_rs_number::{ArithOperator, Number};
 use tt_rs_robot::Robot;
 use tt_rs_scales::Scales;
+use tt_rs_sensors::{Sensor, SensorKind};
+use tt_rs_truck::Truck;
 use tt_rs_vacuum::Vacuum;
 use tt_rs_wand::Wand;
 
-use crate::box_state::BoxState;
 use crate::widget_item::WidgetItem;
 
 // Layout constants
@@ -28,6 +32,19 @@ const COL_BOXES: f64 = 130.0;
 const COL_TT1_TOOLS: f64 = 300.0;
 const COL_TT2_TOOLS: f64 = 400.0;
 
+/// A workspace holding just the palette: the sandbox, and every new house.
+pub fn furnished_engine() -> EngineState {
+    let (widgets, mut positions) = init_widgets();
+    let (boxes, box_positions) = init_boxes();
+    positions.extend(box_positions);
+
+    let mut engine = EngineState::new();
+    engine.widgets = widgets;
+    engine.boxes = boxes;
+    engine.positions = positions;
+    engine
+}
+
 /// Initialize demo widgets and positions.
 pub fn init_widgets() -> (HashMap<WidgetId, WidgetItem>, HashMap<WidgetId, Position>) {
     let mut widgets = HashMap::new();
@@ -47,7 +64,7 @@ pub fn init_widgets() -> (HashMap<WidgetId, WidgetItem>, HashMap<WidgetId, Posit
         widgets.insert(w.id(), w);
     }
 
-    // Column 4: tt2 tools (Nest - only visible in tt2 mode)
+    // Column 4: tt2 tools (Nest, House, Truck, Bomb, Sensors - only visible in tt2 mode)
     for (i, w) in tt2_tools().into_iter().enumerate() {
         let pos = Position::new(COL_TT2_TOOLS, START_Y + (i as f64) * ROW_SPACING);
         positions.insert(w.id(), pos);
@@ -93,7 +110,14 @@ fn tt1_tools() -> Vec<WidgetItem> {
 
 fn tt2_tools() -> Vec<WidgetItem> {
     // Note: Bird is NOT a copy source - birds are created by "hatching" (copying) a nest
-    vec![WidgetItem::Nest(Nest::new().as_copy_source())]
+    vec![
+        WidgetItem::Nest(Nest::new().as_copy_source()),
+        WidgetItem::House(House::new().as_copy_source()),
+        WidgetItem::Truck(Truck::new().as_copy_source()),
+        WidgetItem::Bomb(Bomb::new().as_copy_source()),
+        WidgetItem::Sensor(Sensor::new(SensorKind::Keyboard).as_copy_source()),
+        WidgetItem::Sensor(Sensor::new(SensorKind::Pointer).as_copy_source()),
+    ]
 }
 
 fn arith_tool(v: i64, op: ArithOperator) -> Number {
@@ -22,46 +22,52 @@ const DROPZONE_HEIGHT: f64 = 80.0;
 /// Returns None if the target cannot be found.
 pub fn resolve_target(target: &DemoTarget, state: &AppState) -> Option<(f64, f64)> {
     match target {
-        DemoTarget::Widget { name } => {
-            // Look up widget by name
-            let widget_id = state.widget_names.get(name)?;
-            let pos = state.positions.get(widget_id)?;
-            // Return center of widget
-            Some((pos.x + WIDGET_WIDTH / 2.0, pos.y + WIDGET_HEIGHT / 2.0))
-        }
-        DemoTarget::Box { name } => {
-            // Look up box by name
-            let box_id = state.box_names.get(name)?;
-            let pos = state.positions.get(box_id)?;
-            let box_state = state.boxes.get(box_id)?;
-            // Return center of box
-            let box_width = box_state.num_holes as f64 * BOX_HOLE_WIDTH;
-            Some((pos.x + box_width / 2.0, pos.y + BOX_HEIGHT / 2.0))
-        }
+        DemoTarget::Widget { name } => widget_center(state, *state.widget_names.get(name)?),
+        DemoTarget::Box { name } => box_center(state, *state.box_names.get(name)?),
         DemoTarget::BoxHole { name, hole } => {
-            // Look up box by name, then find specific hole center
-            let box_id = state.box_names.get(name)?;
-            let pos = state.positions.get(box_id)?;
-            let box_state = state.boxes.get(box_id)?;
-            // Verify hole index is valid
-            if *hole >= box_state.num_holes {
-                return None;
-            }
-            // Calculate hole center (holes are arranged horizontally)
-            let hole_x = pos.x + (*hole as f64 + 0.5) * BOX_HOLE_WIDTH;
-            let hole_y = pos.y + BOX_HEIGHT / 2.0;
-            Some((hole_x, hole_y))
+            hole_center(state, *state.box_names.get(name)?, *hole)
         }
         DemoTarget::DropZone { role } => {
             // Look up dropzone by role
             let dropzone_id = state.dropzone_roles.get(role)?;
-            let pos = state.positions.get(dropzone_id)?;
+            let pos = state.engine.positions.get(dropzone_id)?;
             // Return center of dropzone
             Some((pos.x + DROPZONE_WIDTH / 2.0, pos.y + DROPZONE_HEIGHT / 2.0))
         }
     }
 }
 
+/// Center of a widget, box, or the hole a widget sits in.
+pub fn widget_center(state: &AppState, id: WidgetId) -> Option<(f64, f64)> {
+    if state.engine.boxes.contains_key(&id) {
+        return box_center(state, id);
+    }
+    if let Some(&(box_id, hole)) = state.engine.widget_in_box.get(&id) {
+        return hole_center(state, box_id, hole);
+    }
+    let pos = state.engine.positions.get(&id)?;
+    Some((pos.x + WIDGET_WIDTH / 2.0, pos.y + WIDGET_HEIGHT / 2.0))
+}
+
+/// Center of a box.
+pub fn box_center(state: &AppState, box_id: WidgetId) -> Option<(f64, f64)> {
+    let pos = state.engine.positions.get(&box_id)?;
+    let box_state = state.engine.boxes.get(&box_id)?;
+    let box_width = box_state.num_holes as f64 * BOX_HOLE_WIDTH;
+    Some((pos.x + box_width / 2.0, pos.y + BOX_HEIGHT / 2.0))
+}
+
+/// Center of a box hole (holes are arranged horizontally).
+pub fn hole_center(state: &AppState, box_id: WidgetId, hole: usize) -> Option<(f64, f64)> {
+    let pos = state.engine.positions.get(&box_id)?;
+    let box_state = state.engine.boxes.get(&box_id)?;
+    if hole >= box_state.num_holes {
+        return None;
+    }
+    let hole_x = pos.x + (hole as f64 + 0.5) * BOX_HOLE_WIDTH;
+    Some((hole_x, pos.y + BOX_HEIGHT / 2.0))
+}
+
 /// Find a widget at the given screen coordinates.
 /// Returns (widget_id, is_box) if found.
 pub fn find_widget_at(x: f64, y: f64) -> Option<(WidgetId, bool)> {
@@ -94,16 +100,17 @@ pub fn perform_drop(
             // Dropping onto a box - try to find which hole
             if let Some(hole_index) = find_box_hole_at(&new_state, target_id, x, y) {
                 // Put widget in the box hole
-                if let Some(box_state) = new_state.boxes.get_mut(&target_id) {
+                if let Some(box_state) = new_state.engine.boxes.get_mut(&target_id) {
                     // Only insert if hole is empty, using entry API
                     use std::collections::hash_map::Entry;
                     if let Entry::Vacant(e) = box_state.contents.entry(hole_index) {
                         e.insert(dragged_id);
                         new_state
+                            .engine
                             .widget_in_box
                             .insert(dragged_id, (target_id, hole_index));
                         // Remove from free positions since it's now in a box
-                        new_state.positions.remove(&dragged_id);
+                        new_state.engine.positions.remove(&dragged_id);
                         log::info!(
                             "Demo: placed widget {:?} in box {:?} hole {}",
                             dragged_id,
@@ -118,7 +125,7 @@ pub fn perform_drop(
         } else {
             // Check if target is a drop zone
             if let Some(crate::widget_item::WidgetItem::DropZone(_)) =
-                new_state.widgets.get(&target_id)
+                new_state.engine.widgets.get(&target_id)
             {
                 // Use the actual dropzone verification logic
                 log::info!(
@@ -137,8 +144,8 @@ pub fn perform_drop(
 
 /// Find which hole of a box is at the given coordinates.
 fn find_box_hole_at(state: &AppState, box_id: WidgetId, x: f64, _y: f64) -> Option<usize> {
-    let box_pos = state.positions.get(&box_id)?;
-    let box_state = state.boxes.get(&box_id)?;
+    let box_pos = state.engine.positions.get(&box_id)?;
+    let box_state = state.engine.boxes.get(&box_id)?;
 
     // Box holes are approximately 50px wide
     const HOLE_WIDTH: f64 = 50.0;
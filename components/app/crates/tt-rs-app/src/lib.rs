@@ -6,7 +6,7 @@ mod demo;
 mod demo_ops;
 mod demo_runner;
 mod ops;
-mod robot_exec;
+mod robot_watch;
 pub mod routing;
 mod state;
 mod widget_item;
@@ -2,14 +2,14 @@
 
 use tt_rs_core::WidgetId;
 use tt_rs_drag::{DropEvent, Position};
-use tt_rs_hit_test::find_box_hole_at;
-use tt_rs_robot::Action;
+use tt_rs_engine::ops;
+use tt_rs_hit_test::{find_box_hole_at, find_hole_in_box_at};
 
-use super::robot_ops::copy_widget;
+use crate::robot_watch;
 use crate::state::AppState;
-use crate::widget_item::WidgetItem;
 
-/// Handle dropping widget into box hole.
+/// Handle dropping widget into box hole. Dropped on a bird or nest in
+/// the hole, it is given to that bird or nest instead.
 pub fn handle_box_hole_drop(
     state: &mut AppState,
     id: WidgetId,
@@ -21,61 +21,33 @@ pub fn handle_box_hole_drop(
         Some(h) => h,
         None => return false,
     };
-
-    if !state.widgets.contains_key(&id) {
-        return false;
-    }
-
-    eject_existing(state, box_id, hole, event);
-    state.record_action(Action::Drop {
-        path: format!("box:{}:hole:{}", box_id, hole),
-    });
-
-    let place_id = get_widget_to_place(state, id);
-    if let Some(b) = state.boxes.get_mut(&box_id) {
-        b.place_in_hole(hole, place_id);
+    if ops::give_in_hole(&mut state.engine, id, box_id, hole) {
+        robot_watch::wake(state);
+        return true;
     }
-    state.widget_in_box.insert(place_id, (box_id, hole));
-    state.update_scales_in_box(box_id);
-    true
+    let eject_to = Position::new(event.mouse_position.x + 50.0, event.mouse_position.y);
+    ops::drop_in_hole(&mut state.engine, id, box_id, hole, eject_to)
 }
 
-fn eject_existing(state: &mut AppState, box_id: WidgetId, hole: usize, event: &DropEvent) {
-    let existing = state
-        .boxes
-        .get(&box_id)
-        .and_then(|b| b.widget_in_hole(hole));
-    if let Some(old_id) = existing {
-        if let Some(b) = state.boxes.get_mut(&box_id) {
-            b.clear_hole(hole);
-        }
-        state.widget_in_box.remove(&old_id);
-        state.positions.insert(
-            old_id,
-            Position::new(event.mouse_position.x + 50.0, event.mouse_position.y),
-        );
+/// Handle a click on a filled hole of a box: take the content out, or
+/// the top message of a nest there.
+/// A "click" is detected when the box barely moved (< 10 pixels).
+pub fn handle_box_hole_click(state: &mut AppState, event: &DropEvent) -> bool {
+    let (start, end) = (event.start_position, event.position);
+    if ((start.x - end.x).powi(2) + (start.y - end.y).powi(2)).sqrt() >= 10.0 {
+        return false;
     }
-}
+    let box_id = event.widget_id;
+    let (mx, my) = (event.mouse_position.x, event.mouse_position.y);
+    let hole = match find_hole_in_box_at(box_id, mx, my) {
+        Some(h) => h,
+        None => return false,
+    };
 
-fn get_widget_to_place(state: &mut AppState, id: WidgetId) -> WidgetId {
-    let is_tool = matches!(
-        state.widgets.get(&id),
-        Some(
-            WidgetItem::Scales(_)
-                | WidgetItem::Vacuum(_)
-                | WidgetItem::Wand(_)
-                | WidgetItem::Robot(_)
-        )
-    );
+    let at = Position::new(start.x, start.y + 80.0);
+    let taken = ops::pick_from_hole(&mut state.engine, box_id, hole, at).is_some();
 
-    if is_tool {
-        if let Some(w) = state.widgets.get(&id) {
-            let copied = copy_widget(w);
-            let copy_id = copied.id();
-            state.widgets.insert(copy_id, copied);
-            return copy_id;
-        }
-    }
-    state.positions.remove(&id);
-    id
+    // Restore box to its original position (since it was a click, not a drag)
+    state.engine.positions.insert(box_id, start);
+    taken
 }
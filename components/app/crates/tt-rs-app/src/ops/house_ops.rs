@@ -0,0 +1,124 @@
+//! House, truck and bomb operations: go in and out, carry things inside,
+//! load trucks that build new houses, and set off bombs that tear them
+//! down.
+//!
+//! The worlds themselves live in the engine's `City`; the app shows one
+//! at a time in `state.engine` and `state.robot_watch.scheduler`.
+
+use tt_rs_core::WidgetId;
+use tt_rs_drag::DropEvent;
+use tt_rs_engine::city::Loaded;
+use tt_rs_engine::ops;
+use tt_rs_hit_test::find_widget_at_excluding;
+
+use crate::robot_watch;
+use crate::state::AppState;
+use crate::widget_item::WidgetItem;
+
+/// Handle house click: go into the house.
+/// A "click" is detected when the widget barely moved (< 10 pixels).
+pub fn handle_house_click(state: &mut AppState, id: WidgetId, event: &DropEvent) -> bool {
+    if !matches!(state.engine.widgets.get(&id), Some(WidgetItem::House(h)) if !h.is_copy_source()) {
+        return false;
+    }
+
+    let start_pos = event.start_position;
+    let final_pos = event.position;
+    let dist = ((start_pos.x - final_pos.x).powi(2) + (start_pos.y - final_pos.y).powi(2)).sqrt();
+
+    if dist >= 10.0 {
+        return false;
+    }
+
+    // Restore house to its original position (since it was a click, not a drag)
+    state.engine.positions.insert(id, start_pos);
+    enter_house(state, id)
+}
+
+/// Handle bomb click: set it off. A robot in training only remembers
+/// doing so; otherwise the house the user is in comes down.
+pub fn handle_bomb_click(state: &mut AppState, id: WidgetId, event: &DropEvent) -> bool {
+    if !matches!(state.engine.widgets.get(&id), Some(WidgetItem::Bomb(b)) if !b.is_copy_source()) {
+        return false;
+    }
+
+    let start_pos = event.start_position;
+    let final_pos = event.position;
+    let dist = ((start_pos.x - final_pos.x).powi(2) + (start_pos.y - final_pos.y).powi(2)).sqrt();
+
+    if dist >= 10.0 {
+        return false;
+    }
+
+    state.engine.positions.insert(id, start_pos);
+    if !ops::set_off_bomb(&mut state.engine, id) {
+        return false;
+    }
+    robot_watch::detonate(state);
+    settle_in(state);
+    true
+}
+
+/// Handle a widget dropped on a house: carry it (and a robot's team, or
+/// a box's contents) into the house's workspace.
+pub fn handle_drop_on_house(state: &mut AppState, id: WidgetId, mx: f64, my: f64) -> bool {
+    match find_widget_at_excluding(mx, my, id) {
+        Some((target_id, false)) => {
+            let busy = state.robot_watch.scheduler.live_process_of(id).is_some();
+            !busy && state.city.carry_in(&mut state.engine, id, target_id)
+        }
+        _ => false,
+    }
+}
+
+/// Handle a robot or box dropped on a truck: load it. A truck with both
+/// drives off and a new house, with the robot at work, stands in its place.
+pub fn handle_drop_on_truck(state: &mut AppState, id: WidgetId, mx: f64, my: f64) -> bool {
+    let truck = match find_widget_at_excluding(mx, my, id) {
+        Some((target_id, false)) => target_id,
+        _ => return false,
+    };
+    if state.robot_watch.scheduler.live_process_of(id).is_some() {
+        return false;
+    }
+    match state.city.load_truck(&mut state.engine, id, truck) {
+        Some(Loaded::Built(house)) => {
+            log::info!("A truck built house {}", house);
+            state
+                .city
+                .refresh(&mut state.engine, &state.robot_watch.scheduler);
+            true
+        }
+        Some(Loaded::Waiting) => true,
+        None => false,
+    }
+}
+
+/// Go into a house standing in the shown workspace.
+pub fn enter_house(state: &mut AppState, house: WidgetId) -> bool {
+    robot_watch::park(state);
+    let entered = state
+        .city
+        .enter(&mut state.engine, &mut state.robot_watch.scheduler, house);
+    settle_in(state);
+    entered
+}
+
+/// Go out of the shown house into the workspace it stands in.
+pub fn leave_house(state: &mut AppState) -> bool {
+    robot_watch::park(state);
+    let left = state
+        .city
+        .leave(&mut state.engine, &mut state.robot_watch.scheduler);
+    settle_in(state);
+    left
+}
+
+/// Catch up with the world now shown.
+fn settle_in(state: &mut AppState) {
+    state.robot_backside = None;
+    state
+        .city
+        .refresh(&mut state.engine, &state.robot_watch.scheduler);
+    robot_watch::wake(state);
+}
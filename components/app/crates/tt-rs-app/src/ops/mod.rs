@@ -4,9 +4,11 @@ mod bird_ops;
 mod box_hole;
 mod box_ops;
 mod dropzone_ops;
+mod house_ops;
 mod number_ops;
 mod robot_ops;
 mod scales_ops;
+mod sensor_ops;
 mod vacuum_ops;
 mod wand_ops;
 
@@ -14,8 +16,12 @@ pub use bird_ops::{handle_bird_drop, handle_drop_on_bird, handle_nest_click, han
 pub use box_hole::handle_box_hole_drop;
 pub use box_ops::handle_box_drop;
 pub use dropzone_ops::handle_dropzone_drop;
+pub use house_ops::{
+    handle_bomb_click, handle_drop_on_house, handle_drop_on_truck, handle_house_click, leave_house,
+};
 pub use number_ops::handle_number_on_number;
-pub use robot_ops::handle_robot_click;
+pub use robot_ops::{edit_robot_program, handle_robot_click, handle_robot_on_robot, ProgramEdit};
 pub use scales_ops::handle_scales_drop;
+pub use sensor_ops::{handle_key_press, handle_pointer_click, handle_sensor_click, toggle_sensors};
 pub use vacuum_ops::handle_vacuum_drop;
 pub use wand_ops::handle_wand_drop;
@@ -1,31 +1,19 @@
-//! Robot click and training operations.
+//! Robot click, training, team, box-giving and program-editing operations.
 
 use tt_rs_core::WidgetId;
 use tt_rs_drag::DropEvent;
+use tt_rs_engine::{ops, robot_exec};
+use tt_rs_hit_test::find_widget_at_excluding;
 use tt_rs_robot::RobotState;
 
-use crate::robot_exec::execute_robot;
+use crate::robot_watch;
 use crate::state::AppState;
 use crate::widget_item::WidgetItem;
 
-/// Copy a widget to create a new instance.
-pub fn copy_widget(widget: &WidgetItem) -> WidgetItem {
-    match widget {
-        WidgetItem::Number(n) => WidgetItem::Number(n.copy_number()),
-        WidgetItem::Text(t) => WidgetItem::Text(t.copy_text()),
-        WidgetItem::Scales(s) => WidgetItem::Scales(s.copy_scales()),
-        WidgetItem::Vacuum(v) => WidgetItem::Vacuum(v.copy_vacuum()),
-        WidgetItem::Wand(w) => WidgetItem::Wand(w.copy_wand()),
-        WidgetItem::Robot(r) => WidgetItem::Robot(r.copy_robot()),
-        WidgetItem::Nest(n) => WidgetItem::Nest(n.copy_nest()),
-        WidgetItem::Bird(b) => WidgetItem::Bird(b.copy_bird()),
-        WidgetItem::DropZone(dz) => WidgetItem::DropZone(dz.copy_dropzone()),
-    }
-}
-
 /// Handle robot click: toggle training or execute.
 pub fn handle_robot_click(state: &mut AppState, id: WidgetId, event: &DropEvent) -> bool {
     if !state
+        .engine
         .widgets
         .get(&id)
         .map(|w| w.is_robot())
@@ -34,7 +22,7 @@ pub fn handle_robot_click(state: &mut AppState, id: WidgetId, event: &DropEvent)
         return false;
     }
 
-    let old_pos = state.positions.get(&id).copied();
+    let old_pos = state.engine.positions.get(&id).copied();
     let dist = old_pos
         .map(|p| ((p.x - event.position.x).powi(2) + (p.y - event.position.y).powi(2)).sqrt())
         .unwrap_or(0.0);
@@ -43,48 +31,60 @@ pub fn handle_robot_click(state: &mut AppState, id: WidgetId, event: &DropEvent)
         return false;
     }
 
-    let (robot_state, has_actions) = get_robot_info(state, id);
-    handle_state_change(state, id, robot_state, has_actions);
-
     if let Some(pos) = old_pos {
-        state.positions.insert(id, pos);
+        state.engine.positions.insert(id, pos);
+    }
+    if !robot_watch::start_clicked(state, id) {
+        robot_exec::click_robot(&mut state.engine, id);
     }
     true
 }
 
-fn get_robot_info(state: &AppState, id: WidgetId) -> (RobotState, bool) {
-    state
-        .widgets
-        .get(&id)
-        .and_then(|w| match w {
-            WidgetItem::Robot(r) => Some((r.state(), !r.actions().is_empty())),
-            _ => None,
-        })
-        .unwrap_or((RobotState::Idle, false))
+/// Handle a robot dropped on another robot: join that robot's team.
+pub fn handle_robot_on_robot(state: &mut AppState, id: WidgetId, mx: f64, my: f64) -> bool {
+    if !state.engine.widgets.get(&id).is_some_and(|w| w.is_robot()) {
+        return false;
+    }
+    match find_widget_at_excluding(mx, my, id) {
+        Some((lead_id, false)) => ops::join_team(&mut state.engine, id, lead_id),
+        _ => false,
+    }
 }
 
-fn handle_state_change(state: &mut AppState, id: WidgetId, rs: RobotState, has_actions: bool) {
-    match rs {
-        RobotState::Training => stop_robot_training(state, id),
-        RobotState::Idle if has_actions => execute_robot(state, id),
-        RobotState::Idle => start_robot_training(state, id),
-        RobotState::Working => log::info!("Robot {} is working", id),
-    }
+/// Handle a box dropped on a robot: train on it, or match and run.
+pub fn handle_box_on_robot(state: &mut AppState, box_id: WidgetId, mx: f64, my: f64) -> bool {
+    let robot_id = match find_widget_at_excluding(mx, my, box_id) {
+        Some((id, false)) => id,
+        _ => return false,
+    };
+    robot_watch::give_box(state, robot_id, box_id)
 }
 
-fn stop_robot_training(state: &mut AppState, id: WidgetId) {
-    if let Some(WidgetItem::Robot(robot)) = state.widgets.get_mut(&id) {
-        robot.stop_training();
-    }
-    state.training_robot_id = None;
+/// A change made to a robot's program from its backside.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgramEdit {
+    /// Remove a step.
+    Delete(usize),
+    /// Move a step to another place in the program.
+    Move { from: usize, to: usize },
+    /// Keep the steps before this one and train the rest again.
+    RetrainFrom(usize),
 }
 
-fn start_robot_training(state: &mut AppState, id: WidgetId) {
-    if let Some(old_id) = state.training_robot_id {
-        stop_robot_training(state, old_id);
+/// Edit an idle robot's program. Returns true if anything changed.
+pub fn edit_robot_program(state: &mut AppState, id: WidgetId, edit: ProgramEdit) -> bool {
+    if let ProgramEdit::RetrainFrom(step) = edit {
+        return robot_exec::retrain_from(&mut state.engine, id, step);
+    }
+    let Some(WidgetItem::Robot(robot)) = state.engine.widgets.get_mut(&id) else {
+        return false;
+    };
+    if robot.state() != RobotState::Idle {
+        return false;
     }
-    if let Some(WidgetItem::Robot(r)) = state.widgets.get_mut(&id) {
-        r.start_training();
+    match edit {
+        ProgramEdit::Delete(i) => robot.remove_action(i).is_some(),
+        ProgramEdit::Move { from, to } => robot.move_action(from, to),
+        ProgramEdit::RetrainFrom(_) => false,
     }
-    state.training_robot_id = Some(id);
 }
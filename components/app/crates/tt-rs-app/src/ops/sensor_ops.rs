@@ -0,0 +1,71 @@
+//! Sensor operations: pause sensors, and feed them what the user does.
+
+use tt_rs_core::WidgetId;
+use tt_rs_drag::{DropEvent, Position};
+use tt_rs_engine::ops;
+
+use crate::demo_runner::WORKSPACE_OFFSET_Y;
+use crate::robot_watch;
+use crate::state::AppState;
+use crate::widget_item::WidgetItem;
+
+/// Handle sensor click: pause it, or resume it if it was paused.
+/// A "click" is detected when the widget barely moved (< 10 pixels).
+pub fn handle_sensor_click(state: &mut AppState, id: WidgetId, event: &DropEvent) -> bool {
+    if !matches!(state.engine.widgets.get(&id), Some(WidgetItem::Sensor(s)) if !s.is_copy_source())
+    {
+        return false;
+    }
+
+    let start_pos = event.start_position;
+    let final_pos = event.position;
+    let dist = ((start_pos.x - final_pos.x).powi(2) + (start_pos.y - final_pos.y).powi(2)).sqrt();
+
+    if dist >= 10.0 {
+        return false;
+    }
+
+    // Restore sensor to its original position (since it was a click, not a drag)
+    state.engine.positions.insert(id, start_pos);
+    ops::toggle_sensor(&mut state.engine, id)
+}
+
+/// Report a key press to the keyboard sensors of the shown workspace,
+/// and wake the robots waiting on their nests.
+/// Returns true if any sensor reported it.
+pub fn handle_key_press(state: &mut AppState, key: &str) -> bool {
+    if ops::sense_key(&mut state.engine, key) == 0 {
+        return false;
+    }
+    robot_watch::wake(state);
+    true
+}
+
+/// Report a click on a widget or box to the pointer sensors of the shown
+/// workspace, as where it landed on the workspace and what was clicked,
+/// and wake the robots waiting on their nests. Clicking a sensor only
+/// pauses or resumes it. Returns true if any sensor reported it.
+pub fn handle_pointer_click(state: &mut AppState, event: &DropEvent) -> bool {
+    let start_pos = event.start_position;
+    let final_pos = event.position;
+    let dist = ((start_pos.x - final_pos.x).powi(2) + (start_pos.y - final_pos.y).powi(2)).sqrt();
+    let id = event.widget_id;
+    if dist >= 10.0 || matches!(state.engine.widgets.get(&id), Some(WidgetItem::Sensor(_))) {
+        return false;
+    }
+
+    let at = Position::new(
+        event.mouse_position.x,
+        event.mouse_position.y - WORKSPACE_OFFSET_Y,
+    );
+    if ops::sense_click(&mut state.engine, at, Some(id)) == 0 {
+        return false;
+    }
+    robot_watch::wake(state);
+    true
+}
+
+/// Switch the shown workspace's sensors on or off.
+pub fn toggle_sensors(state: &mut AppState) {
+    state.engine.sensors_on = !state.engine.sensors_on;
+}
@@ -1,14 +1,14 @@
-//! Vacuum tool operations: erase widgets.
+//! Vacuum tool operations: erase widgets, or generalize robot patterns.
 
 use tt_rs_core::WidgetId;
 use tt_rs_drag::DropEvent;
-use tt_rs_hit_test::{find_box_hole_at, find_widget_at_excluding};
-use tt_rs_robot::Action;
+use tt_rs_engine::ops;
+use tt_rs_hit_test::{find_box_hole_at, find_pattern_part_at, find_widget_at_excluding};
 
 use crate::state::AppState;
-use crate::widget_item::WidgetItem;
 
-/// Handle vacuum drop: erase from hole or delete widget.
+/// Handle vacuum drop: erase part of a robot's thought bubble, erase
+/// from a hole, or delete a widget.
 pub fn handle_vacuum_drop(
     state: &mut AppState,
     id: WidgetId,
@@ -17,6 +17,7 @@ pub fn handle_vacuum_drop(
     event: &DropEvent,
 ) -> bool {
     if !state
+        .engine
         .widgets
         .get(&id)
         .map(|w| w.is_vacuum())
@@ -25,76 +26,23 @@ pub fn handle_vacuum_drop(
         return false;
     }
 
-    if try_vacuum_hole(state, id, mx, my, event) {
+    // On a thought bubble the vacuum erases rather than removes
+    if let Some((robot_id, path)) = find_pattern_part_at(mx, my) {
+        ops::erase_pattern(&mut state.engine, robot_id, &path);
+        state.engine.positions.insert(id, event.position);
         return true;
     }
-    if try_vacuum_widget(state, id, mx, my, event) {
-        return true;
-    }
-
-    state.positions.insert(id, event.position);
-    true
-}
-
-fn try_vacuum_hole(
-    state: &mut AppState,
-    vac_id: WidgetId,
-    mx: f64,
-    my: f64,
-    event: &DropEvent,
-) -> bool {
-    let (box_id, hole) = match find_box_hole_at(mx, my) {
-        Some(h) => h,
-        None => return false,
-    };
-
-    let erased = state
-        .boxes
-        .get_mut(&box_id)
-        .and_then(|b| b.clear_hole(hole));
-    if let Some(wid) = erased {
-        state.record_action(Action::Remove {
-            path: format!("box:{}:hole:{}", box_id, hole),
-        });
-        state.widget_in_box.remove(&wid);
-        state.widgets.remove(&wid);
-        state.positions.insert(vac_id, event.position);
-        return true;
-    }
-    false
-}
 
-fn try_vacuum_widget(
-    state: &mut AppState,
-    vac_id: WidgetId,
-    mx: f64,
-    my: f64,
-    event: &DropEvent,
-) -> bool {
-    let (target_id, is_box) = match find_widget_at_excluding(mx, my, vac_id) {
-        Some(t) => t,
-        None => return false,
+    let erased = match find_box_hole_at(mx, my) {
+        Some((box_id, hole)) => ops::vacuum_hole(&mut state.engine, box_id, hole),
+        None => false,
     };
-
-    if is_box || !is_deletable(state, target_id) {
-        return false;
+    if !erased {
+        if let Some((target_id, false)) = find_widget_at_excluding(mx, my, id) {
+            ops::vacuum_widget(&mut state.engine, target_id);
+        }
     }
 
-    state.widgets.remove(&target_id);
-    state.positions.remove(&target_id);
-    state.positions.insert(vac_id, event.position);
+    state.engine.positions.insert(id, event.position);
     true
 }
-
-fn is_deletable(state: &AppState, id: WidgetId) -> bool {
-    state
-        .widgets
-        .get(&id)
-        .map(|w| {
-            !matches!(
-                w,
-                WidgetItem::Vacuum(_) | WidgetItem::Wand(_) | WidgetItem::Robot(_)
-            ) && !matches!(w, WidgetItem::Number(n) if n.is_copy_source())
-        })
-        .unwrap_or(false)
-}
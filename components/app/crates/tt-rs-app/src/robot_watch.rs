@@ -0,0 +1,253 @@
+//! Robot processes at work.
+//!
+//! A robot given a box (or clicked) doesn't finish at once: its run joins
+//! the engine's [`Scheduler`] and a timer ticks it, with every running
+//! process taking turns. At watched speeds that is one action per tick,
+//! with each robot moving to its next action's target; at instant speed
+//! it is a batch of actions per tick, so even a robot that never stops
+//! leaves the page responsive until its budget runs out.
+//!
+//! Birds carrying messages between processes fly on a timer of their
+//! own, so a message takes as long to arrive whatever the robot speed.
+
+use std::collections::HashMap;
+
+use tt_rs_core::WidgetId;
+use tt_rs_drag::Position;
+use tt_rs_engine::ops;
+use tt_rs_engine::robot_exec::{self, ActionTarget, Given, RobotOutcome, RobotRun};
+use tt_rs_engine::scheduler::{ProcessState, Scheduler};
+use tt_rs_robot::RobotState;
+use tt_rs_ui::RobotSpeed;
+
+use crate::demo_ops::{hole_center, widget_center};
+use crate::state::AppState;
+use crate::widget_item::WidgetItem;
+
+/// How far left of its target the robot stands.
+const ROBOT_OFFSET_X: f64 = 60.0;
+
+/// Time per step of a bird's flight, in milliseconds.
+pub const FLIGHT_STEP_MS: u32 = 60;
+
+/// The robot processes, and the speed robots run at.
+#[derive(Clone, PartialEq, Default)]
+pub struct RobotWatch {
+    /// Time per action; instant runs are never shown.
+    pub speed: RobotSpeed,
+    /// Every robot process, running, waiting or just finished.
+    pub scheduler: Scheduler,
+    /// Whether the timer is stopped (processes only move on Step).
+    pub paused: bool,
+    /// Where each working lead robot stood before it started moving.
+    pub homes: HashMap<WidgetId, Position>,
+}
+
+impl RobotWatch {
+    /// Whether any process is running.
+    pub fn is_running(&self) -> bool {
+        self.scheduler.is_running()
+    }
+
+    /// Whether the timer should tick the scheduler, given whether
+    /// robots are working in houses not shown.
+    pub fn is_playing(&self, elsewhere: bool) -> bool {
+        (self.is_running() || elsewhere) && !self.paused
+    }
+
+    /// The run shown in the panel: the oldest running process's.
+    pub fn watched(&self) -> Option<&RobotRun> {
+        self.scheduler.processes().iter().find_map(|p| p.run())
+    }
+}
+
+/// Give a box to a robot and start its process, if it has one.
+/// A robot that already has a process is busy.
+pub fn give_box(state: &mut AppState, robot_id: WidgetId, box_id: WidgetId) -> bool {
+    if state
+        .robot_watch
+        .scheduler
+        .live_process_of(robot_id)
+        .is_some()
+    {
+        return false;
+    }
+    match robot_exec::give_box(&mut state.engine, robot_id, box_id) {
+        Some(Given::Running(run)) => {
+            log::info!("Robot {} started work on box {}", robot_id, box_id);
+            begin(state, run);
+            true
+        }
+        Some(Given::Settled(RobotOutcome::Suspended { nest, .. })) => {
+            log::info!("Robot {} waits on nest {}", robot_id, nest);
+            let watch = &mut state.robot_watch;
+            watch.scheduler.remove_finished();
+            watch
+                .scheduler
+                .spawn(&mut state.engine, robot_id, Some(box_id));
+            true
+        }
+        Some(Given::Settled(outcome)) => {
+            log::info!("Gave box {} to robot {}: {:?}", box_id, robot_id, outcome);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Start a clicked idle, trained robot's process without a box.
+/// Returns false if the click should be handled as usual.
+pub fn start_clicked(state: &mut AppState, robot_id: WidgetId) -> bool {
+    if state.engine.training_robot_id.is_some()
+        || state
+            .robot_watch
+            .scheduler
+            .live_process_of(robot_id)
+            .is_some()
+        || !matches!(
+            state.engine.widgets.get(&robot_id),
+            Some(WidgetItem::Robot(r)) if r.state() == RobotState::Idle
+        )
+    {
+        return false;
+    }
+    match RobotRun::start(&mut state.engine, robot_id, None) {
+        Ok(run) => {
+            begin(state, run);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// Give every running process its turn (a batch of turns at instant
+/// speed) and move the robots to their next targets. Robots that stop
+/// go back where they were.
+pub fn advance(state: &mut AppState) {
+    let rounds = state.robot_watch.speed.actions_per_tick();
+    state.robot_watch.scheduler.tick(&mut state.engine, rounds);
+    // Robots in the other houses keep working while they aren't shown
+    state.city.tick(rounds);
+    detonate(state);
+    state
+        .city
+        .deliver_mail(&mut state.engine, &mut state.robot_watch.scheduler);
+    state
+        .city
+        .refresh(&mut state.engine, &state.robot_watch.scheduler);
+    send_home(state);
+    move_robots(state);
+}
+
+/// Whether birds are away delivering, here or in other houses.
+pub fn is_flying(state: &AppState) -> bool {
+    ops::has_flights(&state.engine) || state.city.has_flights()
+}
+
+/// Move every bird that is away a step, here and in the other houses,
+/// then carry mail and wake processes waiting on the nests birds reached.
+pub fn fly(state: &mut AppState) {
+    ops::fly_birds(&mut state.engine);
+    state.city.fly_birds();
+    wake(state);
+}
+
+/// Carry mail to nests in other houses, and wake processes waiting on
+/// a nest that just received a message.
+pub fn wake(state: &mut AppState) {
+    let watch = &mut state.robot_watch;
+    state
+        .city
+        .deliver_mail(&mut state.engine, &mut watch.scheduler);
+    watch.scheduler.wake(&mut state.engine);
+    move_robots(state);
+}
+
+/// Tear down the houses bombs went off in. If the shown house goes, the
+/// world it stood in is shown instead.
+pub fn detonate(state: &mut AppState) {
+    let leaving = state.engine.bombed && state.city.inside().is_some();
+    if leaving {
+        park(state);
+        state.robot_backside = None;
+    }
+    state
+        .city
+        .detonate(&mut state.engine, &mut state.robot_watch.scheduler);
+}
+
+/// Put every working robot back where it was, before another house's
+/// workspace is shown.
+pub fn park(state: &mut AppState) {
+    for (lead, home) in state.robot_watch.homes.drain() {
+        state.engine.positions.insert(lead, home);
+    }
+}
+
+/// Where the watched run's next action happens, in workspace coordinates.
+/// Instant runs aren't shown, so they have no target.
+pub fn target_center(state: &AppState) -> Option<(f64, f64)> {
+    run_target(state, state.robot_watch.watched()?)
+}
+
+fn run_target(state: &AppState, run: &RobotRun) -> Option<(f64, f64)> {
+    if state.robot_watch.speed == RobotSpeed::Instant {
+        return None;
+    }
+    match run.target(&state.engine)? {
+        ActionTarget::Widget(id) => widget_center(state, id),
+        ActionTarget::Hole(box_id, hole) => hole_center(state, box_id, hole),
+    }
+}
+
+/// Queue a started run; finished processes make way for it.
+fn begin(state: &mut AppState, run: RobotRun) {
+    let watch = &mut state.robot_watch;
+    watch.scheduler.remove_finished();
+    watch.scheduler.spawn_run(run);
+    watch.paused = false;
+    move_robots(state);
+}
+
+/// Stand each running lead robot next to its next action's target.
+fn move_robots(state: &mut AppState) {
+    let moves: Vec<(WidgetId, (f64, f64))> = state
+        .robot_watch
+        .scheduler
+        .processes()
+        .iter()
+        .filter_map(|p| Some((p.lead(), run_target(state, p.run()?)?)))
+        .collect();
+    for (lead, (x, y)) in moves {
+        if let Some(&home) = state.engine.positions.get(&lead) {
+            state.robot_watch.homes.entry(lead).or_insert(home);
+        }
+        let pos = Position::new(x - ROBOT_OFFSET_X, y);
+        state.engine.positions.insert(lead, pos);
+    }
+}
+
+/// Put robots whose process stopped running back where they were.
+fn send_home(state: &mut AppState) {
+    let watch = &mut state.robot_watch;
+    for process in watch.scheduler.processes() {
+        if process.state() == ProcessState::Running {
+            continue;
+        }
+        if let Some(home) = watch.homes.remove(&process.lead()) {
+            log::info!(
+                "Robot {} is {}: {:?}",
+                process.lead(),
+                process.state().name(),
+                process.outcome()
+            );
+            // A robot that set off a bomb is gone
+            if state.engine.widgets.contains_key(&process.lead()) {
+                state.engine.positions.insert(process.lead(), home);
+            }
+        }
+    }
+    if !watch.is_running() && !state.city.is_running() {
+        watch.paused = false;
+    }
+}
@@ -3,13 +3,12 @@
 use std::collections::HashMap;
 use tt_rs_core::WidgetId;
 use tt_rs_drag::Position;
-use tt_rs_robot::Action;
-use tt_rs_scales::Scales;
+use tt_rs_engine::city::City;
+use tt_rs_engine::EngineState;
 use tt_rs_ui::UserLevel;
 
-use crate::box_state::BoxState;
 use crate::demo;
-use crate::widget_item::WidgetItem;
+use crate::robot_watch::RobotWatch;
 use crate::workspace::WidgetData;
 
 /// Default workspace notes for tt1 (Basic) mode.
@@ -48,14 +47,11 @@ pub fn default_notes_for_level(level: UserLevel) -> &'static str {
     }
 }
 
-/// Application state.
+/// Application state: the engine's workspace model plus view-only state.
 #[derive(Clone)]
 pub struct AppState {
-    pub widgets: HashMap<WidgetId, WidgetItem>,
-    pub boxes: HashMap<WidgetId, BoxState>,
-    pub positions: HashMap<WidgetId, Position>,
-    pub widget_in_box: HashMap<WidgetId, (WidgetId, usize)>,
-    pub training_robot_id: Option<WidgetId>,
+    /// Widgets, boxes, positions and robot training (headless).
+    pub engine: EngineState,
     /// Workspace notes/documentation content.
     pub text_pane_content: String,
     /// Text pane size (width, height).
@@ -72,20 +68,19 @@ pub struct AppState {
     pub box_names: HashMap<String, WidgetId>,
     /// Map of dropzone roles to their IDs (e.g., "example" -> dropzone_id).
     pub dropzone_roles: HashMap<String, WidgetId>,
+    /// Robot speed and the robot run being watched.
+    pub robot_watch: RobotWatch,
+    /// Robot whose backside (program) is shown.
+    pub robot_backside: Option<WidgetId>,
+    /// Houses, each with its own workspace and robots (see `engine` for
+    /// the one being shown).
+    pub city: City,
 }
 
 impl AppState {
     pub fn new() -> Self {
-        let (widgets, mut positions) = demo::init_widgets();
-        let (boxes, box_positions) = demo::init_boxes();
-        positions.extend(box_positions);
-
         Self {
-            widgets,
-            boxes,
-            positions,
-            widget_in_box: HashMap::new(),
-            training_robot_id: None,
+            engine: demo::furnished_engine(),
             // Initialize with tt1 content (default level)
             text_pane_content: TT1_DEFAULT_NOTES.to_string(),
             // Default size for sandbox mode (tutorials may override this)
@@ -98,28 +93,9 @@ impl AppState {
             widget_names: HashMap::new(),
             box_names: HashMap::new(),
             dropzone_roles: HashMap::new(),
-        }
-    }
-
-    pub fn record_action(&mut self, action: Action) {
-        if let Some(rid) = self.training_robot_id {
-            if let Some(WidgetItem::Robot(r)) = self.widgets.get_mut(&rid) {
-                r.record_action(action);
-            }
-        }
-    }
-
-    pub fn update_scales_in_box(&mut self, box_id: WidgetId) {
-        let contents = match self.boxes.get(&box_id) {
-            Some(b) => b.contents.clone(),
-            None => return,
-        };
-
-        for (&hole, &wid) in &contents {
-            if let Some(WidgetItem::Scales(s)) = self.widgets.get(&wid) {
-                let updated = update_scales(s, &contents, hole, &self.widgets);
-                self.widgets.insert(wid, WidgetItem::Scales(updated));
-            }
+            robot_watch: RobotWatch::default(),
+            robot_backside: None,
+            city: City::new(demo::furnished_engine),
         }
     }
 }
@@ -129,25 +105,3 @@ impl Default for AppState {
         Self::new()
     }
 }
-
-fn update_scales(
-    scales: &Scales,
-    contents: &HashMap<usize, WidgetId>,
-    hole: usize,
-    widgets: &HashMap<WidgetId, WidgetItem>,
-) -> Scales {
-    let mut s = scales.clone();
-    if hole > 0 {
-        if let Some(&lid) = contents.get(&(hole - 1)) {
-            if let Some(WidgetItem::Number(n)) = widgets.get(&lid) {
-                s.set_left(n.numerator());
-            }
-        }
-    }
-    if let Some(&rid) = contents.get(&(hole + 1)) {
-        if let Some(WidgetItem::Number(n)) = widgets.get(&rid) {
-            s.set_right(n.numerator());
-        }
-    }
-    s
-}
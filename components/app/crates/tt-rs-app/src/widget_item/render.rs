@@ -4,7 +4,7 @@ use tt_rs_core::Widget;
 use tt_rs_scales::CompareResult;
 use yew::prelude::*;
 
-use super::WidgetItem;
+use tt_rs_engine::WidgetItem;
 
 /// Render a widget item at full size.
 pub fn render(item: &WidgetItem) -> Html {
@@ -17,6 +17,10 @@ pub fn render(item: &WidgetItem) -> Html {
         WidgetItem::Robot(r) => r.render(),
         WidgetItem::Nest(nest) => nest.render(),
         WidgetItem::Bird(bird) => bird.render(),
+        WidgetItem::House(house) => house.render(),
+        WidgetItem::Truck(truck) => truck.render(),
+        WidgetItem::Bomb(bomb) => bomb.render(),
+        WidgetItem::Sensor(sensor) => sensor.render(),
         WidgetItem::DropZone(dz) => dz.render(),
     }
 }
@@ -30,8 +34,42 @@ pub fn render_small(widget: &WidgetItem) -> Html {
         WidgetItem::Vacuum(_) => html! { <div class="widget vacuum in-hole">{"[vacuum]"}</div> },
         WidgetItem::Wand(_) => html! { <div class="widget wand in-hole">{"[wand]"}</div> },
         WidgetItem::Robot(_) => html! { <div class="widget robot in-hole">{"[robot]"}</div> },
-        WidgetItem::Nest(_) => html! { <div class="widget nest in-hole">{"[nest]"}</div> },
-        WidgetItem::Bird(_) => html! { <div class="widget bird in-hole">{"[bird]"}</div> },
+        WidgetItem::Nest(nest) => render_nest_small(nest),
+        WidgetItem::Bird(bird) => {
+            // A bird in a hole stays put on screen; fade it while it's away
+            let class = if bird.is_away() {
+                "widget bird in-hole away"
+            } else {
+                "widget bird in-hole"
+            };
+            html! {
+                <div class={class}>
+                    {"[bird]"}
+                    if let Some(channel) = bird.channel() {
+                        { channel.render_tag() }
+                    }
+                </div>
+            }
+        }
+        WidgetItem::House(_) => html! { <div class="widget house in-hole">{"[house]"}</div> },
+        WidgetItem::Truck(_) => html! { <div class="widget truck in-hole">{"[truck]"}</div> },
+        WidgetItem::Bomb(_) => html! { <div class="widget bomb in-hole">{"[bomb]"}</div> },
+        WidgetItem::Sensor(sensor) => {
+            let class = classes!(
+                "widget",
+                "sensor",
+                "in-hole",
+                sensor.is_paused().then_some("paused")
+            );
+            html! {
+                <div class={class}>
+                    { format!("[{}]", sensor.kind().name()) }
+                    if let Some(channel) = sensor.channel() {
+                        { channel.render_tag() }
+                    }
+                </div>
+            }
+        }
         WidgetItem::DropZone(_) => {
             html! { <div class="widget dropzone in-hole">{"[dropzone]"}</div> }
         }
@@ -59,6 +97,25 @@ fn render_text_small(t: &tt_rs_text::Text) -> Html {
     }
 }
 
+/// A nest in a hole shows its top message, as a robot sees it.
+fn render_nest_small(nest: &tt_rs_nest::Nest) -> Html {
+    let Some(top) = nest.peek_top() else {
+        return html! {
+            <div class="widget nest in-hole">{"[nest]"}{ nest.channel().render_tag() }</div>
+        };
+    };
+    let count = nest.message_count();
+    html! {
+        <div class="widget nest in-hole has-message">
+            { render_small(&WidgetItem::from_boxed_widget(top.copy())) }
+            { nest.channel().render_tag() }
+            if count > 1 {
+                <div class="nest-badge">{format!("+{}", count - 1)}</div>
+            }
+        </div>
+    }
+}
+
 fn render_scales_small(s: &tt_rs_scales::Scales) -> Html {
     let (class_modifier, image_src) = scales_render_info(s.result());
     html! {
@@ -1,8 +1,9 @@
 //! Tooltip information for widget items.
 
 use tt_rs_number::ArithOperator;
+use tt_rs_sensors::SensorKind;
 
-use super::WidgetItem;
+use tt_rs_engine::WidgetItem;
 
 /// Tooltip information for a widget.
 pub struct TooltipInfo {
@@ -83,6 +84,67 @@ const TOOLTIP_BIRD: TooltipInfo = TooltipInfo {
     hint: "Drop an item on a bird to send it to the nest.",
 };
 
+const TOOLTIP_HOUSE_SOURCE: TooltipInfo = TooltipInfo {
+    title: "House Source",
+    description: "Click to build a new house.",
+    hint: "Each house has its own workspace and robots.",
+};
+
+const TOOLTIP_HOUSE: TooltipInfo = TooltipInfo {
+    title: "House",
+    description: "A separate workspace where robots work on their own.",
+    hint:
+        "Click to go in. Drop things on it to carry them inside; birds carry messages in and out.",
+};
+
+const TOOLTIP_TRUCK_SOURCE: TooltipInfo = TooltipInfo {
+    title: "Truck Source",
+    description: "Click to get a new truck.",
+    hint: "Load a robot and a box to start a new process.",
+};
+
+const TOOLTIP_TRUCK: TooltipInfo = TooltipInfo {
+    title: "Truck",
+    description: "Builds a new house where a robot works on a box.",
+    hint: "Drop a robot and a box on it. Birds in the box still fly home.",
+};
+
+const TOOLTIP_BOMB_SOURCE: TooltipInfo = TooltipInfo {
+    title: "Bomb Source",
+    description: "Click to get a new bomb.",
+    hint: "Put one in a box so a robot can end its own process.",
+};
+
+const TOOLTIP_BOMB: TooltipInfo = TooltipInfo {
+    title: "Bomb",
+    description: "Ends the process it goes off in.",
+    hint: "Click it to tear down the house you are in. A robot trained to click it does the same.",
+};
+
+const TOOLTIP_KEYBOARD_SENSOR_SOURCE: TooltipInfo = TooltipInfo {
+    title: "Keyboard Sensor Source",
+    description: "Click to get a new keyboard sensor with its nest.",
+    hint: "Give the nest to a robot so it can react to typing.",
+};
+
+const TOOLTIP_KEYBOARD_SENSOR: TooltipInfo = TooltipInfo {
+    title: "Keyboard Sensor",
+    description: "Puts each key you press in its nest, as text.",
+    hint: "Click it to pause or resume. The Sensors switch turns them all off here.",
+};
+
+const TOOLTIP_POINTER_SENSOR_SOURCE: TooltipInfo = TooltipInfo {
+    title: "Pointer Sensor Source",
+    description: "Click to get a new pointer sensor with its nest.",
+    hint: "Give the nest to a robot so it can react to where you click.",
+};
+
+const TOOLTIP_POINTER_SENSOR: TooltipInfo = TooltipInfo {
+    title: "Pointer Sensor",
+    description: "Puts each click in its nest, as a box of x, y, and what was clicked.",
+    hint: "Click it to pause or resume. The Sensors switch turns them all off here.",
+};
+
 const TOOLTIP_DROPZONE: TooltipInfo = TooltipInfo {
     title: "Drop Zone",
     description: "Drop the correct answer here to verify.",
@@ -106,6 +168,18 @@ pub fn tooltip_info(item: &WidgetItem) -> &'static TooltipInfo {
         WidgetItem::Robot(_) => &TOOLTIP_ROBOT,
         WidgetItem::Nest(_) => &TOOLTIP_NEST,
         WidgetItem::Bird(_) => &TOOLTIP_BIRD,
+        WidgetItem::House(h) if h.is_copy_source() => &TOOLTIP_HOUSE_SOURCE,
+        WidgetItem::House(_) => &TOOLTIP_HOUSE,
+        WidgetItem::Truck(t) if t.is_copy_source() => &TOOLTIP_TRUCK_SOURCE,
+        WidgetItem::Truck(_) => &TOOLTIP_TRUCK,
+        WidgetItem::Bomb(b) if b.is_copy_source() => &TOOLTIP_BOMB_SOURCE,
+        WidgetItem::Bomb(_) => &TOOLTIP_BOMB,
+        WidgetItem::Sensor(s) => match (s.kind(), s.is_copy_source()) {
+            (SensorKind::Keyboard, true) => &TOOLTIP_KEYBOARD_SENSOR_SOURCE,
+            (SensorKind::Keyboard, false) => &TOOLTIP_KEYBOARD_SENSOR,
+            (SensorKind::Pointer, true) => &TOOLTIP_POINTER_SENSOR_SOURCE,
+            (SensorKind::Pointer, false) => &TOOLTIP_POINTER_SENSOR,
+        },
         WidgetItem::DropZone(_) => &TOOLTIP_DROPZONE,
     }
 }
@@ -1,5 +1,7 @@
 //! Workspace data structures for JSON serialization.
 
+use std::num::NonZeroU64;
+
 use serde::{Deserialize, Serialize};
 
 /// Metadata about a workspace.
@@ -45,6 +47,26 @@ pub struct Workspace {
     /// Demo steps for "Show Me" animation (for tutorials).
     #[serde(default)]
     pub demo_steps: Vec<DemoStep>,
+    /// Whether the workspace's sensors report what they sense.
+    #[serde(default = "default_sensors_on")]
+    pub sensors_on: bool,
+}
+
+fn default_sensors_on() -> bool {
+    true
+}
+
+/// The workspace inside a house.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorldData {
+    /// All widgets in the house.
+    pub widgets: Vec<WidgetData>,
+    /// All boxes in the house.
+    #[serde(default)]
+    pub boxes: Vec<BoxData>,
+    /// Whether the house's sensors report what they sense.
+    #[serde(default = "default_sensors_on")]
+    pub sensors_on: bool,
 }
 
 /// Position in the workspace.
@@ -82,6 +104,14 @@ pub enum WidgetData {
     Bird(BirdData),
     #[serde(rename = "dropzone")]
     DropZone(DropZoneData),
+    #[serde(rename = "house")]
+    House(HouseData),
+    #[serde(rename = "truck")]
+    Truck(TruckData),
+    #[serde(rename = "bomb")]
+    Bomb(BombData),
+    #[serde(rename = "sensor")]
+    Sensor(SensorData),
     /// Box as a widget (for expected patterns in drop zones).
     #[serde(rename = "box")]
     Box(BoxPatternData),
@@ -98,6 +128,9 @@ pub struct BoxPatternData {
     /// Contents of each hole.
     #[serde(default)]
     pub contents: Vec<BoxHoleContent>,
+    /// Whether the box is erased (matches any box of this size).
+    #[serde(default)]
+    pub erased: bool,
 }
 
 /// Number widget data.
@@ -119,6 +152,9 @@ pub struct NumberData {
     /// Whether this is a copy source (infinite stack).
     #[serde(default)]
     pub is_copy_source: bool,
+    /// Whether this is an erased pattern (matches any number).
+    #[serde(default)]
+    pub erased: bool,
 }
 
 fn default_denominator() -> u64 {
@@ -136,6 +172,9 @@ pub struct TextData {
     pub content: String,
     /// Position in workspace.
     pub position: PositionData,
+    /// Whether this is an erased pattern (matches any text).
+    #[serde(default)]
+    pub erased: bool,
 }
 
 /// Scales widget data.
@@ -162,19 +201,67 @@ pub struct RobotData {
     /// Whether the robot is trained.
     #[serde(default)]
     pub is_trained: bool,
+    /// The box pattern in the robot's thought bubble.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<BoxPatternData>,
+    /// Index (in `widgets`) of the robot that gets unmatched boxes.
+    #[serde(default)]
+    pub next_robot_index: Option<usize>,
 }
 
-/// Serializable action data.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ActionData {
-    /// Action type (move, copy, etc.).
-    pub action_type: String,
-    /// Source widget description.
-    #[serde(default)]
-    pub source: Option<String>,
-    /// Target location description.
-    #[serde(default)]
-    pub target: Option<String>,
+/// Serializable robot action.
+///
+/// Paths are saved as recorded, except that absolute references to saved
+/// widgets and boxes are written as `widget:#I` / `box:#I:hole:N`, where
+/// `I` indexes `widgets` followed by `boxes`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action")]
+pub enum ActionData {
+    /// Pick up a widget.
+    #[serde(rename = "pick_up")]
+    PickUp { path: String },
+    /// Drop the held widget.
+    #[serde(rename = "drop")]
+    Drop { path: String },
+    /// Copy a widget or box with the wand.
+    #[serde(rename = "copy")]
+    Copy { path: String },
+    /// Erase a widget with the vacuum.
+    #[serde(rename = "remove")]
+    Remove { path: String },
+    /// Drop a number (the operand) on a target number.
+    #[serde(rename = "apply_arithmetic")]
+    ApplyArithmetic {
+        operator: OperatorData,
+        numerator: i64,
+        denominator: NonZeroU64,
+        target: String,
+    },
+    /// Create a new empty box.
+    #[serde(rename = "new_box")]
+    NewBox { holes: usize },
+    /// Split a box as if dropped on a number.
+    #[serde(rename = "split_box")]
+    SplitBox { path: String, at: i64 },
+    /// Take the top message out of a nest.
+    #[serde(rename = "take_from_nest")]
+    TakeFromNest { path: String },
+    /// Set off a bomb.
+    #[serde(rename = "set_off_bomb")]
+    SetOffBomb { path: String },
+}
+
+/// Operator of a recorded arithmetic step (see `ArithOperator`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OperatorData {
+    #[serde(rename = "+")]
+    Add,
+    #[serde(rename = "-")]
+    Subtract,
+    #[serde(rename = "*")]
+    Multiply,
+    #[serde(rename = "/")]
+    Divide,
 }
 
 /// Vacuum tool data.
@@ -191,6 +278,62 @@ pub struct WandData {
     pub position: PositionData,
 }
 
+/// House widget data.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HouseData {
+    /// Position in workspace.
+    pub position: PositionData,
+    /// What is inside the house, if anyone ever went in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub world: Option<Box<WorldData>>,
+}
+
+/// Truck widget data.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TruckData {
+    /// Position in workspace.
+    pub position: PositionData,
+    /// What the truck is loaded with, if anything.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub load: Option<Box<LoadData>>,
+}
+
+/// What is loaded onto a truck: a robot (with its team) or a box.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoadData {
+    /// The robots and boxes on the truck.
+    pub world: WorldData,
+    /// Index (in the load's `widgets`) of the loaded robot, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub robot_index: Option<usize>,
+    /// Index (in the load's `widgets` then `boxes`) of the loaded box, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub box_index: Option<usize>,
+}
+
+/// Bomb widget data.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BombData {
+    /// Position in workspace.
+    pub position: PositionData,
+}
+
+/// Sensor widget data.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SensorData {
+    /// Position in workspace.
+    pub position: PositionData,
+    /// What the sensor senses ("keyboard").
+    pub kind: String,
+    /// Whether the sensor is paused.
+    #[serde(default)]
+    pub paused: bool,
+    /// Channel of the nest the sensor reports to, if any. On loading, the
+    /// sensor is bonded to the nest on that channel, wherever it is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<u32>,
+}
+
 /// Nest widget data.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NestData {
@@ -202,6 +345,9 @@ pub struct NestData {
     /// Contents queued in the nest.
     #[serde(default)]
     pub contents: Vec<WidgetData>,
+    /// The nest's channel (colour and label), shared by its birds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<u32>,
 }
 
 /// Bird widget data.
@@ -212,9 +358,10 @@ pub struct BirdData {
     /// Whether this is a copy source.
     #[serde(default)]
     pub is_copy_source: bool,
-    /// Paired nest index (if paired).
-    #[serde(default)]
-    pub paired_nest_index: Option<usize>,
+    /// Channel of the nest the bird flies to, if any. On loading, the
+    /// bird is bonded to the nest on that channel, wherever it is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<u32>,
 }
 
 /// Box widget data.
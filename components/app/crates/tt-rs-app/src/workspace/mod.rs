@@ -3,14 +3,15 @@
 //! Defines JSON-serializable data structures for workspaces.
 
 mod data;
+mod programs;
 mod puzzles;
 mod serialize;
 #[cfg(test)]
 mod tests;
 
 pub use data::{
-    BoxData, BoxPatternData, DemoStep, DemoTarget, DropZoneData, NumberData, PositionData,
-    RobotData, ScalesData, TextData, WidgetData, Workspace, WorkspaceMetadata,
+    ActionData, BoxData, BoxPatternData, DemoStep, DemoTarget, DropZoneData, NumberData,
+    PositionData, RobotData, ScalesData, TextData, WidgetData, Workspace, WorkspaceMetadata,
 };
 pub use puzzles::load_bundled_puzzle;
-pub use serialize::{from_workspace, to_workspace};
+pub use serialize::{data_to_pattern, from_workspace, to_workspace};
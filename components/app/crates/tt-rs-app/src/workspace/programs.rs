@@ -0,0 +1,162 @@
+//! Robot program serialization: recorded actions and their paths.
+//!
+//! Relative paths (`input/...`, `new:K/...`) are saved unchanged. Absolute
+//! paths name widget IDs, which change on reload, so they are saved as
+//! indices into the workspace's widget and box lists and mapped back to
+//! the new IDs when loading.
+
+use std::collections::HashMap;
+use std::num::NonZeroU64;
+
+use tt_rs_core::WidgetId;
+use tt_rs_engine::robot_exec::{parse_box_hole_path, parse_widget_path};
+use tt_rs_robot::Action;
+
+use super::data::{ActionData, OperatorData};
+
+/// Position of every saved widget and box: `widgets` first, then `boxes`.
+pub struct SaveIndex {
+    index: HashMap<WidgetId, usize>,
+}
+
+impl SaveIndex {
+    /// Indexes widgets and boxes in the order they are saved.
+    pub fn new(saved: impl IntoIterator<Item = WidgetId>) -> Self {
+        let index = saved
+            .into_iter()
+            .enumerate()
+            .map(|(i, id)| (id, i))
+            .collect();
+        Self { index }
+    }
+
+    /// Index of a saved widget or box.
+    pub fn get(&self, id: WidgetId) -> Option<usize> {
+        self.index.get(&id).copied()
+    }
+}
+
+/// Convert recorded actions to their saved form. An arithmetic step with
+/// an operator or denominator no number can have is left out.
+pub fn actions_to_data(actions: &[Action], saved: &SaveIndex) -> Vec<ActionData> {
+    let path = |p: &str| encode_path(p, saved);
+    actions
+        .iter()
+        .filter_map(|action| {
+            Some(match action {
+                Action::PickUp { path: p } => ActionData::PickUp { path: path(p) },
+                Action::Drop { path: p } => ActionData::Drop { path: path(p) },
+                Action::Copy { path: p } => ActionData::Copy { path: path(p) },
+                Action::Remove { path: p } => ActionData::Remove { path: path(p) },
+                Action::ApplyArithmetic {
+                    operator,
+                    numerator,
+                    denominator,
+                    target_path,
+                } => ActionData::ApplyArithmetic {
+                    operator: operator_to_data(*operator)?,
+                    numerator: *numerator,
+                    denominator: u64::try_from(*denominator).ok().and_then(NonZeroU64::new)?,
+                    target: path(target_path),
+                },
+                Action::NewBox { holes } => ActionData::NewBox { holes: *holes },
+                Action::SplitBox { path: p, at } => ActionData::SplitBox {
+                    path: path(p),
+                    at: *at,
+                },
+                Action::TakeFromNest { path: p } => ActionData::TakeFromNest { path: path(p) },
+                Action::SetOffBomb { path: p } => ActionData::SetOffBomb { path: path(p) },
+            })
+        })
+        .collect()
+}
+
+/// Convert saved actions back, mapping saved indices to `loaded` IDs.
+/// An arithmetic step whose denominator is too big for a number is
+/// dropped.
+pub fn data_to_actions(data: &[ActionData], loaded: &[Option<WidgetId>]) -> Vec<Action> {
+    let path = |p: &str| decode_path(p, loaded);
+    data.iter()
+        .filter_map(|action| {
+            Some(match action {
+                ActionData::PickUp { path: p } => Action::PickUp { path: path(p) },
+                ActionData::Drop { path: p } => Action::Drop { path: path(p) },
+                ActionData::Copy { path: p } => Action::Copy { path: path(p) },
+                ActionData::Remove { path: p } => Action::Remove { path: path(p) },
+                ActionData::ApplyArithmetic {
+                    operator,
+                    numerator,
+                    denominator,
+                    target,
+                } => Action::ApplyArithmetic {
+                    operator: operator_symbol(*operator),
+                    numerator: *numerator,
+                    denominator: match i64::try_from(denominator.get()) {
+                        Ok(denominator) => denominator,
+                        Err(_) => {
+                            log::warn!("Dropped a robot step dividing by {}", denominator);
+                            return None;
+                        }
+                    },
+                    target_path: path(target),
+                },
+                ActionData::NewBox { holes } => Action::NewBox { holes: *holes },
+                ActionData::SplitBox { path: p, at } => Action::SplitBox {
+                    path: path(p),
+                    at: *at,
+                },
+                ActionData::TakeFromNest { path: p } => Action::TakeFromNest { path: path(p) },
+                ActionData::SetOffBomb { path: p } => Action::SetOffBomb { path: path(p) },
+            })
+        })
+        .collect()
+}
+
+fn operator_to_data(symbol: char) -> Option<OperatorData> {
+    match symbol {
+        '+' => Some(OperatorData::Add),
+        '-' => Some(OperatorData::Subtract),
+        '*' => Some(OperatorData::Multiply),
+        '/' => Some(OperatorData::Divide),
+        _ => None,
+    }
+}
+
+fn operator_symbol(operator: OperatorData) -> char {
+    match operator {
+        OperatorData::Add => '+',
+        OperatorData::Subtract => '-',
+        OperatorData::Multiply => '*',
+        OperatorData::Divide => '/',
+    }
+}
+
+/// "widget:123" -> "widget:#I", "box:45:hole:0" -> "box:#I:hole:0".
+fn encode_path(path: &str, saved: &SaveIndex) -> String {
+    if let Some(i) = parse_widget_path(path).and_then(|id| saved.get(id)) {
+        return format!("widget:#{}", i);
+    }
+    if let Some((box_id, hole)) = parse_box_hole_path(path) {
+        if let Some(i) = saved.get(box_id) {
+            return format!("box:#{}:hole:{}", i, hole);
+        }
+    }
+    path.to_string()
+}
+
+/// "widget:#I" -> "widget:<new id>", "box:#I:hole:0" -> "box:<new id>:hole:0".
+fn decode_path(path: &str, loaded: &[Option<WidgetId>]) -> String {
+    let id_at = |i: &str| i.parse::<usize>().ok().and_then(|i| *loaded.get(i)?);
+    if let Some(id) = path.strip_prefix("widget:#").and_then(id_at) {
+        return format!("widget:{}", id);
+    }
+    if let Some((i, hole)) = path
+        .strip_prefix("box:#")
+        .and_then(|rest| rest.split_once(":hole:"))
+    {
+        if let Some(id) = id_at(i) {
+            return format!("box:{}:hole:{}", id, hole);
+        }
+    }
+    path.to_string()
+}
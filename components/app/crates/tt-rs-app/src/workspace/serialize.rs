@@ -3,57 +3,41 @@
 use std::collections::HashMap;
 
 use tt_rs_bird::Bird;
+use tt_rs_bomb::Bomb;
+use tt_rs_box::ToonBox;
+use tt_rs_core::{Widget, WidgetId};
 use tt_rs_drag::Position;
 use tt_rs_dropzone::DropZone;
-use tt_rs_nest::Nest;
-use tt_rs_number::{ArithOperator, Number};
+use tt_rs_engine::city::{City, World};
+use tt_rs_engine::{BoxState, EngineState};
+use tt_rs_house::House;
+use tt_rs_nest::{Channel, Nest};
+use tt_rs_number::{ArithOperator, ErasureLevel as NumberErasure, Number};
 use tt_rs_robot::Robot;
 use tt_rs_scales::Scales;
-use tt_rs_text::Text;
+use tt_rs_sensors::{Sensor, SensorKind};
+use tt_rs_text::{ErasureLevel as TextErasure, Text};
+use tt_rs_truck::Truck;
 use tt_rs_vacuum::Vacuum;
 use tt_rs_wand::Wand;
 
-use crate::box_state::BoxState;
+use crate::demo;
 use crate::state::AppState;
 use crate::widget_item::WidgetItem;
 
 use super::data::*;
+use super::programs::{actions_to_data, data_to_actions, SaveIndex};
 
 /// Convert AppState to a serializable Workspace.
 pub fn to_workspace(state: &AppState, metadata: WorkspaceMetadata) -> Workspace {
-    let mut widgets = Vec::new();
-    let mut boxes = Vec::new();
-
-    // Serialize widgets (skip copy sources - they're part of the palette)
-    for (id, widget) in &state.widgets {
-        if widget.is_copy_source() {
-            continue; // Skip palette items
-        }
-
-        // Skip widgets that are in boxes (they'll be serialized with the box)
-        if state.widget_in_box.contains_key(id) {
-            continue;
-        }
-
-        if let Some(pos) = state.positions.get(id) {
-            if let Some(data) = widget_to_data(widget, pos) {
-                widgets.push(data);
-            }
-        }
-    }
-
-    // Serialize boxes
-    for (id, box_state) in &state.boxes {
-        if let Some(pos) = state.positions.get(id) {
-            let data = box_to_data(box_state, pos, state);
-            boxes.push(data);
-        }
-    }
+    // Save from outdoors down, whichever house is being shown
+    let outdoors = state.city.outdoors().map_or(&state.engine, |w| &w.engine);
+    let (world, _) = world_to_data(outdoors, state);
 
     Workspace {
         metadata,
-        widgets,
-        boxes,
+        widgets: world.widgets,
+        boxes: world.boxes,
         notes: state.text_pane_content.clone(),
         notes_position: Some(PositionData::new(
             state.text_pane_position.x,
@@ -61,6 +45,80 @@ pub fn to_workspace(state: &AppState, metadata: WorkspaceMetadata) -> Workspace
         )),
         notes_size: Some(state.text_pane_size),
         demo_steps: state.demo_steps.clone(),
+        sensors_on: world.sensors_on,
+    }
+}
+
+/// Convert a world's widgets and boxes, saving each house and truck in
+/// it with what is inside. Also returns where each widget was saved.
+fn world_to_data(engine: &EngineState, state: &AppState) -> (WorldData, SaveIndex) {
+    // Widgets to save (skip copy sources - they're part of the palette -
+    // and widgets in boxes, which are serialized with the box)
+    let saved_widgets: Vec<_> = engine
+        .widgets
+        .iter()
+        .filter(|(id, w)| !w.is_copy_source() && !engine.widget_in_box.contains_key(id))
+        .filter_map(|(id, w)| Some((*id, w, engine.positions.get(id)?)))
+        .collect();
+    // Boxes sitting in holes are serialized with the box they are in
+    let saved_boxes: Vec<_> = engine
+        .boxes
+        .iter()
+        .filter(|(id, _)| !engine.widget_in_box.contains_key(id))
+        .filter_map(|(id, b)| Some((*id, b, engine.positions.get(id)?)))
+        .collect();
+
+    // Robots refer to other saved widgets by their index in the file
+    let saved = SaveIndex::new(
+        saved_widgets
+            .iter()
+            .map(|(id, _, _)| *id)
+            .chain(saved_boxes.iter().map(|(id, _, _)| *id)),
+    );
+
+    let widgets = saved_widgets
+        .iter()
+        .filter_map(|(id, widget, pos)| {
+            let mut data = widget_to_data(widget, pos, &saved)?;
+            add_inside(&mut data, widget, *id, state);
+            Some(data)
+        })
+        .collect();
+    let boxes = saved_boxes
+        .iter()
+        .map(|(_, box_state, pos)| box_to_data(box_state, pos, engine, state, &saved))
+        .collect();
+
+    let world = WorldData {
+        widgets,
+        boxes,
+        sensors_on: engine.sensors_on,
+    };
+    (world, saved)
+}
+
+/// Save the world inside a house, or a truck's load, along with it.
+fn add_inside(data: &mut WidgetData, widget: &WidgetItem, id: WidgetId, state: &AppState) {
+    match (data, widget) {
+        (WidgetData::House(house), _) => {
+            let engine = if state.city.inside() == Some(id) {
+                Some(&state.engine)
+            } else {
+                state.city.world(id).map(|w| &w.engine)
+            };
+            house.world = engine.map(|engine| Box::new(world_to_data(engine, state).0));
+        }
+        (WidgetData::Truck(data), WidgetItem::Truck(truck)) => {
+            data.load = state.city.load(id).map(|load| {
+                let (world, saved) = world_to_data(&load.engine, state);
+                Box::new(LoadData {
+                    world,
+                    robot_index: truck.robot().and_then(|robot| saved.get(robot)),
+                    box_index: truck.cargo().and_then(|cargo| saved.get(cargo)),
+                })
+            });
+        }
+        _ => {}
     }
 }
 
@@ -76,102 +134,307 @@ fn get_widget_name(data: &WidgetData) -> Option<String> {
 
 /// Convert a Workspace to AppState.
 pub fn from_workspace(workspace: &Workspace) -> AppState {
-    let mut widgets = HashMap::new();
-    let mut positions = HashMap::new();
-    let mut boxes = HashMap::new();
-    let mut widget_in_box = HashMap::new();
-    let mut dropzone_patterns = HashMap::new();
-    let mut widget_names = HashMap::new();
-    let mut box_names = HashMap::new();
-    let mut dropzone_roles = HashMap::new();
-
-    // Deserialize standalone widgets
-    for widget_data in &workspace.widgets {
-        // Extract name for semantic targeting
-        let name = get_widget_name(widget_data);
-
-        // Extract dropzone expected patterns and roles before converting
-        if let WidgetData::DropZone(dz_data) = widget_data {
-            if let Some((item, pos)) = data_to_widget(widget_data) {
-                let id = item.id();
-                positions.insert(id, pos);
-                widgets.insert(id, item);
-
-                // Register dropzone role for semantic targeting
-                if let Some(ref role) = dz_data.role {
-                    dropzone_roles.insert(role.clone(), id);
+    let mut loader = Loader::default();
+    let outdoors = loader.load_world(&workspace.widgets, &workspace.boxes, None);
+
+    // Birds and sensors may send to a nest in another house
+    let nests = channel_nests(
+        std::iter::once(&outdoors)
+            .chain(loader.houses.iter().map(|(_, _, world)| world))
+            .chain(loader.loads.iter().map(|(_, load)| load)),
+    );
+    let mut engine = outdoors.bond(&nests);
+    engine.sensors_on = workspace.sensors_on;
+    let mut city = City::new(demo::furnished_engine);
+    for (house, place, world) in loader.houses {
+        city.restore_house(house, place, World::new(world.bond(&nests)));
+    }
+    for (truck, load) in loader.loads {
+        city.restore_load(truck, World::new(load.bond(&nests)));
+    }
+
+    // Use custom notes position if provided, otherwise default to right side
+    let text_pane_position = workspace
+        .notes_position
+        .as_ref()
+        .map(|p| Position::new(p.x, p.y))
+        .unwrap_or_else(|| Position::new(550.0, 60.0));
+
+    // Use custom notes size if provided, otherwise default
+    let text_pane_size = workspace.notes_size.unwrap_or((475.0, 500.0));
+
+    AppState {
+        engine,
+        text_pane_content: workspace.notes.clone(),
+        text_pane_size,
+        text_pane_position,
+        dropzone_patterns: loader.dropzone_patterns,
+        demo_steps: workspace.demo_steps.clone(),
+        widget_names: loader.widget_names,
+        box_names: loader.box_names,
+        dropzone_roles: loader.dropzone_roles,
+        robot_watch: Default::default(),
+        robot_backside: None,
+        city,
+    }
+}
+
+/// What loading a workspace has found so far.
+#[derive(Default)]
+struct Loader {
+    dropzone_patterns: HashMap<WidgetId, WidgetData>,
+    widget_names: HashMap<String, WidgetId>,
+    box_names: HashMap<String, WidgetId>,
+    dropzone_roles: HashMap<String, WidgetId>,
+    /// Each loaded house with the house (or truck) it stands in and the
+    /// world inside it.
+    houses: Vec<(WidgetId, Option<WidgetId>, LoadedWorld)>,
+    /// Each loaded truck with what it is loaded with.
+    loads: Vec<(WidgetId, LoadedWorld)>,
+}
+
+/// A loaded world, not yet in the city.
+struct LoadedWorld {
+    engine: EngineState,
+    /// ID of each loaded widget and box, in file order (for robot programs)
+    loaded: Vec<Option<WidgetId>>,
+    /// Each loaded bird or sensor with the channel of the nest it sends to
+    senders: Vec<(WidgetId, u32)>,
+}
+
+impl LoadedWorld {
+    /// Bond the world's birds and sensors to their nests, wherever they are.
+    fn bond(mut self, nests: &HashMap<u32, Nest>) -> EngineState {
+        bond_senders(&self.senders, nests, &mut self.engine.widgets);
+        self.engine
+    }
+}
+
+impl Loader {
+    /// Load the widgets and boxes of the world inside `place` (None:
+    /// outdoors), with what is inside the houses and trucks in it.
+    fn load_world(
+        &mut self,
+        widgets: &[WidgetData],
+        boxes: &[BoxData],
+        place: Option<WidgetId>,
+    ) -> LoadedWorld {
+        let mut engine = EngineState::new();
+        let mut loaded: Vec<Option<WidgetId>> = Vec::new();
+        let mut sender_channels: Vec<(WidgetId, u32)> = Vec::new();
+
+        // Deserialize standalone widgets
+        for widget_data in widgets {
+            // Extract name for semantic targeting
+            let name = get_widget_name(widget_data);
+
+            // Extract dropzone expected patterns and roles before converting
+            if let WidgetData::DropZone(dz_data) = widget_data {
+                let item = data_to_widget(widget_data);
+                loaded.push(item.as_ref().map(|(item, _)| item.id()));
+                if let Some((item, pos)) = item {
+                    let id = item.id();
+                    engine.add_widget(item, pos);
+
+                    // Register dropzone role for semantic targeting
+                    if let Some(ref role) = dz_data.role {
+                        self.dropzone_roles.insert(role.clone(), id);
+                    }
+
+                    // Store expected pattern if present
+                    if let Some(ref expected) = dz_data.expected {
+                        self.dropzone_patterns.insert(id, expected.as_ref().clone());
+                    }
+                }
+                continue;
+            }
+
+            let item = data_to_widget(widget_data);
+            loaded.push(item.as_ref().map(|(item, _)| item.id()));
+            if let Some((mut item, pos)) = item {
+                self.load_inside(widget_data, &mut item, place);
+                let id = engine.add_widget(item, pos);
+                if let Some(channel) = sender_channel(widget_data) {
+                    sender_channels.push((id, channel));
                 }
 
-                // Store expected pattern if present
-                if let Some(ref expected) = dz_data.expected {
-                    dropzone_patterns.insert(id, expected.as_ref().clone());
+                // Register widget name for semantic targeting
+                if let Some(n) = name {
+                    self.widget_names.insert(n, id);
                 }
             }
-            continue;
         }
 
-        if let Some((item, pos)) = data_to_widget(widget_data) {
-            let id = item.id();
-            positions.insert(id, pos);
-            widgets.insert(id, item);
+        // Deserialize boxes and their contents
+        for box_data in boxes {
+            let mut box_state = if box_data.erased {
+                BoxState::erased()
+            } else {
+                BoxState::new(box_data.num_holes)
+            };
+            self.fill_box(
+                &mut engine,
+                &mut box_state,
+                &box_data.contents,
+                place,
+                &mut sender_channels,
+            );
+            let box_pos = Position::new(box_data.position.x, box_data.position.y);
+            let box_id = engine.add_box(box_state, box_pos);
+            loaded.push(Some(box_id));
+
+            // Register box name for semantic targeting
+            if let Some(ref name) = box_data.name {
+                self.box_names.insert(name.clone(), box_id);
+            }
+        }
+
+        link_robots(widgets, &loaded, &mut engine.widgets);
+        LoadedWorld {
+            engine,
+            loaded,
+            senders: sender_channels,
+        }
+    }
 
-            // Register widget name for semantic targeting
-            if let Some(n) = name {
-                widget_names.insert(n, id);
+    /// Load what is inside the house, or on the truck, just loaded from
+    /// `data` into `place`.
+    fn load_inside(&mut self, data: &WidgetData, item: &mut WidgetItem, place: Option<WidgetId>) {
+        let id = item.id();
+        match (data, item) {
+            (
+                WidgetData::House(HouseData {
+                    world: Some(world), ..
+                }),
+                _,
+            ) => {
+                let mut inside = self.load_world(&world.widgets, &world.boxes, Some(id));
+                inside.engine.sensors_on = world.sensors_on;
+                self.houses.push((id, place, inside));
             }
+            (
+                WidgetData::Truck(TruckData {
+                    load: Some(load), ..
+                }),
+                WidgetItem::Truck(truck),
+            ) => {
+                // Houses on the truck stand in the truck until it drives off
+                let mut inside = self.load_world(&load.world.widgets, &load.world.boxes, Some(id));
+                inside.engine.sensors_on = load.world.sensors_on;
+                let found = |index: Option<usize>| *inside.loaded.get(index?)?;
+                if let Some(robot) = found(load.robot_index) {
+                    truck.load_robot(robot);
+                }
+                if let Some(cargo) = found(load.box_index) {
+                    truck.load_box(cargo);
+                }
+                self.loads.push((id, inside));
+            }
+            _ => {}
         }
     }
 
-    // Deserialize boxes and their contents
-    for box_data in &workspace.boxes {
-        let (box_state, box_pos, contents) = data_to_box(box_data);
+    /// Load saved hole contents into a box, loading a box in a hole with
+    /// its own contents.
+    fn fill_box(
+        &mut self,
+        engine: &mut EngineState,
+        box_state: &mut BoxState,
+        contents: &[BoxHoleContent],
+        place: Option<WidgetId>,
+        sender_channels: &mut Vec<(WidgetId, u32)>,
+    ) {
         let box_id = box_state.id();
-        positions.insert(box_id, box_pos);
-
-        // Register box name for semantic targeting
-        if let Some(ref name) = box_data.name {
-            box_names.insert(name.clone(), box_id);
+        for hole_content in contents {
+            let item_id = match &hole_content.widget {
+                WidgetData::Box(b) => {
+                    let mut inner = if b.erased {
+                        BoxState::erased()
+                    } else {
+                        BoxState::new(b.num_holes)
+                    };
+                    self.fill_box(engine, &mut inner, &b.contents, place, sender_channels);
+                    let inner_id = inner.id();
+                    engine.boxes.insert(inner_id, inner);
+                    inner_id
+                }
+                data => {
+                    let Some((mut item, _)) = data_to_widget(data) else {
+                        continue;
+                    };
+                    self.load_inside(data, &mut item, place);
+                    let item_id = item.id();
+                    if let Some(channel) = sender_channel(data) {
+                        sender_channels.push((item_id, channel));
+                    }
+                    engine.widgets.insert(item_id, item);
+                    item_id
+                }
+            };
+            box_state.place_in_hole(hole_content.hole, item_id);
+            engine
+                .widget_in_box
+                .insert(item_id, (box_id, hole_content.hole));
         }
+    }
+}
 
-        // Add box contents
-        for (hole, item) in contents {
-            let item_id = item.id();
-            widgets.insert(item_id, item);
-            widget_in_box.insert(item_id, (box_id, hole));
+/// Point loaded robots' absolute paths and chains at the new widget IDs.
+fn link_robots(
+    data: &[WidgetData],
+    loaded: &[Option<WidgetId>],
+    widgets: &mut HashMap<WidgetId, WidgetItem>,
+) {
+    for (widget_data, id) in data.iter().zip(loaded) {
+        if let (WidgetData::Robot(r), Some(id)) = (widget_data, id) {
+            if let Some(WidgetItem::Robot(robot)) = widgets.get_mut(id) {
+                robot.set_actions(data_to_actions(&r.actions, loaded));
+                robot.set_next_robot(r.next_robot_index.and_then(|i| *loaded.get(i)?));
+            }
         }
-
-        boxes.insert(box_id, box_state);
     }
+}
 
-    // Use custom notes position if provided, otherwise default to right side
-    let text_pane_position = workspace
-        .notes_position
-        .as_ref()
-        .map(|p| Position::new(p.x, p.y))
-        .unwrap_or_else(|| Position::new(550.0, 60.0));
+/// The channel of the nest a saved bird or sensor sends to, if it has one.
+fn sender_channel(data: &WidgetData) -> Option<u32> {
+    match data {
+        WidgetData::Bird(b) => b.channel,
+        WidgetData::Sensor(s) => s.channel,
+        _ => None,
+    }
+}
 
-    // Use custom notes size if provided, otherwise default
-    let text_pane_size = workspace.notes_size.unwrap_or((475.0, 500.0));
+/// The loaded nests in any of the worlds, by channel.
+fn channel_nests<'a>(worlds: impl Iterator<Item = &'a LoadedWorld>) -> HashMap<u32, Nest> {
+    worlds
+        .flat_map(|world| world.engine.widgets.values())
+        .filter_map(|w| match w {
+            WidgetItem::Nest(n) => Some((n.channel().index(), n.clone())),
+            _ => None,
+        })
+        .collect()
+}
 
-    AppState {
-        widgets,
-        boxes,
-        positions,
-        widget_in_box,
-        training_robot_id: None,
-        text_pane_content: workspace.notes.clone(),
-        text_pane_size,
-        text_pane_position,
-        dropzone_patterns,
-        demo_steps: workspace.demo_steps.clone(),
-        widget_names,
-        box_names,
-        dropzone_roles,
+/// Bond loaded birds and sensors to the loaded nests on their channels.
+fn bond_senders(
+    sender_channels: &[(WidgetId, u32)],
+    nests: &HashMap<u32, Nest>,
+    widgets: &mut HashMap<WidgetId, WidgetItem>,
+) {
+    for (sender_id, channel) in sender_channels {
+        let Some(nest) = nests.get(channel) else {
+            continue;
+        };
+        match widgets.get_mut(sender_id) {
+            Some(WidgetItem::Bird(bird)) => bird.bond(nest),
+            Some(WidgetItem::Sensor(sensor)) => sensor.bond(nest),
+            _ => {}
+        }
     }
 }
 
 /// Convert a WidgetItem to WidgetData.
-fn widget_to_data(widget: &WidgetItem, pos: &Position) -> Option<WidgetData> {
+fn widget_to_data(widget: &WidgetItem, pos: &Position, saved: &SaveIndex) -> Option<WidgetData> {
     let position = PositionData::new(pos.x, pos.y);
 
     match widget {
@@ -182,10 +445,12 @@ fn widget_to_data(widget: &WidgetItem, pos: &Position) -> Option<WidgetData> {
             operator: operator_to_string(n.operator()),
             position,
             is_copy_source: n.is_copy_source(),
+            erased: n.erasure() != NumberErasure::None,
         })),
         WidgetItem::Text(t) => Some(WidgetData::Text(TextData {
             content: t.value().to_string(),
             position,
+            erased: t.erasure() != TextErasure::None,
         })),
         WidgetItem::Scales(s) => Some(WidgetData::Scales(ScalesData {
             position,
@@ -194,8 +459,10 @@ fn widget_to_data(widget: &WidgetItem, pos: &Position) -> Option<WidgetData> {
         })),
         WidgetItem::Robot(r) => Some(WidgetData::Robot(RobotData {
             position,
-            actions: actions_to_data(r),
+            actions: actions_to_data(r.actions(), saved),
             is_trained: !r.actions().is_empty(),
+            pattern: r.pattern().map(|p| box_pattern_to_data(p, saved)),
+            next_robot_index: r.next_robot().and_then(|id| saved.get(id)),
         })),
         WidgetItem::Vacuum(_) => Some(WidgetData::Vacuum(VacuumData { position })),
         WidgetItem::Wand(_) => Some(WidgetData::Wand(WandData { position })),
@@ -203,11 +470,29 @@ fn widget_to_data(widget: &WidgetItem, pos: &Position) -> Option<WidgetData> {
             position,
             is_copy_source: nest.is_copy_source(),
             contents: vec![], // TODO: serialize nest contents
+            channel: Some(nest.channel().index()),
         })),
         WidgetItem::Bird(bird) => Some(WidgetData::Bird(BirdData {
             position,
             is_copy_source: bird.is_copy_source(),
-            paired_nest_index: None, // TODO: track paired nest
+            channel: bird.channel().map(|c| c.index()),
+        })),
+        // The world inside is added by `add_inside`
+        WidgetItem::House(_) => Some(WidgetData::House(HouseData {
+            position,
+            world: None,
+        })),
+        // The load is added by `add_inside`
+        WidgetItem::Truck(_) => Some(WidgetData::Truck(TruckData {
+            position,
+            load: None,
+        })),
+        WidgetItem::Bomb(_) => Some(WidgetData::Bomb(BombData { position })),
+        WidgetItem::Sensor(sensor) => Some(WidgetData::Sensor(SensorData {
+            position,
+            kind: sensor.kind().name().to_string(),
+            paused: sensor.is_paused(),
+            channel: sensor.channel().map(|c| c.index()),
         })),
         WidgetItem::DropZone(dz) => Some(WidgetData::DropZone(DropZoneData {
             label: dz.label().to_string(),
@@ -220,6 +505,58 @@ fn widget_to_data(widget: &WidgetItem, pos: &Position) -> Option<WidgetData> {
     }
 }
 
+/// Convert expected-pattern WidgetData to a pattern widget for matching.
+pub fn data_to_pattern(data: &WidgetData) -> Option<Box<dyn Widget>> {
+    match data {
+        WidgetData::Box(b) => Some(Box::new(data_to_box_pattern(b)?)),
+        _ => data_to_widget(data).map(|(w, _)| w.to_boxed_widget()),
+    }
+}
+
+fn data_to_box_pattern(b: &BoxPatternData) -> Option<ToonBox> {
+    let mut pattern = if b.erased {
+        ToonBox::erased_with_size(b.num_holes)
+    } else {
+        ToonBox::new(b.num_holes)
+    };
+    for c in &b.contents {
+        pattern.set_hole(c.hole, data_to_pattern(&c.widget)?);
+    }
+    Some(pattern)
+}
+
+/// Convert a robot's thought-bubble pattern to BoxPatternData.
+fn box_pattern_to_data(pattern: &ToonBox, saved: &SaveIndex) -> BoxPatternData {
+    let contents = pattern
+        .holes()
+        .filter_map(|hole| {
+            let widget = pattern_widget_to_data(hole.content()?, saved)?;
+            Some(BoxHoleContent {
+                hole: hole.index(),
+                widget,
+            })
+        })
+        .collect();
+    BoxPatternData {
+        num_holes: pattern.len(),
+        contents,
+        erased: pattern.is_erased(),
+    }
+}
+
+/// Pattern holes hold boxes, numbers or text; other widgets are left out.
+fn pattern_widget_to_data(widget: &dyn Widget, saved: &SaveIndex) -> Option<WidgetData> {
+    let any = widget.as_any();
+    if let Some(b) = any.downcast_ref::<ToonBox>() {
+        return Some(WidgetData::Box(box_pattern_to_data(b, saved)));
+    }
+    let item = match any.downcast_ref::<Number>() {
+        Some(n) => WidgetItem::Number(n.clone()),
+        None => WidgetItem::Text(any.downcast_ref::<Text>()?.clone()),
+    };
+    widget_to_data(&item, &Position::new(0.0, 0.0), saved)
+}
+
 /// Convert WidgetData to WidgetItem and Position.
 fn data_to_widget(data: &WidgetData) -> Option<(WidgetItem, Position)> {
     match data {
@@ -233,13 +570,20 @@ fn data_to_widget(data: &WidgetData) -> Option<(WidgetItem, Position)> {
             if n.is_copy_source {
                 num = num.as_copy_source();
             }
+            if n.erased {
+                num = Number::erased();
+            }
             Some((
                 WidgetItem::Number(num),
                 Position::new(n.position.x, n.position.y),
             ))
         }
         WidgetData::Text(t) => {
-            let text = Text::new(&t.content);
+            let text = if t.erased {
+                Text::erased()
+            } else {
+                Text::new(&t.content)
+            };
             Some((
                 WidgetItem::Text(text),
                 Position::new(t.position.x, t.position.y),
@@ -259,8 +603,12 @@ fn data_to_widget(data: &WidgetData) -> Option<(WidgetItem, Position)> {
             ))
         }
         WidgetData::Robot(r) => {
-            let robot = Robot::new();
-            // TODO: restore actions
+            // Absolute paths and the chain are linked once all IDs are known
+            let mut robot = Robot::new();
+            robot.set_actions(data_to_actions(&r.actions, &[]));
+            if let Some(pattern) = r.pattern.as_ref().and_then(data_to_box_pattern) {
+                robot.set_pattern(pattern);
+            }
             Some((
                 WidgetItem::Robot(robot),
                 Position::new(r.position.x, r.position.y),
@@ -275,7 +623,10 @@ fn data_to_widget(data: &WidgetData) -> Option<(WidgetItem, Position)> {
             Position::new(w.position.x, w.position.y),
         )),
         WidgetData::Nest(n) => {
-            let mut nest = Nest::new();
+            let mut nest = match n.channel {
+                Some(index) => Nest::with_channel(Channel::restore(index)),
+                None => Nest::new(),
+            };
             if n.is_copy_source {
                 nest = nest.as_copy_source();
             }
@@ -285,6 +636,7 @@ fn data_to_widget(data: &WidgetData) -> Option<(WidgetItem, Position)> {
             ))
         }
         WidgetData::Bird(b) => {
+            // Bonded to its nest once every nest is loaded (see `bond_senders`)
             let mut bird = Bird::new();
             if b.is_copy_source {
                 bird = bird.as_copy_source();
@@ -294,6 +646,26 @@ fn data_to_widget(data: &WidgetData) -> Option<(WidgetItem, Position)> {
                 Position::new(b.position.x, b.position.y),
             ))
         }
+        WidgetData::House(h) => Some((
+            WidgetItem::House(House::new()),
+            Position::new(h.position.x, h.position.y),
+        )),
+        WidgetData::Truck(t) => Some((
+            WidgetItem::Truck(Truck::new()),
+            Position::new(t.position.x, t.position.y),
+        )),
+        WidgetData::Bomb(b) => Some((
+            WidgetItem::Bomb(Bomb::new()),
+            Position::new(b.position.x, b.position.y),
+        )),
+        WidgetData::Sensor(s) => {
+            // Bonded to its nest once every nest is loaded (see `bond_senders`)
+            let kind = SensorKind::from_name(&s.kind)?;
+            Some((
+                WidgetItem::Sensor(Sensor::new(kind).with_paused(s.paused)),
+                Position::new(s.position.x, s.position.y),
+            ))
+        }
         WidgetData::DropZone(dz) => {
             let mut dropzone = DropZone::new(&dz.label);
             if let Some(ref url) = dz.on_success_url {
@@ -319,55 +691,54 @@ fn data_to_widget(data: &WidgetData) -> Option<(WidgetItem, Position)> {
 }
 
 /// Convert BoxState to BoxData.
-fn box_to_data(box_state: &BoxState, pos: &Position, state: &AppState) -> BoxData {
-    let position = PositionData::new(pos.x, pos.y);
-    let mut contents = Vec::new();
-
-    for hole in 0..box_state.num_holes {
-        if let Some(widget_id) = box_state.widget_in_hole(hole) {
-            if let Some(widget) = state.widgets.get(&widget_id) {
-                // Use a dummy position since it's inside a box
-                let dummy_pos = Position::new(0.0, 0.0);
-                if let Some(widget_data) = widget_to_data(widget, &dummy_pos) {
-                    contents.push(BoxHoleContent {
-                        hole,
-                        widget: widget_data,
-                    });
-                }
-            }
-        }
-    }
-
+fn box_to_data(
+    box_state: &BoxState,
+    pos: &Position,
+    engine: &EngineState,
+    state: &AppState,
+    saved: &SaveIndex,
+) -> BoxData {
     BoxData {
         name: None, // Name is only loaded from puzzle files
         num_holes: box_state.num_holes,
-        position,
-        contents,
+        position: PositionData::new(pos.x, pos.y),
+        contents: hole_contents(box_state, engine, state, saved),
         erased: box_state.erased,
         is_copy_source: false, // TODO: add is_copy_source to BoxState
     }
 }
 
-/// Convert BoxData to BoxState and contents.
-fn data_to_box(data: &BoxData) -> (BoxState, Position, Vec<(usize, WidgetItem)>) {
-    let mut box_state = if data.erased {
-        BoxState::erased()
-    } else {
-        BoxState::new(data.num_holes)
-    };
-
-    let pos = Position::new(data.position.x, data.position.y);
-    let mut contents = Vec::new();
-
-    for hole_content in &data.contents {
-        if let Some((item, _)) = data_to_widget(&hole_content.widget) {
-            let item_id = item.id();
-            box_state.place_in_hole(hole_content.hole, item_id);
-            contents.push((hole_content.hole, item));
-        }
-    }
-
-    (box_state, pos, contents)
+/// Convert the contents of a box's holes; a box in a hole is saved
+/// with its own contents.
+fn hole_contents(
+    box_state: &BoxState,
+    engine: &EngineState,
+    state: &AppState,
+    saved: &SaveIndex,
+) -> Vec<BoxHoleContent> {
+    // Use a dummy position since it's inside a box
+    let dummy_pos = Position::new(0.0, 0.0);
+    (0..box_state.num_holes)
+        .filter_map(|hole| {
+            let id = box_state.widget_in_hole(hole)?;
+            let widget = match engine.widgets.get(&id) {
+                Some(widget) => {
+                    let mut data = widget_to_data(widget, &dummy_pos, saved)?;
+                    add_inside(&mut data, widget, id, state);
+                    data
+                }
+                None => {
+                    let inner = engine.boxes.get(&id)?;
+                    WidgetData::Box(BoxPatternData {
+                        num_holes: inner.num_holes,
+                        contents: hole_contents(inner, engine, state, saved),
+                        erased: inner.erased,
+                    })
+                }
+            };
+            Some(BoxHoleContent { hole, widget })
+        })
+        .collect()
 }
 
 fn operator_to_string(op: ArithOperator) -> String {
@@ -387,8 +758,3 @@ fn string_to_operator(s: &str) -> ArithOperator {
         _ => ArithOperator::Add,
     }
 }
-
-fn actions_to_data(_robot: &Robot) -> Vec<ActionData> {
-    // TODO: serialize robot actions
-    vec![]
-}
@@ -1,13 +1,31 @@
 //! Tests for workspace serialization.
 
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod tests {
     use super::super::data::*;
+    use crate::state::AppState;
 
     const PUZZLE_FILL_BOX: &str = include_str!("../../assets/puzzles/puzzle-fill-box.json");
     const PUZZLE_MAKE_FOUR: &str = include_str!("../../assets/puzzles/puzzle-make-four.json");
     const PUZZLE_MAKE_NINE: &str = include_str!("../../assets/puzzles/puzzle-make-nine.json");
 
+    /// Save a state to JSON and load it back.
+    fn round_trip(state: &AppState) -> AppState {
+        let metadata = WorkspaceMetadata {
+            id: "round-trip".to_string(),
+            name: "Round Trip".to_string(),
+            description: String::new(),
+            user_level: "tt2".to_string(),
+            is_bundled: false,
+            created_at: None,
+            modified_at: None,
+        };
+        let json = serde_json::to_string(&super::super::to_workspace(state, metadata)).unwrap();
+        let workspace: Workspace = serde_json::from_str(&json).unwrap();
+        super::super::from_workspace(&workspace)
+    }
+
     #[test]
     fn test_parse_puzzle_fill_box() {
         let workspace: Workspace =
@@ -109,4 +127,391 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_dropzone_box_pattern_matches_structurally() {
+        use tt_rs_box::ToonBox;
+        use tt_rs_core::MatchResult;
+        use tt_rs_number::Number;
+
+        let workspace: Workspace =
+            serde_json::from_str(PUZZLE_FILL_BOX).expect("Failed to parse puzzle-fill-box.json");
+        let expected = workspace
+            .widgets
+            .iter()
+            .find_map(|w| match w {
+                WidgetData::DropZone(dz) => dz.expected.clone(),
+                _ => None,
+            })
+            .expect("Expected pattern should exist");
+        let pattern = super::super::data_to_pattern(&expected).expect("Pattern should convert");
+
+        let mut answer = ToonBox::new(2);
+        answer.set_hole(0, Box::new(Number::new(1)));
+        answer.set_hole(1, Box::new(Number::new(2)));
+        assert!(pattern.matches(&answer).is_match());
+
+        answer.set_hole(1, Box::new(Number::new(3)));
+        assert_eq!(pattern.matches(&answer), MatchResult::NoMatch);
+    }
+
+    #[test]
+    fn test_trained_robot_survives_save_and_load() {
+        use tt_rs_engine::robot_exec::{self, RobotOutcome};
+        use tt_rs_engine::{ops, BoxState};
+        use tt_rs_number::Number;
+        use tt_rs_robot::{Action, Robot};
+
+        use crate::widget_item::WidgetItem;
+        use tt_rs_drag::Position;
+
+        let mut state = AppState::new();
+        let engine = &mut state.engine;
+        let at = Position::new(10.0, 10.0);
+        let robot_id = engine.add_widget(WidgetItem::Robot(Robot::new()), at);
+        let next_id = engine.add_widget(WidgetItem::Robot(Robot::new()), at);
+        let counter = engine.add_widget(WidgetItem::Number(Number::new(40)), at);
+        let input = engine.add_box(BoxState::new(1), at);
+        let seven = engine.add_widget(WidgetItem::Number(Number::new(7)), at);
+        ops::drop_in_hole(engine, seven, input, 0, at);
+
+        // Train: add 1 to a counter outside the box (an absolute path)
+        robot_exec::give_box_to_robot(engine, robot_id, input);
+        let one = engine.add_widget(WidgetItem::Number(Number::new(1)), at);
+        ops::apply_number(engine, one, counter);
+        robot_exec::click_robot(engine, robot_id);
+        if let Some(WidgetItem::Robot(r)) = engine.widgets.get_mut(&robot_id) {
+            r.set_next_robot(Some(next_id));
+        }
+        // Generalize the pattern: 7 becomes "any number"
+        assert!(ops::erase_pattern(engine, robot_id, &[0]));
+
+        let mut loaded = round_trip(&state);
+        let engine = &mut loaded.engine;
+
+        let (robot_id, robot) = engine
+            .widgets
+            .iter()
+            .find_map(|(id, w)| match w {
+                WidgetItem::Robot(r) if !r.actions().is_empty() => Some((*id, r.clone())),
+                _ => None,
+            })
+            .expect("trained robot should be restored");
+        assert_eq!(robot.pattern().map(|p| p.len()), Some(1));
+        assert_eq!(
+            robot
+                .pattern()
+                .and_then(|p| p.hole(0)?.content())
+                .map(|w| w.description()),
+            Some("erased number".to_string())
+        );
+        let next = robot.next_robot().expect("chain should be restored");
+        assert!(matches!(
+            engine.widgets.get(&next),
+            Some(WidgetItem::Robot(_))
+        ));
+        let counter = match robot.actions() {
+            [Action::ApplyArithmetic {
+                operator: '+',
+                numerator: 1,
+                denominator: 1,
+                target_path,
+            }] => robot_exec::parse_widget_path(target_path).expect("absolute path"),
+            other => panic!("unexpected actions: {:?}", other),
+        };
+
+        let input = engine
+            .boxes
+            .iter()
+            .find(|(_, b)| {
+                b.widget_in_hole(0)
+                    .is_some_and(|w| matches!(engine.widgets.get(&w), Some(WidgetItem::Number(n)) if n.numerator() == 7))
+            })
+            .map(|(id, _)| *id)
+            .expect("input box should be restored");
+        engine.step_budget = 1;
+        assert!(matches!(
+            robot_exec::give_box_to_robot(engine, robot_id, input),
+            Some(RobotOutcome::Ran { .. })
+        ));
+        match engine.widgets.get(&counter) {
+            Some(WidgetItem::Number(n)) => assert_eq!(n.numerator(), 42),
+            _ => panic!("counter should be restored"),
+        }
+    }
+
+    #[test]
+    fn test_arithmetic_steps_no_number_could_take_are_rejected() {
+        let step = |operator: &str, denominator: i64| {
+            format!(
+                r#"{{"action":"apply_arithmetic","operator":"{}","numerator":1,"denominator":{},"target":"input"}}"#,
+                operator, denominator
+            )
+        };
+        let parse = |json: String| serde_json::from_str::<ActionData>(&json);
+
+        assert!(matches!(
+            parse(step("*", 3)),
+            Ok(ActionData::ApplyArithmetic {
+                operator: OperatorData::Multiply,
+                ..
+            })
+        ));
+        assert!(parse(step("%", 3)).is_err());
+        assert!(parse(step("+", 0)).is_err());
+        assert!(parse(step("+", -1)).is_err());
+    }
+
+    #[test]
+    fn test_box_in_a_box_survives_save_and_load() {
+        use tt_rs_drag::Position;
+        use tt_rs_engine::{ops, BoxState};
+        use tt_rs_number::Number;
+
+        use crate::widget_item::WidgetItem;
+
+        let mut state = AppState::new();
+        let box_count = state.engine.boxes.len();
+        let engine = &mut state.engine;
+        let at = Position::new(10.0, 10.0);
+        let outer = engine.add_box(BoxState::new(1), at);
+        let inner = engine.add_box(BoxState::new(1), at);
+        let n = engine.add_widget(WidgetItem::Number(Number::new(8)), at);
+        ops::drop_in_hole(engine, n, inner, 0, at);
+        if let Some(b) = engine.boxes.get_mut(&outer) {
+            b.place_in_hole(0, inner);
+        }
+        engine.widget_in_box.insert(inner, (outer, 0));
+
+        let loaded = round_trip(&state);
+        let engine = &loaded.engine;
+        assert_eq!(engine.boxes.len(), box_count + 2);
+        let (inner, outer) = engine
+            .widget_in_box
+            .iter()
+            .find(|(id, _)| engine.boxes.contains_key(id))
+            .map(|(id, (outer, _))| (*id, *outer))
+            .expect("inner box should be in a hole");
+        assert!(!engine.widget_in_box.contains_key(&outer));
+        let eight = engine.boxes[&inner].widget_in_hole(0);
+        assert!(matches!(
+            eight.and_then(|id| engine.widgets.get(&id)),
+            Some(WidgetItem::Number(n)) if n.numerator() == 8
+        ));
+    }
+
+    #[test]
+    fn test_what_is_in_a_house_survives_save_and_load() {
+        use tt_rs_drag::Position;
+        use tt_rs_house::House;
+        use tt_rs_number::Number;
+        use tt_rs_robot::Robot;
+
+        use crate::widget_item::WidgetItem;
+
+        let mut state = AppState::new();
+        let at = Position::new(10.0, 10.0);
+        let house = state.engine.add_widget(WidgetItem::House(House::new()), at);
+
+        // Inside, a new house has its own palette; put a number and a
+        // robot next to it and save without going back out
+        let palette_size = state.engine.widgets.len() - 1;
+        let scheduler = &mut state.robot_watch.scheduler;
+        assert!(state.city.enter(&mut state.engine, scheduler, house));
+        assert_eq!(state.engine.widgets.len(), palette_size);
+        state
+            .engine
+            .add_widget(WidgetItem::Number(Number::new(7)), at);
+        state.engine.add_widget(WidgetItem::Robot(Robot::new()), at);
+
+        // Loading lands outdoors, with the house standing there
+        let loaded = round_trip(&state);
+        assert_eq!(loaded.city.inside(), None);
+        let (&house, _) = loaded
+            .engine
+            .widgets
+            .iter()
+            .find(|(_, w)| matches!(w, WidgetItem::House(h) if !h.is_copy_source()))
+            .expect("the house is outdoors");
+        assert!(!loaded
+            .engine
+            .widgets
+            .values()
+            .any(|w| matches!(w, WidgetItem::Number(n) if n.numerator() == 7)));
+
+        let inside = &loaded
+            .city
+            .world(house)
+            .expect("the house has a world")
+            .engine;
+        assert!(inside
+            .widgets
+            .values()
+            .any(|w| matches!(w, WidgetItem::Number(n) if n.numerator() == 7)));
+        assert!(inside
+            .widgets
+            .values()
+            .any(|w| w.is_robot() && !w.is_copy_source()));
+    }
+
+    #[test]
+    fn test_what_is_on_a_truck_survives_save_and_load() {
+        use tt_rs_drag::Position;
+        use tt_rs_engine::city::Loaded;
+        use tt_rs_engine::{ops, BoxState};
+        use tt_rs_number::Number;
+        use tt_rs_robot::Robot;
+        use tt_rs_truck::Truck;
+
+        use crate::widget_item::WidgetItem;
+
+        let mut state = AppState::new();
+        let engine = &mut state.engine;
+        let at = Position::new(10.0, 10.0);
+        // A robot with a teammate on one truck, a box holding a 5 on another
+        let first = engine.add_widget(WidgetItem::Robot(Robot::new()), at);
+        let second = engine.add_widget(WidgetItem::Robot(Robot::new()), at);
+        if let Some(WidgetItem::Robot(r)) = engine.widgets.get_mut(&first) {
+            r.set_next_robot(Some(second));
+        }
+        let robot_truck = engine.add_widget(WidgetItem::Truck(Truck::new()), at);
+        let box_truck = engine.add_widget(WidgetItem::Truck(Truck::new()), at);
+        let cargo = engine.add_box(BoxState::new(1), at);
+        let five = engine.add_widget(WidgetItem::Number(Number::new(5)), at);
+        ops::drop_in_hole(engine, five, cargo, 0, at);
+        let loaded = state.city.load_truck(&mut state.engine, first, robot_truck);
+        assert_eq!(loaded, Some(Loaded::Waiting));
+        let loaded = state.city.load_truck(&mut state.engine, cargo, box_truck);
+        assert_eq!(loaded, Some(Loaded::Waiting));
+
+        let mut loaded = round_trip(&state);
+        let trucks: Vec<_> = loaded
+            .engine
+            .widgets
+            .iter()
+            .filter_map(|(&id, w)| match w {
+                WidgetItem::Truck(t) if !t.is_copy_source() => Some((id, t.clone())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(trucks.len(), 2);
+        let (robot_truck, truck) = trucks
+            .iter()
+            .find(|(_, t)| t.robot().is_some())
+            .expect("a truck with a robot");
+        let load = &loaded
+            .city
+            .load(*robot_truck)
+            .expect("robots loaded")
+            .engine;
+        let next = match truck.robot().and_then(|id| load.widgets.get(&id)) {
+            Some(WidgetItem::Robot(r)) => r.next_robot(),
+            _ => panic!("the loaded robot is on the truck"),
+        };
+        assert!(next.is_some_and(|id| load.widgets.get(&id).is_some_and(|w| w.is_robot())));
+
+        let (box_truck, truck) = trucks
+            .iter()
+            .find(|(_, t)| t.cargo().is_some())
+            .expect("a truck with a box");
+        let load = &loaded.city.load(*box_truck).expect("box loaded").engine;
+        let five = truck
+            .cargo()
+            .and_then(|id| load.boxes.get(&id)?.widget_in_hole(0));
+        assert!(matches!(
+            five.and_then(|id| load.widgets.get(&id)),
+            Some(WidgetItem::Number(n)) if n.numerator() == 5
+        ));
+
+        // The loaded truck still drives off once it gets a box
+        let more = loaded.engine.add_box(BoxState::new(1), at);
+        let built = loaded
+            .city
+            .load_truck(&mut loaded.engine, more, *robot_truck);
+        assert!(matches!(built, Some(Loaded::Built(_))));
+    }
+
+    #[test]
+    fn test_birds_find_their_nests_after_save_and_load() {
+        use tt_rs_drag::Position;
+        use tt_rs_engine::{ops, BoxState};
+        use tt_rs_nest::Nest;
+
+        use crate::widget_item::WidgetItem;
+
+        let mut state = AppState::new();
+        let engine = &mut state.engine;
+        let at = Position::new(10.0, 10.0);
+        // One pair lying free, another with its nest in a box
+        let free_nest = ops::hatch_nest(engine, Nest::new(), at);
+        let boxed_nest = ops::hatch_nest(engine, Nest::new(), at);
+        let box_id = engine.add_box(BoxState::new(1), at);
+        ops::drop_in_hole(engine, boxed_nest, box_id, 0, at);
+
+        let channel = |state: &AppState, id| match state.engine.widgets.get(&id) {
+            Some(WidgetItem::Nest(n)) => n.channel(),
+            _ => panic!("not a nest"),
+        };
+        let channels = [channel(&state, free_nest), channel(&state, boxed_nest)];
+        let loaded = round_trip(&state);
+
+        // Every loaded bird flies to the loaded nest on its channel
+        let widgets = &loaded.engine.widgets;
+        let birds: Vec<_> = widgets
+            .values()
+            .filter_map(|w| match w {
+                WidgetItem::Bird(b) if !b.is_copy_source() => Some(b),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(birds.len(), 2);
+        for bird in birds {
+            let nest = match bird.nest_id().and_then(|id| widgets.get(&id)) {
+                Some(WidgetItem::Nest(n)) => n,
+                _ => panic!("bird should fly to a loaded nest"),
+            };
+            assert_eq!(bird.channel(), Some(nest.channel()));
+            assert!(channels.contains(&nest.channel()));
+        }
+    }
+
+    #[test]
+    fn test_sensors_keep_their_nest_and_switch_after_save_and_load() {
+        use tt_rs_drag::Position;
+        use tt_rs_engine::ops;
+        use tt_rs_sensors::SensorKind;
+
+        use crate::widget_item::WidgetItem;
+
+        let mut state = AppState::new();
+        let at = Position::new(10.0, 10.0);
+        let sensor = ops::hatch_sensor(&mut state.engine, SensorKind::Keyboard, at);
+        ops::toggle_sensor(&mut state.engine, sensor);
+        state.engine.sensors_on = false;
+
+        let loaded = round_trip(&state);
+
+        assert!(!loaded.engine.sensors_on);
+        let widgets = &loaded.engine.widgets;
+        let sensor = widgets
+            .values()
+            .find_map(|w| match w {
+                WidgetItem::Sensor(s) if !s.is_copy_source() => Some(s),
+                _ => None,
+            })
+            .expect("sensor loaded");
+        assert_eq!(sensor.kind(), SensorKind::Keyboard);
+        assert!(sensor.is_paused());
+        match sensor.nest_id().and_then(|id| widgets.get(&id)) {
+            Some(WidgetItem::Nest(n)) => assert_eq!(sensor.channel(), Some(n.channel())),
+            _ => panic!("sensor should report to a loaded nest"),
+        }
+    }
+
+    #[test]
+    fn test_workspaces_without_the_switch_have_sensors_on() {
+        let workspace: Workspace =
+            serde_json::from_str(PUZZLE_FILL_BOX).expect("Failed to parse puzzle-fill-box.json");
+        assert!(workspace.sensors_on);
+    }
 }
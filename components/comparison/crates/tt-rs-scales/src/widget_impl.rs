@@ -1,6 +1,7 @@
 //! Widget trait implementation for Scales.
 
 use crate::scales::{CompareResult, Scales};
+use std::any::Any;
 use tt_rs_core::{MatchResult, Widget, WidgetId};
 use yew::prelude::*;
 
@@ -13,13 +14,17 @@ impl Widget for Scales {
         self.id
     }
 
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
     fn copy(&self) -> Box<dyn Widget> {
         Box::new(Scales::new())
     }
 
     fn matches(&self, other: &dyn Widget) -> MatchResult {
         if other.type_name() == "scales" {
-            MatchResult::Match
+            MatchResult::matched(other.id())
         } else {
             MatchResult::NoMatch
         }
@@ -1,44 +1,46 @@
 //! Bird struct and constructors.
 
-use tt_rs_core::WidgetId;
+use std::collections::VecDeque;
+use tt_rs_core::{Widget, WidgetId};
+use tt_rs_nest::{Channel, Nest};
 
 /// A bird widget that delivers messages to its home nest.
 ///
 /// Birds are the sending end of ToonTalk's message passing system.
 /// When a widget is dropped on a bird, the bird flies to its paired
-/// nest and delivers the message.
-#[derive(Debug, Clone)]
+/// nest and delivers the message. Messages given while it is away wait
+/// with the bird until it comes back.
+#[derive(Debug)]
 pub struct Bird {
     pub(crate) id: WidgetId,
-    pub(crate) color: BirdColor,
+    /// The channel of the nest the bird flies to (None: no nest).
+    pub(crate) channel: Option<Channel>,
     pub(crate) nest_id: Option<WidgetId>,
     pub(crate) state: BirdState,
+    /// The message being flown to the nest.
+    pub(crate) carrying: Option<Box<dyn Widget>>,
+    /// Messages given while the bird was away, oldest first.
+    pub(crate) queue: VecDeque<Box<dyn Widget>>,
     pub(crate) is_copy_source: bool,
 }
 
-/// Colors for bird/nest pairs (must match NestColor).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub enum BirdColor {
-    #[default]
-    Blue,
-    Red,
-    Green,
-    Yellow,
-}
-
-impl From<tt_rs_nest::NestColor> for BirdColor {
-    fn from(color: tt_rs_nest::NestColor) -> Self {
-        match color {
-            tt_rs_nest::NestColor::Blue => BirdColor::Blue,
-            tt_rs_nest::NestColor::Red => BirdColor::Red,
-            tt_rs_nest::NestColor::Green => BirdColor::Green,
-            tt_rs_nest::NestColor::Yellow => BirdColor::Yellow,
+impl Clone for Bird {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            channel: self.channel,
+            nest_id: self.nest_id,
+            state: self.state,
+            // Deep copy each message using Widget::copy()
+            carrying: self.carrying.as_ref().map(|m| m.copy()),
+            queue: self.queue.iter().map(|m| m.copy()).collect(),
+            is_copy_source: self.is_copy_source,
         }
     }
 }
 
 /// State of the bird.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum BirdState {
     /// Bird is idle at rest, ready to receive a message.
     #[default]
@@ -50,35 +52,34 @@ pub enum BirdState {
 }
 
 impl Bird {
-    /// Creates a new bird with the default color and no nest.
+    /// Creates a new bird with no nest.
     pub fn new() -> Self {
         Self {
             id: WidgetId::new(),
-            color: BirdColor::default(),
+            channel: None,
             nest_id: None,
             state: BirdState::Idle,
+            carrying: None,
+            queue: VecDeque::new(),
             is_copy_source: false,
         }
     }
 
-    /// Creates a new bird with a specific color.
-    pub fn with_color(color: BirdColor) -> Self {
-        Self {
-            id: WidgetId::new(),
-            color,
-            nest_id: None,
-            state: BirdState::Idle,
-            is_copy_source: false,
-        }
+    /// Hatches a new bird bonded to a nest: it flies there and wears the
+    /// nest's channel.
+    pub fn hatch(nest: &Nest) -> Self {
+        Self::with_nest(nest.id(), nest.channel())
     }
 
-    /// Creates a new bird paired with a specific nest.
-    pub fn with_nest(nest_id: WidgetId, color: BirdColor) -> Self {
+    /// Creates a new bird paired with the nest with this ID and channel.
+    pub fn with_nest(nest_id: WidgetId, channel: Channel) -> Self {
         Self {
             id: WidgetId::new(),
-            color,
+            channel: Some(channel),
             nest_id: Some(nest_id),
             state: BirdState::Idle,
+            carrying: None,
+            queue: VecDeque::new(),
             is_copy_source: false,
         }
     }
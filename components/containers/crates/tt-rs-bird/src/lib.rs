@@ -2,7 +2,9 @@
 //!
 //! Birds are the delivery end of ToonTalk's bird/nest messaging system.
 //! Each bird has a home nest. When given a message (widget), the bird
-//! flies to its nest and delivers the message.
+//! flies to its nest, delivers the message and flies back; messages given
+//! while it is away wait with it until it returns. A bird wears its
+//! nest's channel colour and label; [`Bird::hatch`] makes one for a nest.
 //!
 //! # Module Organization
 //!
@@ -15,4 +17,4 @@ mod ops;
 mod rendering;
 mod widget_impl;
 
-pub use bird::{Bird, BirdColor, BirdState};
+pub use bird::{Bird, BirdState};
@@ -1,8 +1,11 @@
 //! Accessor methods for Bird.
 
-use tt_rs_core::WidgetId;
+use std::collections::VecDeque;
+use tt_rs_core::{Widget, WidgetId};
 
-use crate::{Bird, BirdColor, BirdState};
+use tt_rs_nest::{Channel, Nest};
+
+use crate::{Bird, BirdState};
 
 impl Bird {
     /// Returns the bird's unique ID.
@@ -10,9 +13,9 @@ impl Bird {
         self.id
     }
 
-    /// Returns the bird's color.
-    pub fn color(&self) -> BirdColor {
-        self.color
+    /// Returns the channel of the bird's nest, if it has one.
+    pub fn channel(&self) -> Option<Channel> {
+        self.channel
     }
 
     /// Returns the ID of the bird's home nest, if any.
@@ -25,6 +28,26 @@ impl Bird {
         self.state
     }
 
+    /// Returns true if the bird is away delivering (or coming back).
+    pub fn is_away(&self) -> bool {
+        self.state != BirdState::Idle
+    }
+
+    /// Returns the message being flown to the nest, if any.
+    pub fn carrying(&self) -> Option<&dyn Widget> {
+        self.carrying.as_deref()
+    }
+
+    /// Returns the number of messages waiting for the bird to come back.
+    pub fn queued_count(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns the messages waiting for the bird to come back, oldest first.
+    pub fn queued(&self) -> impl Iterator<Item = &dyn Widget> {
+        self.queue.iter().map(|m| m.as_ref())
+    }
+
     /// Returns whether this bird is a copy source (palette item).
     pub fn is_copy_source(&self) -> bool {
         self.is_copy_source
@@ -39,9 +62,11 @@ impl Bird {
     pub fn copy_bird(&self) -> Self {
         Self {
             id: WidgetId::new(),
-            color: self.color,
+            channel: self.channel,
             nest_id: self.nest_id,
             state: BirdState::Idle,
+            carrying: None,
+            queue: VecDeque::new(),
             is_copy_source: false,
         }
     }
@@ -52,9 +77,24 @@ impl Bird {
         self
     }
 
-    /// Pairs this bird with a nest.
-    pub fn pair_with_nest(&mut self, nest_id: WidgetId) {
-        self.nest_id = Some(nest_id);
+    /// Bonds this bird to a nest: from now on it flies there, and wears
+    /// the nest's channel.
+    pub fn bond(&mut self, nest: &Nest) {
+        self.nest_id = Some(nest.id());
+        self.channel = Some(nest.channel());
+    }
+
+    /// Gives the bird a message. An idle bird sets off with it at once;
+    /// one that is away keeps it until it comes back.
+    /// Returns true if the bird set off.
+    pub fn give(&mut self, message: Box<dyn Widget>) -> bool {
+        if self.is_away() {
+            self.queue.push_back(message);
+            return false;
+        }
+        self.carrying = Some(message);
+        self.start_flying();
+        true
     }
 
     /// Starts the bird flying to deliver a message.
@@ -62,25 +102,20 @@ impl Bird {
         self.state = BirdState::Flying;
     }
 
-    /// Bird has delivered its message, now returning.
-    pub fn start_returning(&mut self) {
+    /// The bird reached the nest: it lets go of its message and heads back.
+    pub fn start_returning(&mut self) -> Option<Box<dyn Widget>> {
         self.state = BirdState::Returning;
+        self.carrying.take()
     }
 
-    /// Bird has returned and is idle.
-    pub fn arrive_home(&mut self) {
+    /// The bird is back. It sets off again at once with the next waiting
+    /// message, if there is one; otherwise it is idle.
+    /// Returns true if it set off again.
+    pub fn arrive_home(&mut self) -> bool {
         self.state = BirdState::Idle;
-    }
-}
-
-impl BirdColor {
-    /// Returns the CSS color for this bird color.
-    pub fn css_color(&self) -> &'static str {
-        match self {
-            BirdColor::Blue => "#63B3ED",
-            BirdColor::Red => "#FC8181",
-            BirdColor::Green => "#68D391",
-            BirdColor::Yellow => "#F6E05E",
+        match self.queue.pop_front() {
+            Some(message) => self.give(message),
+            None => false,
         }
     }
 }
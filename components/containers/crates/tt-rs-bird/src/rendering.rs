@@ -1,17 +1,32 @@
 //! Rendering functions for Bird.
 
-use crate::Bird;
+use crate::{Bird, BirdState};
 use yew::prelude::*;
 
 /// Renders a Bird as HTML using the tt-bird.svg asset.
+/// A bird in flight carries a miniature of its message.
 pub fn render(bird: &Bird) -> Html {
     let widget_id = bird.id.to_string();
     let is_copy_source = bird.is_copy_source();
+    let queued = bird.queued_count();
 
     let class = if is_copy_source {
-        "widget bird copy-source"
+        "widget bird copy-source".to_string()
     } else {
-        "widget bird"
+        match bird.state() {
+            BirdState::Idle => "widget bird".to_string(),
+            BirdState::Flying => "widget bird flying".to_string(),
+            BirdState::Returning => "widget bird returning".to_string(),
+        }
+    };
+
+    let carrying_html = match bird.carrying() {
+        Some(message) => html! {
+            <div class="bird-carrying">
+                { message.render() }
+            </div>
+        },
+        None => html! {},
     };
 
     html! {
@@ -19,6 +34,15 @@ pub fn render(bird: &Bird) -> Html {
              data-widget-id={widget_id}
              data-copy-source={is_copy_source.to_string()}>
             <img src="images/tt-bird.svg" alt="Bird" class="bird-img"/>
+            if !is_copy_source {
+                if let Some(channel) = bird.channel() {
+                    { channel.render_tag() }
+                }
+            }
+            { carrying_html }
+            if queued > 0 {
+                <div class="bird-badge">{format!("+{}", queued)}</div>
+            }
         </div>
     }
 }
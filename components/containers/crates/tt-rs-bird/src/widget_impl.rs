@@ -2,7 +2,7 @@
 
 use crate::bird::Bird;
 use crate::rendering;
-use crate::BirdColor;
+use std::any::Any;
 use tt_rs_core::{MatchResult, Widget, WidgetId};
 use yew::Html;
 
@@ -15,6 +15,10 @@ impl Widget for Bird {
         self.id
     }
 
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
     fn copy(&self) -> Box<dyn Widget> {
         Box::new(self.copy_bird())
     }
@@ -23,8 +27,8 @@ impl Widget for Bird {
         if other.type_name() != "bird" {
             return MatchResult::NoMatch;
         }
-        // Birds match if they have the same color
-        MatchResult::Match
+        // Any bird matches any other, whatever its channel
+        MatchResult::matched(other.id())
     }
 
     fn render(&self) -> Html {
@@ -32,16 +36,10 @@ impl Widget for Bird {
     }
 
     fn description(&self) -> String {
-        let color_name = match self.color {
-            BirdColor::Blue => "blue",
-            BirdColor::Red => "red",
-            BirdColor::Green => "green",
-            BirdColor::Yellow => "yellow",
-        };
-        if self.nest_id.is_some() {
-            format!("{color_name} bird (has nest)")
-        } else {
-            format!("{color_name} bird (no nest)")
+        match (self.nest_id, self.channel) {
+            (Some(_), Some(channel)) => format!("bird {} (has nest)", channel.label()),
+            (Some(_), None) => "bird (has nest)".to_string(),
+            (None, _) => "bird (no nest)".to_string(),
         }
     }
 }
@@ -1,26 +1,23 @@
 //! Hole type for box slots.
 
-use tt_rs_core::WidgetId;
-use yew::Html;
-
-// Note: Html import used in HoleContent struct
+use tt_rs_core::Widget;
 
 /// A hole in a box that can optionally contain a widget.
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct Hole {
     /// The index of this hole in the box.
     index: usize,
-    /// The content of the hole, if any.
-    content: Option<HoleContent>,
+    /// The widget in the hole, if any.
+    content: Option<Box<dyn Widget>>,
 }
 
-/// Content that can be placed in a hole.
-#[derive(Debug, Clone)]
-pub struct HoleContent {
-    /// The ID of the widget in this hole.
-    pub widget_id: WidgetId,
-    /// The rendered HTML of the widget.
-    pub html: Html,
+impl Clone for Hole {
+    fn clone(&self) -> Self {
+        Self {
+            index: self.index,
+            content: self.content.as_ref().map(|w| w.copy()),
+        }
+    }
 }
 
 impl Hole {
@@ -42,14 +39,14 @@ impl Hole {
         self.content.is_none()
     }
 
-    /// Returns the content of this hole, if any.
-    pub fn content(&self) -> Option<&HoleContent> {
-        self.content.as_ref()
+    /// Returns the widget in this hole, if any.
+    pub fn content(&self) -> Option<&dyn Widget> {
+        self.content.as_deref()
     }
 
     /// Sets the content of this hole.
-    pub fn set_content(&mut self, widget_id: WidgetId, html: Html) {
-        self.content = Some(HoleContent { widget_id, html });
+    pub fn set_content(&mut self, widget: Box<dyn Widget>) {
+        self.content = Some(widget);
     }
 
     /// Clears the content of this hole.
@@ -58,7 +55,7 @@ impl Hole {
     }
 
     /// Takes the content out of this hole, leaving it empty.
-    pub fn take(&mut self) -> Option<HoleContent> {
+    pub fn take(&mut self) -> Option<Box<dyn Widget>> {
         self.content.take()
     }
 }
@@ -10,8 +10,10 @@
 //! - [`Hole`] - hole struct and operations
 //! - `ops` - accessor and query methods
 //! - `mutators` - mutation methods
+//! - `matching` - structural pattern matching
 
 mod hole;
+mod matching;
 mod mutators;
 mod ops;
 mod rendering;
@@ -0,0 +1,69 @@
+//! Structural pattern matching for ToonBox.
+//!
+//! A box pattern matches a box of the same size whose holes match hole by
+//! hole. Empty pattern holes are wildcards. Erased boxes ignore contents:
+//! `erased()` matches any box, `erased_with_size(n)` any box with n holes.
+//!
+//! A nest in a target hole stands for its first message. If the nest is
+//! empty the match is suspended until a message arrives, unless another
+//! hole already fails (a mismatch always wins over a suspension).
+
+use crate::ToonBox;
+use tt_rs_core::{Bindings, MatchResult, Widget};
+use tt_rs_nest::Nest;
+
+/// Checks if a ToonBox pattern matches another widget.
+pub fn matches(pattern: &ToonBox, other: &dyn Widget) -> MatchResult {
+    let target = match other.as_any().downcast_ref::<ToonBox>() {
+        Some(b) => b,
+        None => return MatchResult::NoMatch,
+    };
+    if pattern.is_erased() && pattern.is_empty() {
+        return MatchResult::matched(target.id());
+    }
+    if pattern.len() != target.len() {
+        return MatchResult::NoMatch;
+    }
+
+    let mut bindings = Bindings::root(target.id());
+    let mut suspended = None;
+    for (i, (p, t)) in pattern.holes().zip(target.holes()).enumerate() {
+        let p = if pattern.is_erased() {
+            None
+        } else {
+            p.content()
+        };
+        match match_hole(p, t.content()) {
+            MatchResult::Match(inner) => bindings.nest(&format!("hole:{}", i), inner),
+            MatchResult::NoMatch => return MatchResult::NoMatch,
+            waiting @ MatchResult::Suspended { .. } => {
+                suspended.get_or_insert(waiting);
+            }
+        }
+    }
+    suspended.unwrap_or(MatchResult::Match(bindings))
+}
+
+fn match_hole(pattern: Option<&dyn Widget>, target: Option<&dyn Widget>) -> MatchResult {
+    match (pattern, target) {
+        // Empty pattern hole is a wildcard
+        (None, None) => MatchResult::Match(Bindings::new()),
+        (None, Some(t)) => MatchResult::matched(t.id()),
+        (Some(_), None) => MatchResult::NoMatch,
+        (Some(p), Some(t)) => match_slot(p, t),
+    }
+}
+
+/// Matches a filled pattern hole, looking through nests to their messages.
+fn match_slot(pattern: &dyn Widget, target: &dyn Widget) -> MatchResult {
+    let nest = match target.as_any().downcast_ref::<Nest>() {
+        Some(n) if pattern.as_any().downcast_ref::<Nest>().is_none() => n,
+        _ => return pattern.matches(target),
+    };
+    match nest.peek_top() {
+        Some(message) => pattern.matches(message),
+        None => MatchResult::Suspended {
+            waiting_on: nest.id(),
+        },
+    }
+}
@@ -1,8 +1,7 @@
 //! Mutation methods for ToonBox.
 
 use crate::{Hole, ToonBox};
-use tt_rs_core::WidgetId;
-use yew::Html;
+use tt_rs_core::Widget;
 
 impl ToonBox {
     /// Gets a mutable reference to the hole at the given index.
@@ -10,10 +9,10 @@ impl ToonBox {
         self.holes.get_mut(index)
     }
 
-    /// Sets the content of a hole at the given index.
-    pub fn set_hole(&mut self, index: usize, widget_id: WidgetId, html: Html) -> bool {
+    /// Places a widget in the hole at the given index.
+    pub fn set_hole(&mut self, index: usize, widget: Box<dyn Widget>) -> bool {
         if let Some(hole) = self.holes.get_mut(index) {
-            hole.set_content(widget_id, html);
+            hole.set_content(widget);
             true
         } else {
             false
@@ -30,6 +29,56 @@ impl ToonBox {
         }
     }
 
+    /// Erases the pattern part at `path` (hole indices, outermost first)
+    /// one level further; an empty path erases the box itself. A widget
+    /// that can't be erased further is removed, leaving a wildcard hole.
+    /// Returns false if there is nothing there to erase.
+    pub fn erase_at(&mut self, path: &[usize]) -> bool {
+        let Some((&index, rest)) = path.split_first() else {
+            return match self.erased_further() {
+                Some(erased) => {
+                    *self = erased;
+                    true
+                }
+                None => false,
+            };
+        };
+        let Some(hole) = self.holes.get_mut(index) else {
+            return false;
+        };
+        let Some(content) = hole.take() else {
+            return false;
+        };
+        if rest.is_empty() {
+            if let Some(erased) = content.erase() {
+                hole.set_content(erased);
+            }
+            return true;
+        }
+        match content.as_any().downcast_ref::<ToonBox>() {
+            Some(inner) => {
+                let mut inner = inner.clone();
+                let erased = inner.erase_at(rest);
+                hole.set_content(Box::new(inner));
+                erased
+            }
+            None => {
+                hole.set_content(content);
+                false
+            }
+        }
+    }
+
+    /// A box matches any box of its size, then any box at all.
+    pub(crate) fn erased_further(&self) -> Option<ToonBox> {
+        let erased = match (self.erased, self.holes.is_empty()) {
+            (false, _) => ToonBox::erased_with_size(self.holes.len()),
+            (true, false) => ToonBox::erased(),
+            (true, true) => return None,
+        };
+        Some(erased.with_id(self.id))
+    }
+
     /// Resizes the box to have the specified number of holes.
     ///
     /// If increasing size, new empty holes are appended.
@@ -50,4 +50,10 @@ impl ToonBox {
             erased: true,
         }
     }
+
+    /// Gives the box a specific ID (e.g. to mirror a workspace box).
+    pub fn with_id(mut self, id: WidgetId) -> Self {
+        self.id = id;
+        self
+    }
 }
@@ -1,6 +1,7 @@
 //! Widget trait implementation for ToonBox.
 
-use crate::{rendering, ToonBox};
+use crate::{matching, rendering, ToonBox};
+use std::any::Any;
 use tt_rs_core::{MatchResult, Widget, WidgetId};
 use yew::Html;
 
@@ -13,18 +14,27 @@ impl Widget for ToonBox {
         self.id
     }
 
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
     fn render(&self) -> Html {
         rendering::render(self)
     }
 
     fn copy(&self) -> Box<dyn Widget> {
-        let mut new_box = ToonBox::new(self.len());
-        new_box.erased = self.is_erased();
+        // Cloning copies each hole's widget with a new ID
+        let mut new_box = self.clone();
+        new_box.id = WidgetId::new();
         Box::new(new_box)
     }
 
+    fn erase(&self) -> Option<Box<dyn Widget>> {
+        Some(Box::new(self.erased_further()?))
+    }
+
     fn matches(&self, other: &dyn Widget) -> MatchResult {
-        rendering::matches(self, other)
+        matching::matches(self, other)
     }
 
     fn description(&self) -> String {
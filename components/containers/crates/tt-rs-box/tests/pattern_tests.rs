@@ -2,6 +2,9 @@
 
 use tt_rs_box::ToonBox;
 use tt_rs_core::{MatchResult, Widget};
+use tt_rs_nest::Nest;
+use tt_rs_number::Number;
+use tt_rs_text::Text;
 
 #[test]
 fn test_erased_matches_any_box() {
@@ -10,9 +13,9 @@ fn test_erased_matches_any_box() {
     let box2 = ToonBox::new(5);
     let box3 = ToonBox::new(0);
 
-    assert_eq!(pattern.matches(&box1), MatchResult::Match);
-    assert_eq!(pattern.matches(&box2), MatchResult::Match);
-    assert_eq!(pattern.matches(&box3), MatchResult::Match);
+    assert!(pattern.matches(&box1).is_match());
+    assert!(pattern.matches(&box2).is_match());
+    assert!(pattern.matches(&box3).is_match());
 }
 
 #[test]
@@ -21,7 +24,7 @@ fn test_erased_with_size_matches_same_size() {
     let box_same = ToonBox::new(3);
     let box_different = ToonBox::new(4);
 
-    assert_eq!(pattern.matches(&box_same), MatchResult::Match);
+    assert!(pattern.matches(&box_same).is_match());
     assert_eq!(pattern.matches(&box_different), MatchResult::NoMatch);
 }
 
@@ -31,7 +34,7 @@ fn test_box_matches_same_size() {
     let box2 = ToonBox::new(3);
     let box3 = ToonBox::new(4);
 
-    assert_eq!(box1.matches(&box2), MatchResult::Match);
+    assert!(box1.matches(&box2).is_match());
     assert_eq!(box1.matches(&box3), MatchResult::NoMatch);
 }
 
@@ -56,3 +59,213 @@ fn test_copy() {
     assert_ne!(original.id(), copy.id());
     assert_eq!(original.description(), copy.description());
 }
+
+#[test]
+fn test_box_matches_hole_contents() {
+    let mut pattern = ToonBox::new(2);
+    pattern.set_hole(0, Box::new(Number::new(1)));
+
+    let mut same = ToonBox::new(2);
+    same.set_hole(0, Box::new(Number::new(1)));
+    same.set_hole(1, Box::new(Text::new("anything")));
+
+    let mut different = ToonBox::new(2);
+    different.set_hole(0, Box::new(Number::new(2)));
+
+    assert!(pattern.matches(&same).is_match());
+    assert_eq!(pattern.matches(&different), MatchResult::NoMatch);
+}
+
+#[test]
+fn test_filled_pattern_hole_needs_content() {
+    let mut pattern = ToonBox::new(1);
+    pattern.set_hole(0, Box::new(Number::erased()));
+    let empty = ToonBox::new(1);
+    let mut filled = ToonBox::new(1);
+    filled.set_hole(0, Box::new(Number::new(7)));
+
+    assert_eq!(pattern.matches(&empty), MatchResult::NoMatch);
+    assert!(pattern.matches(&filled).is_match());
+}
+
+#[test]
+fn test_erased_contents_respect_type() {
+    let mut pattern = ToonBox::new(1);
+    pattern.set_hole(0, Box::new(Text::erased()));
+    let mut number = ToonBox::new(1);
+    number.set_hole(0, Box::new(Number::new(7)));
+
+    assert_eq!(pattern.matches(&number), MatchResult::NoMatch);
+}
+
+#[test]
+fn test_nested_boxes_recurse() {
+    let mut inner_pattern = ToonBox::new(1);
+    inner_pattern.set_hole(0, Box::new(Number::new(3)));
+    let mut pattern = ToonBox::new(1);
+    pattern.set_hole(0, Box::new(inner_pattern));
+
+    let mut inner = ToonBox::new(1);
+    inner.set_hole(0, Box::new(Number::new(3)));
+    let mut target = ToonBox::new(1);
+    target.set_hole(0, Box::new(inner));
+
+    let mut wrong_inner = ToonBox::new(1);
+    wrong_inner.set_hole(0, Box::new(Number::new(4)));
+    let mut wrong = ToonBox::new(1);
+    wrong.set_hole(0, Box::new(wrong_inner));
+
+    assert!(pattern.matches(&target).is_match());
+    assert_eq!(pattern.matches(&wrong), MatchResult::NoMatch);
+}
+
+#[test]
+fn test_copy_copies_contents() {
+    let mut original = ToonBox::new(1);
+    original.set_hole(0, Box::new(Number::new(5)));
+    let copy = original.copy();
+
+    assert_eq!(copy.description(), "box[1/1]");
+    assert!(original.matches(copy.as_ref()).is_match());
+}
+
+#[test]
+fn test_match_binds_holes_by_path() {
+    let mut inner_pattern = ToonBox::new(1);
+    inner_pattern.set_hole(0, Box::new(Number::erased()));
+    let mut pattern = ToonBox::new(2);
+    pattern.set_hole(1, Box::new(inner_pattern));
+
+    let first = Text::new("a");
+    let number = Number::new(3);
+    let (first_id, number_id) = (first.id(), number.id());
+    let mut inner = ToonBox::new(1);
+    inner.set_hole(0, Box::new(number));
+    let inner_id = inner.id();
+    let mut target = ToonBox::new(2);
+    target.set_hole(0, Box::new(first));
+    target.set_hole(1, Box::new(inner));
+
+    let result = pattern.matches(&target);
+    let bindings = result.bindings().expect("should match");
+    assert_eq!(bindings.get(""), Some(target.id()));
+    assert_eq!(bindings.get("hole:0"), Some(first_id));
+    assert_eq!(bindings.get("hole:1"), Some(inner_id));
+    assert_eq!(bindings.get("hole:1/hole:0"), Some(number_id));
+}
+
+#[test]
+fn test_empty_nest_suspends() {
+    let mut pattern = ToonBox::new(1);
+    pattern.set_hole(0, Box::new(Number::erased()));
+    let nest = Nest::new();
+    let nest_id = nest.id();
+    let mut target = ToonBox::new(1);
+    target.set_hole(0, Box::new(nest));
+
+    assert_eq!(
+        pattern.matches(&target),
+        MatchResult::Suspended {
+            waiting_on: nest_id
+        }
+    );
+}
+
+#[test]
+fn test_nest_with_message_matches_message() {
+    let mut pattern = ToonBox::new(1);
+    pattern.set_hole(0, Box::new(Number::new(5)));
+    let mut nest = Nest::new();
+    nest.receive(Box::new(Number::new(5)));
+    let mut target = ToonBox::new(1);
+    target.set_hole(0, Box::new(nest));
+
+    assert!(pattern.matches(&target).is_match());
+}
+
+#[test]
+fn test_nest_pattern_matches_any_nest() {
+    let mut pattern = ToonBox::new(1);
+    pattern.set_hole(0, Box::new(Nest::new()));
+    let mut nest = Nest::new();
+    nest.receive(Box::new(Number::new(5)));
+    let mut target = ToonBox::new(1);
+    target.set_hole(0, Box::new(nest));
+    assert!(pattern.matches(&target).is_match());
+
+    target.set_hole(0, Box::new(Number::new(5)));
+    assert_eq!(pattern.matches(&target), MatchResult::NoMatch);
+}
+
+#[test]
+fn test_mismatch_wins_over_suspension() {
+    let mut pattern = ToonBox::new(2);
+    pattern.set_hole(0, Box::new(Number::erased()));
+    pattern.set_hole(1, Box::new(Number::new(1)));
+    let mut target = ToonBox::new(2);
+    target.set_hole(0, Box::new(Nest::new()));
+    target.set_hole(1, Box::new(Number::new(2)));
+
+    assert_eq!(pattern.matches(&target), MatchResult::NoMatch);
+}
+
+#[test]
+fn test_erase_at_steps_through_levels() {
+    let mut pattern = ToonBox::new(2);
+    pattern.set_hole(0, Box::new(Number::new(3)));
+    pattern.set_hole(1, Box::new(Text::new("hi")));
+    let mut target = ToonBox::new(2);
+    target.set_hole(0, Box::new(Number::new(7)));
+    target.set_hole(1, Box::new(Text::new("hi")));
+    assert_eq!(pattern.matches(&target), MatchResult::NoMatch);
+
+    // 3 becomes "any number"
+    assert!(pattern.erase_at(&[0]));
+    assert!(pattern.matches(&target).is_match());
+    assert_eq!(
+        pattern
+            .hole(0)
+            .and_then(|h| h.content())
+            .map(|w| w.description()),
+        Some("erased number".to_string())
+    );
+
+    // Erasing again empties the hole, which matches anything
+    assert!(pattern.erase_at(&[0]));
+    assert!(pattern.hole(0).unwrap().is_empty());
+    assert!(!pattern.erase_at(&[0]));
+    target.set_hole(0, Box::new(Text::new("not a number")));
+    assert!(pattern.matches(&target).is_match());
+}
+
+#[test]
+fn test_erase_at_reaches_nested_boxes() {
+    let mut inner = ToonBox::new(1);
+    inner.set_hole(0, Box::new(Number::new(1)));
+    let mut pattern = ToonBox::new(1);
+    pattern.set_hole(0, Box::new(inner));
+
+    assert!(pattern.erase_at(&[0, 0]));
+    assert!(!pattern.erase_at(&[0, 0, 0]));
+    assert!(pattern.erase_at(&[0]));
+    assert_eq!(
+        pattern
+            .hole(0)
+            .and_then(|h| h.content())
+            .map(|w| w.description()),
+        Some("erased box[1]".to_string())
+    );
+    assert!(pattern
+        .matches(&{
+            let mut b = ToonBox::new(1);
+            b.set_hole(0, Box::new(ToonBox::new(1)));
+            b
+        })
+        .is_match());
+
+    assert!(pattern.erase_at(&[]));
+    assert!(pattern.erase_at(&[]));
+    assert!(pattern.is_erased() && pattern.is_empty());
+    assert!(!pattern.erase_at(&[]));
+    assert!(pattern.matches(&ToonBox::new(4)).is_match());
+}
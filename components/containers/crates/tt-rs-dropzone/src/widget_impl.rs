@@ -2,6 +2,7 @@
 
 use crate::dropzone::DropZone;
 use crate::rendering;
+use std::any::Any;
 use tt_rs_core::{MatchResult, Widget, WidgetId};
 use yew::Html;
 
@@ -14,6 +15,10 @@ impl Widget for DropZone {
         self.id
     }
 
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
     fn copy(&self) -> Box<dyn Widget> {
         Box::new(self.copy_dropzone())
     }
@@ -25,7 +30,7 @@ impl Widget for DropZone {
         }
 
         if other.description() == self.description() {
-            MatchResult::Match
+            MatchResult::matched(other.id())
         } else {
             MatchResult::NoMatch
         }
@@ -0,0 +1,54 @@
+//! Channel identity shared by a nest and the birds that fly to it.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+/// Golden angle in degrees: successive hues land far apart on the wheel.
+const GOLDEN_ANGLE: f64 = 137.508;
+
+/// Identity of a bird/nest channel.
+///
+/// Every new nest gets the next channel; its birds share it. A channel
+/// shows as a colour from a generated palette plus a short label
+/// ("A" .. "Z", "AA" ..), so dozens of channels stay distinguishable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Channel(u32);
+
+impl Channel {
+    /// Returns a channel no nest has used yet.
+    pub fn next() -> Self {
+        Self(COUNTER.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// Returns the channel with this index (e.g. read from a saved
+    /// workspace). Channels handed out later never repeat it.
+    pub fn restore(index: u32) -> Self {
+        COUNTER.fetch_max(index.saturating_add(1), Ordering::Relaxed);
+        Self(index)
+    }
+
+    /// Returns the channel's index.
+    pub fn index(&self) -> u32 {
+        self.0
+    }
+
+    /// Returns the CSS colour for this channel.
+    pub fn css_color(&self) -> String {
+        let hue = (f64::from(self.0) * GOLDEN_ANGLE) % 360.0;
+        format!("hsl({hue:.0}, 70%, 55%)")
+    }
+
+    /// Returns the channel's short label: "A" .. "Z", then "AA", "AB", ...
+    pub fn label(&self) -> String {
+        let mut n = self.0 as usize + 1;
+        let mut label = Vec::new();
+        while n > 0 {
+            n -= 1;
+            label.push(b'A' + (n % 26) as u8);
+            n /= 26;
+        }
+        label.reverse();
+        String::from_utf8(label).unwrap_or_default()
+    }
+}
@@ -2,18 +2,22 @@
 //!
 //! Nests are the receiving end of ToonTalk's bird/nest messaging system.
 //! Birds deliver messages to their paired nest, where they queue until retrieved.
+//! A nest and its birds share a [`Channel`], shown as a colour and a label.
 //!
 //! # Module Organization
 //!
 //! - [`Nest`] - struct and constructors
+//! - [`Channel`] - channel identity: generated colour and label
 //! - `ops` - accessor methods
 //! - `mutators` - message receive/take operations
 //! - `rendering` - HTML rendering
 
+mod channel;
 mod mutators;
 mod nest;
 mod ops;
 mod rendering;
 mod widget_impl;
 
-pub use nest::{Nest, NestColor};
+pub use channel::Channel;
+pub use nest::Nest;
@@ -1,6 +1,6 @@
 //! Mutator methods for Nest.
 
-use crate::Nest;
+use crate::{Channel, Nest};
 use tt_rs_core::Widget;
 
 impl Nest {
@@ -14,18 +14,26 @@ impl Nest {
         self.messages.pop_front()
     }
 
+    /// Marks this nest as having no bird left to deliver here, so
+    /// robots waiting on it stop instead of waiting forever.
+    pub fn mark_no_senders(&mut self) {
+        self.no_senders = true;
+    }
+
     /// Marks this nest as a copy source (for palette).
     pub fn as_copy_source(mut self) -> Self {
         self.is_copy_source = true;
         self
     }
 
-    /// Creates a copy of this nest with a new ID.
+    /// Creates a copy of this nest with a new ID, on a channel of its own
+    /// (a copied nest is a new destination, not another way into this one).
     pub fn copy_nest(&self) -> Self {
         Self {
             id: tt_rs_core::WidgetId::new(),
-            color: self.color,
+            channel: Channel::next(),
             messages: VecDeque::new(), // New nest starts empty
+            no_senders: false,
             is_copy_source: false,
         }
     }
@@ -3,6 +3,8 @@
 use std::collections::VecDeque;
 use tt_rs_core::{Widget, WidgetId};
 
+use crate::Channel;
+
 /// A nest widget that receives messages from birds.
 ///
 /// Nests are the receiving end of ToonTalk's message passing system.
@@ -11,8 +13,10 @@ use tt_rs_core::{Widget, WidgetId};
 #[derive(Debug)]
 pub struct Nest {
     pub(crate) id: WidgetId,
-    pub(crate) color: NestColor,
+    pub(crate) channel: Channel,
     pub(crate) messages: VecDeque<Box<dyn Widget>>,
+    /// Set once every bird that could deliver here is gone.
+    pub(crate) no_senders: bool,
     pub(crate) is_copy_source: bool,
 }
 
@@ -20,41 +24,28 @@ impl Clone for Nest {
     fn clone(&self) -> Self {
         Self {
             id: self.id,
-            color: self.color,
+            channel: self.channel,
             // Deep copy each message using Widget::copy()
             messages: self.messages.iter().map(|m| m.copy()).collect(),
+            no_senders: self.no_senders,
             is_copy_source: self.is_copy_source,
         }
     }
 }
 
-/// Colors for nest/bird pairs.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub enum NestColor {
-    #[default]
-    Blue,
-    Red,
-    Green,
-    Yellow,
-}
-
 impl Nest {
-    /// Creates a new empty nest with the default color.
+    /// Creates a new empty nest on a channel of its own.
     pub fn new() -> Self {
-        Self {
-            id: WidgetId::new(),
-            color: NestColor::default(),
-            messages: VecDeque::new(),
-            is_copy_source: false,
-        }
+        Self::with_channel(Channel::next())
     }
 
-    /// Creates a new nest with a specific color.
-    pub fn with_color(color: NestColor) -> Self {
+    /// Creates a new empty nest on a specific channel.
+    pub fn with_channel(channel: Channel) -> Self {
         Self {
             id: WidgetId::new(),
-            color,
+            channel,
             messages: VecDeque::new(),
+            no_senders: false,
             is_copy_source: false,
         }
     }
@@ -1,12 +1,12 @@
 //! Accessor methods for Nest.
 
-use crate::{Nest, NestColor};
+use crate::{Channel, Nest};
 use tt_rs_core::Widget;
 
 impl Nest {
-    /// Returns the nest's color.
-    pub fn color(&self) -> NestColor {
-        self.color
+    /// Returns the nest's channel, shared by the birds that fly to it.
+    pub fn channel(&self) -> Channel {
+        self.channel
     }
 
     /// Returns the number of messages in the nest.
@@ -19,25 +19,23 @@ impl Nest {
         !self.messages.is_empty()
     }
 
+    /// Returns true if no bird is left to deliver here.
+    pub fn has_no_senders(&self) -> bool {
+        self.no_senders
+    }
+
     /// Returns true if this is a copy source (palette item).
     pub fn is_copy_source(&self) -> bool {
         self.is_copy_source
     }
 
+    /// Returns the queued messages, oldest first.
+    pub fn messages(&self) -> impl Iterator<Item = &dyn Widget> {
+        self.messages.iter().map(|m| m.as_ref())
+    }
+
     /// Peeks at the top (oldest) message without removing it.
     pub fn peek_top(&self) -> Option<&dyn Widget> {
         self.messages.front().map(|m| m.as_ref())
     }
 }
-
-impl NestColor {
-    /// Returns the CSS color string for this nest color.
-    pub fn css_color(&self) -> &'static str {
-        match self {
-            NestColor::Blue => "#4a90d9",
-            NestColor::Red => "#d94a4a",
-            NestColor::Green => "#4ad94a",
-            NestColor::Yellow => "#d9d94a",
-        }
-    }
-}
@@ -1,6 +1,6 @@
 //! Rendering functions for Nest.
 
-use crate::Nest;
+use crate::{Channel, Nest};
 use yew::prelude::*;
 
 /// Renders a Nest as HTML using the tt-nest.svg asset.
@@ -12,6 +12,8 @@ pub fn render(nest: &Nest) -> Html {
 
     let class = if is_copy_source {
         "widget nest copy-source"
+    } else if nest.has_no_senders() {
+        "widget nest no-senders"
     } else {
         "widget nest"
     };
@@ -33,9 +35,24 @@ pub fn render(nest: &Nest) -> Html {
              data-copy-source={is_copy_source.to_string()}>
             <img src="images/tt-nest.svg" alt="Nest" class="nest-img"/>
             { top_message_html }
+            if !is_copy_source {
+                { nest.channel.render_tag() }
+            }
             if msg_count > 1 {
                 <div class="nest-badge">{format!("+{}", msg_count - 1)}</div>
             }
         </div>
     }
 }
+
+impl Channel {
+    /// Renders the channel's label on its colour, as worn by its nest
+    /// and birds.
+    pub fn render_tag(&self) -> Html {
+        html! {
+            <span class="channel-tag" style={format!("background: {}", self.css_color())}>
+                { self.label() }
+            </span>
+        }
+    }
+}
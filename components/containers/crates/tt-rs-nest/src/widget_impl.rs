@@ -2,6 +2,7 @@
 
 use crate::nest::Nest;
 use crate::rendering;
+use std::any::Any;
 use tt_rs_core::{MatchResult, Widget, WidgetId};
 use yew::Html;
 
@@ -14,16 +15,20 @@ impl Widget for Nest {
         self.id
     }
 
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
     fn copy(&self) -> Box<dyn Widget> {
         Box::new(self.copy_nest())
     }
 
     fn matches(&self, other: &dyn Widget) -> MatchResult {
-        if other.type_name() != "nest" {
-            return MatchResult::NoMatch;
+        // Any nest matches any other, whatever its channel or messages
+        match other.as_any().downcast_ref::<Nest>() {
+            Some(nest) => MatchResult::matched(nest.id()),
+            None => MatchResult::NoMatch,
         }
-        // Nests match if they have the same color
-        MatchResult::Match
     }
 
     fn render(&self) -> Html {
@@ -31,16 +36,11 @@ impl Widget for Nest {
     }
 
     fn description(&self) -> String {
-        let color_name = match self.color {
-            crate::NestColor::Blue => "blue",
-            crate::NestColor::Red => "red",
-            crate::NestColor::Green => "green",
-            crate::NestColor::Yellow => "yellow",
-        };
+        let label = self.channel.label();
         if self.messages.is_empty() {
-            format!("{color_name} nest (empty)")
+            format!("nest {label} (empty)")
         } else {
-            format!("{color_name} nest ({} messages)", self.messages.len())
+            format!("nest {label} ({} messages)", self.messages.len())
         }
     }
 }
@@ -0,0 +1,69 @@
+//! Pattern bindings: which widget each pattern slot matched.
+
+use crate::WidgetId;
+
+/// Widget IDs bound by a successful match, keyed by pattern path.
+///
+/// The root of the pattern has the empty path `""`. Box holes are
+/// `hole:N`, nested holes join with `/` (e.g. `hole:1/hole:0`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Bindings {
+    entries: Vec<(String, WidgetId)>,
+}
+
+impl Bindings {
+    /// Creates an empty set of bindings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates bindings with the pattern root bound to a widget.
+    pub fn root(id: WidgetId) -> Self {
+        let mut b = Self::new();
+        b.bind("", id);
+        b
+    }
+
+    /// Binds a pattern path to a widget, replacing any earlier binding.
+    pub fn bind(&mut self, path: &str, id: WidgetId) {
+        match self.entries.iter_mut().find(|(p, _)| p == path) {
+            Some(entry) => entry.1 = id,
+            None => self.entries.push((path.to_string(), id)),
+        }
+    }
+
+    /// Adds another set of bindings under a path prefix.
+    pub fn nest(&mut self, prefix: &str, inner: Bindings) {
+        for (path, id) in inner.entries {
+            let full = if path.is_empty() {
+                prefix.to_string()
+            } else {
+                format!("{}/{}", prefix, path)
+            };
+            self.bind(&full, id);
+        }
+    }
+
+    /// Returns the widget bound to a pattern path.
+    pub fn get(&self, path: &str) -> Option<WidgetId> {
+        self.entries
+            .iter()
+            .find(|(p, _)| p == path)
+            .map(|(_, id)| *id)
+    }
+
+    /// Iterates over (path, widget) pairs in binding order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, WidgetId)> {
+        self.entries.iter().map(|(p, id)| (p.as_str(), *id))
+    }
+
+    /// Returns the number of bound paths.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if nothing is bound.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
@@ -1,7 +1,9 @@
 //! tt-rs-core: Core traits and types for ToonTalk widgets.
 
+mod bindings;
 mod widget_id;
 mod widget_trait;
 
+pub use bindings::Bindings;
 pub use widget_id::WidgetId;
 pub use widget_trait::{MatchResult, Widget};
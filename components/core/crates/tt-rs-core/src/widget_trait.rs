@@ -1,15 +1,38 @@
 //! Widget trait definition.
 
-use crate::WidgetId;
+use crate::{Bindings, WidgetId};
+use std::any::Any;
 use yew::Html;
 
 /// Result of pattern matching between widgets.
 #[derive(Debug, Clone, PartialEq)]
 pub enum MatchResult {
-    /// Pattern matches the widget.
-    Match,
+    /// Pattern matches the widget, binding each pattern slot to a widget.
+    Match(Bindings),
     /// Pattern does not match.
     NoMatch,
+    /// A pattern slot hit an empty nest; try again once it has a message.
+    Suspended { waiting_on: WidgetId },
+}
+
+impl MatchResult {
+    /// A match that binds only the pattern root to the given widget.
+    pub fn matched(id: WidgetId) -> Self {
+        MatchResult::Match(Bindings::root(id))
+    }
+
+    /// Returns true if the pattern matched.
+    pub fn is_match(&self) -> bool {
+        matches!(self, MatchResult::Match(_))
+    }
+
+    /// Returns the bindings of a successful match.
+    pub fn bindings(&self) -> Option<&Bindings> {
+        match self {
+            MatchResult::Match(b) => Some(b),
+            _ => None,
+        }
+    }
 }
 
 /// The fundamental trait for all ToonTalk widgets.
@@ -20,10 +43,14 @@ pub trait Widget: std::fmt::Debug {
     /// Returns the unique identifier.
     fn id(&self) -> WidgetId;
 
+    /// Returns this widget as `Any` so matchers can inspect its concrete type.
+    fn as_any(&self) -> &dyn Any;
+
     /// Creates a deep copy with a new ID.
     fn copy(&self) -> Box<dyn Widget>;
 
-    /// Checks if this widget matches another.
+    /// Checks if this widget, used as a pattern, matches another.
+    /// Matching is structural: it compares concrete types and contents.
     fn matches(&self, other: &dyn Widget) -> MatchResult;
 
     /// Renders this widget as HTML.
@@ -31,4 +58,10 @@ pub trait Widget: std::fmt::Debug {
 
     /// Returns a text description.
     fn description(&self) -> String;
+
+    /// This widget one erasure level further, as a more general pattern.
+    /// None if it can't be erased any further (the vacuum then removes it).
+    fn erase(&self) -> Option<Box<dyn Widget>> {
+        None
+    }
 }
@@ -0,0 +1,40 @@
+//! Tests for match bindings.
+
+use tt_rs_core::{Bindings, MatchResult, WidgetId};
+
+#[test]
+fn test_nest_prefixes_paths() {
+    let (a, b) = (WidgetId::new(), WidgetId::new());
+    let mut inner = Bindings::root(a);
+    inner.bind("hole:0", b);
+
+    let mut outer = Bindings::new();
+    outer.nest("hole:1", inner);
+
+    assert_eq!(outer.get("hole:1"), Some(a));
+    assert_eq!(outer.get("hole:1/hole:0"), Some(b));
+    assert_eq!(outer.get(""), None);
+    assert_eq!(outer.len(), 2);
+}
+
+#[test]
+fn test_bind_replaces_existing_path() {
+    let (a, b) = (WidgetId::new(), WidgetId::new());
+    let mut bindings = Bindings::root(a);
+    bindings.bind("", b);
+
+    assert_eq!(bindings.get(""), Some(b));
+    assert_eq!(bindings.len(), 1);
+}
+
+#[test]
+fn test_match_result_accessors() {
+    let id = WidgetId::new();
+    let matched = MatchResult::matched(id);
+    assert!(matched.is_match());
+    assert_eq!(matched.bindings().and_then(|b| b.get("")), Some(id));
+
+    let suspended = MatchResult::Suspended { waiting_on: id };
+    assert!(!suspended.is_match());
+    assert!(suspended.bindings().is_none());
+}
@@ -6,6 +6,7 @@ mod demo_cursor;
 mod footer;
 mod help_button;
 mod help_panel;
+mod robot_watch;
 mod slide_panel;
 mod text_pane;
 mod tooltip;
@@ -20,6 +21,7 @@ pub use demo_cursor::DemoCursor;
 pub use footer::Footer;
 pub use help_button::HelpButton;
 pub use help_panel::HelpPanel;
+pub use robot_watch::{RobotSpeed, RobotWatchPanel};
 pub use slide_panel::SlidePanel;
 pub use text_pane::TextPane;
 pub use tooltip::{Tooltip, TooltipPosition};
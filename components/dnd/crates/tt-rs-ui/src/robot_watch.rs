@@ -0,0 +1,148 @@
+//! Robot watch panel component.
+//!
+//! Speed slider for robot runs, the robot processes and their states,
+//! plus the working robot's actions and pause/step/resume controls while
+//! a run is being watched.
+
+use yew::prelude::*;
+
+/// Actions an instant run performs before letting the page draw.
+const INSTANT_ACTIONS_PER_TICK: usize = 50;
+
+/// How fast watched robots perform their actions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RobotSpeed {
+    /// As fast as possible, without showing the actions. The run is
+    /// still spread over frames so a runaway robot can't hang the page.
+    #[default]
+    Instant,
+    /// 150ms per action.
+    Fast,
+    /// Half a second per action.
+    Normal,
+    /// A second per action.
+    Slow,
+    /// Two and a half seconds per action.
+    SlowMotion,
+}
+
+impl RobotSpeed {
+    /// All speeds, fastest first (slider order).
+    pub const ALL: [RobotSpeed; 5] = [
+        RobotSpeed::Instant,
+        RobotSpeed::Fast,
+        RobotSpeed::Normal,
+        RobotSpeed::Slow,
+        RobotSpeed::SlowMotion,
+    ];
+
+    /// Display name for the speed.
+    pub fn name(&self) -> &'static str {
+        match self {
+            RobotSpeed::Instant => "Instant",
+            RobotSpeed::Fast => "Fast",
+            RobotSpeed::Normal => "Normal",
+            RobotSpeed::Slow => "Slow",
+            RobotSpeed::SlowMotion => "Slow motion",
+        }
+    }
+
+    /// Time spent on each action, in milliseconds.
+    pub fn delay_ms(&self) -> u32 {
+        match self {
+            RobotSpeed::Instant => 0,
+            RobotSpeed::Fast => 150,
+            RobotSpeed::Normal => 500,
+            RobotSpeed::Slow => 1000,
+            RobotSpeed::SlowMotion => 2500,
+        }
+    }
+
+    /// Actions performed per timer tick.
+    pub fn actions_per_tick(&self) -> usize {
+        match self {
+            RobotSpeed::Instant => INSTANT_ACTIONS_PER_TICK,
+            _ => 1,
+        }
+    }
+
+    fn index(&self) -> usize {
+        Self::ALL.iter().position(|s| s == self).unwrap_or(0)
+    }
+}
+
+/// Properties for the RobotWatchPanel component.
+#[derive(Properties, Clone, PartialEq)]
+pub struct RobotWatchPanelProps {
+    /// Current speed.
+    pub speed: RobotSpeed,
+    /// Callback when the speed slider moves.
+    pub on_speed: Callback<RobotSpeed>,
+    /// The working robot's actions, in plain language (empty when idle).
+    #[prop_or_default]
+    pub actions: Vec<AttrValue>,
+    /// Index of the action about to be performed.
+    #[prop_or_default]
+    pub current: usize,
+    /// Robot processes: a label and a state (running, waiting, finished).
+    #[prop_or_default]
+    pub processes: Vec<(AttrValue, AttrValue)>,
+    /// Whether the watched run is paused.
+    #[prop_or(false)]
+    pub paused: bool,
+    /// Callback to pause or resume the run.
+    pub on_pause: Callback<()>,
+    /// Callback to perform one action while paused.
+    pub on_step: Callback<()>,
+}
+
+/// Speed slider, robot processes and, during a watched run, its actions
+/// and controls.
+#[function_component(RobotWatchPanel)]
+pub fn robot_watch_panel(props: &RobotWatchPanelProps) -> Html {
+    let on_input = {
+        let callback = props.on_speed.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            let index = input.value().parse::<usize>().unwrap_or(0);
+            callback.emit(RobotSpeed::ALL[index.min(RobotSpeed::ALL.len() - 1)]);
+        })
+    };
+    let on_pause = {
+        let cb = props.on_pause.clone();
+        Callback::from(move |_: MouseEvent| cb.emit(()))
+    };
+    let on_step = {
+        let cb = props.on_step.clone();
+        Callback::from(move |_: MouseEvent| cb.emit(()))
+    };
+    let max = (RobotSpeed::ALL.len() - 1).to_string();
+
+    html! {
+        <div class="robot-watch" title="How fast robots work. Slower speeds show each action.">
+            <label class="robot-watch-speed">
+                { format!("Robot speed: {}", props.speed.name()) }
+                <input type="range" min="0" max={max} step="1"
+                    value={props.speed.index().to_string()} oninput={on_input} />
+            </label>
+            if !props.processes.is_empty() {
+                <ul class="robot-watch-processes">
+                    { for props.processes.iter().map(|(label, state)| html! {
+                        <li class={format!("process-{}", state)}>{ format!("{}: {}", label, state) }</li>
+                    }) }
+                </ul>
+            }
+            if !props.actions.is_empty() {
+                <ol class="robot-watch-actions">
+                    { for props.actions.iter().enumerate().map(|(i, action)| html! {
+                        <li class={classes!((i == props.current).then_some("current"))}>{ action }</li>
+                    }) }
+                </ol>
+                <div class="robot-watch-controls">
+                    <button onclick={on_pause}>{ if props.paused { "Resume" } else { "Pause" } }</button>
+                    <button onclick={on_step} disabled={!props.paused}>{ "Step" }</button>
+                </div>
+            }
+        </div>
+    }
+}
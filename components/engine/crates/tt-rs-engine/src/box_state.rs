@@ -22,7 +22,6 @@ impl BoxState {
         }
     }
 
-    #[allow(dead_code)]
     pub fn erased() -> Self {
         Self {
             id: WidgetId::new(),
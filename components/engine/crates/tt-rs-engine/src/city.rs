@@ -0,0 +1,634 @@
+//! Houses and the city they stand in.
+//!
+//! Every house has its own [`World`]: a workspace and the robot processes
+//! working in it, out of reach of every other house. The app shows one
+//! world at a time (outdoors, or the house the user went into); the
+//! [`City`] keeps all the others, ticks their robots along with the shown
+//! one, and carries mail between them. Birds are the only way anything
+//! crosses from one house to another: a bird whose nest is in another
+//! house leaves its message in the outbox, and the city delivers it.
+//! Birds fly in every house, shown or not, as the city moves them along.
+//!
+//! Trucks are how new houses get built: a robot and a box loaded onto a
+//! truck wait in the city until the truck has both, then move into a new
+//! house of their own where the robot starts working on the box.
+
+use std::collections::{HashMap, HashSet};
+
+use tt_rs_core::WidgetId;
+use tt_rs_house::{House, HouseStatus};
+use tt_rs_robot::RobotState;
+
+use crate::ops;
+use crate::robot_exec::team;
+use crate::scheduler::Scheduler;
+use crate::state::EngineState;
+use crate::widget_item::WidgetItem;
+
+/// A house's workspace and the robot processes working in it.
+#[derive(Clone, Default)]
+pub struct World {
+    pub engine: EngineState,
+    pub scheduler: Scheduler,
+}
+
+impl World {
+    /// A world with no robots working yet.
+    pub fn new(engine: EngineState) -> Self {
+        Self {
+            engine,
+            scheduler: Scheduler::new(),
+        }
+    }
+
+    /// What the robots in this world are doing.
+    pub fn status(&self) -> HouseStatus {
+        status_of(&self.scheduler)
+    }
+}
+
+/// What happened to a truck something was loaded onto.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Loaded {
+    /// The truck waits for a robot or a box.
+    Waiting,
+    /// The truck drove off and this house stands in its place.
+    Built(WidgetId),
+}
+
+/// Every house's world except the one being shown.
+#[derive(Clone)]
+pub struct City {
+    /// Worlds of the houses not being shown, by house ID.
+    houses: HashMap<WidgetId, World>,
+    /// The house (or truck) each house stands in (None: outdoors).
+    parents: HashMap<WidgetId, Option<WidgetId>>,
+    /// Robots and boxes loaded onto trucks, by truck ID.
+    loads: HashMap<WidgetId, World>,
+    /// The outdoor world, while the user is inside a house.
+    outdoors: Option<World>,
+    /// The house being shown (None: outdoors).
+    inside: Option<WidgetId>,
+    /// Makes the workspace of a new house.
+    furnish: fn() -> EngineState,
+    /// Robot ticks so far, so the app can tell the city moved on.
+    ticks: u64,
+    /// Bird steps so far, so the app can tell birds elsewhere moved on.
+    flight_steps: u64,
+}
+
+impl Default for City {
+    fn default() -> Self {
+        Self::new(EngineState::new)
+    }
+}
+
+impl City {
+    /// A city whose new houses are furnished by `furnish`.
+    pub fn new(furnish: fn() -> EngineState) -> Self {
+        Self {
+            houses: HashMap::new(),
+            parents: HashMap::new(),
+            loads: HashMap::new(),
+            outdoors: None,
+            inside: None,
+            furnish,
+            ticks: 0,
+            flight_steps: 0,
+        }
+    }
+
+    /// The house being shown, or None outdoors.
+    pub fn inside(&self) -> Option<WidgetId> {
+        self.inside
+    }
+
+    /// The houses from outdoors down to the one being shown.
+    pub fn path(&self) -> Vec<WidgetId> {
+        let mut path = Vec::new();
+        let mut current = self.inside;
+        while let Some(house) = current {
+            path.push(house);
+            current = self.parents.get(&house).copied().flatten();
+        }
+        path.reverse();
+        path
+    }
+
+    /// A house's world, unless it is the one being shown.
+    pub fn world(&self, house: WidgetId) -> Option<&World> {
+        self.houses.get(&house)
+    }
+
+    /// The outdoor world, while a house is being shown.
+    pub fn outdoors(&self) -> Option<&World> {
+        self.outdoors.as_ref()
+    }
+
+    /// What is loaded onto a truck, if anything.
+    pub fn load(&self, truck: WidgetId) -> Option<&World> {
+        self.loads.get(&truck)
+    }
+
+    /// Give a house standing in `place` (a house, or None outdoors) a
+    /// world made elsewhere, such as one loaded from a file.
+    pub fn restore_house(&mut self, house: WidgetId, place: Option<WidgetId>, world: World) {
+        self.houses.insert(house, world);
+        self.parents.insert(house, place);
+    }
+
+    /// Put back what a truck was loaded with, such as a load read from a
+    /// file. The truck itself must already hold the robot and box IDs.
+    pub fn restore_load(&mut self, truck: WidgetId, load: World) {
+        self.loads.insert(truck, load);
+    }
+
+    /// What the robots in a house are doing.
+    pub fn status(&self, house: WidgetId) -> Option<HouseStatus> {
+        self.houses.get(&house).map(World::status)
+    }
+
+    /// Houses whose robots are working, not counting the one shown,
+    /// oldest first.
+    pub fn running_houses(&self) -> Vec<WidgetId> {
+        let mut running: Vec<WidgetId> = self
+            .houses
+            .iter()
+            .filter(|(_, w)| w.scheduler.is_running())
+            .map(|(&id, _)| id)
+            .collect();
+        running.sort_by_key(WidgetId::as_u64);
+        running
+    }
+
+    /// Whether robots are working anywhere but the shown world.
+    pub fn is_running(&self) -> bool {
+        self.outdoors
+            .iter()
+            .chain(self.houses.values())
+            .any(|w| w.scheduler.is_running())
+    }
+
+    /// Robot ticks so far.
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    /// Bird steps so far.
+    pub fn flight_steps(&self) -> u64 {
+        self.flight_steps
+    }
+
+    /// Give a house standing in the shown world a newly furnished world.
+    pub fn build(&mut self, house: WidgetId) {
+        if !self.houses.contains_key(&house) && self.inside != Some(house) {
+            self.houses.insert(house, World::new((self.furnish)()));
+            self.parents.insert(house, self.inside);
+        }
+    }
+
+    /// Go into a house in the shown world, which becomes the shown world.
+    pub fn enter(
+        &mut self,
+        engine: &mut EngineState,
+        scheduler: &mut Scheduler,
+        house: WidgetId,
+    ) -> bool {
+        if !is_house(engine, house) {
+            return false;
+        }
+        self.build(house);
+        let Some(world) = self.houses.remove(&house) else {
+            return false;
+        };
+        self.store_shown(engine, scheduler, world);
+        self.inside = Some(house);
+        log::info!("Entered house {}", house);
+        true
+    }
+
+    /// Go out of the shown house into the world it stands in.
+    pub fn leave(&mut self, engine: &mut EngineState, scheduler: &mut Scheduler) -> bool {
+        let Some(house) = self.inside else {
+            return false;
+        };
+        let parent = self.parents.get(&house).copied().flatten();
+        let world = match parent {
+            Some(p) => self.houses.remove(&p),
+            None => self.outdoors.take(),
+        };
+        let Some(world) = world else {
+            return false;
+        };
+        let here = World {
+            engine: std::mem::replace(engine, world.engine),
+            scheduler: std::mem::replace(scheduler, world.scheduler),
+        };
+        self.houses.insert(house, here);
+        self.inside = parent;
+        log::info!("Left house {}", house);
+        true
+    }
+
+    /// Carry a widget or box (with everything in it) from the shown world
+    /// into a house standing there. A robot brings its team along; busy
+    /// robots, palette items and the house itself can't be carried.
+    pub fn carry_in(&mut self, engine: &mut EngineState, id: WidgetId, house: WidgetId) -> bool {
+        if id == house || !is_house(engine, house) || !is_carriable(engine, id) {
+            return false;
+        }
+        self.build(house);
+        let Some(world) = self.houses.get_mut(&house) else {
+            return false;
+        };
+        detach(engine, id);
+        let mut moved = Vec::new();
+        let items = match engine.widgets.get(&id) {
+            Some(WidgetItem::Robot(_)) => team(engine, id),
+            _ => vec![id],
+        };
+        for item in items {
+            transfer(engine, &mut world.engine, item, &mut moved);
+        }
+        self.move_houses(&moved, Some(house));
+        log::info!("Carried {} into house {}", id, house);
+        true
+    }
+
+    /// Load a robot (with its team) or a box (with everything in it) from
+    /// the shown world onto a truck. Once the truck has both it drives
+    /// off, and a new house stands in its place with the robot working on
+    /// the box inside. Returns None (and loads nothing) if the item can't
+    /// go on the truck.
+    pub fn load_truck(
+        &mut self,
+        engine: &mut EngineState,
+        id: WidgetId,
+        truck: WidgetId,
+    ) -> Option<Loaded> {
+        if !is_carriable(engine, id) {
+            return None;
+        }
+        let is_robot = engine.widgets.get(&id).is_some_and(WidgetItem::is_robot);
+        let is_box = engine.boxes.contains_key(&id);
+        let Some(WidgetItem::Truck(t)) = engine.widgets.get_mut(&truck) else {
+            return None;
+        };
+        let loaded =
+            !t.is_copy_source() && ((is_robot && t.load_robot(id)) || (is_box && t.load_box(id)));
+        if !loaded {
+            return None;
+        }
+        let furnish = self.furnish;
+        let load = self
+            .loads
+            .entry(truck)
+            .or_insert_with(|| World::new(furnish()));
+        detach(engine, id);
+        let items = if is_robot { team(engine, id) } else { vec![id] };
+        let mut moved = Vec::new();
+        for item in items {
+            transfer(engine, &mut load.engine, item, &mut moved);
+        }
+        self.move_houses(&moved, Some(truck));
+        log::info!("Loaded {} onto truck {}", id, truck);
+        Some(match self.dispatch(engine, truck) {
+            Some(house) => Loaded::Built(house),
+            None => Loaded::Waiting,
+        })
+    }
+
+    /// Send a full truck off: replace it with a house whose world is its
+    /// load, and start the robot working on the box there.
+    fn dispatch(&mut self, engine: &mut EngineState, truck: WidgetId) -> Option<WidgetId> {
+        let (robot, cargo) = match engine.widgets.get_mut(&truck) {
+            Some(WidgetItem::Truck(t)) => t.unload()?,
+            _ => return None,
+        };
+        let mut world = self.loads.remove(&truck)?;
+        detach(engine, truck);
+        engine.widgets.remove(&truck);
+        let at = engine.positions.remove(&truck).unwrap_or_default();
+        let house = engine.add_widget(WidgetItem::House(House::new()), at);
+
+        world.scheduler.spawn(&mut world.engine, robot, Some(cargo));
+        self.houses.insert(house, world);
+        self.parents.insert(house, self.inside);
+        let riders: Vec<WidgetId> = self
+            .parents
+            .iter()
+            .filter(|(_, &parent)| parent == Some(truck))
+            .map(|(&id, _)| id)
+            .collect();
+        self.move_houses(&riders, Some(house));
+        log::info!(
+            "Truck {} built house {}: robot {} on box {}",
+            truck,
+            house,
+            robot,
+            cargo
+        );
+        Some(house)
+    }
+
+    /// Tick the robots in every world but the shown one.
+    pub fn tick(&mut self, rounds: usize) {
+        self.ticks += 1;
+        for world in self.outdoors.iter_mut().chain(self.houses.values_mut()) {
+            world.scheduler.tick(&mut world.engine, rounds);
+        }
+    }
+
+    /// Move the birds in every world but the shown one a step, waking
+    /// robots whose nest a bird just reached. Returns true if any bird
+    /// is still away.
+    pub fn fly_birds(&mut self) -> bool {
+        self.flight_steps += 1;
+        let mut flying = false;
+        for world in self.outdoors.iter_mut().chain(self.houses.values_mut()) {
+            flying |= ops::fly_birds(&mut world.engine);
+            world.scheduler.wake(&mut world.engine);
+        }
+        flying
+    }
+
+    /// Whether birds are away delivering anywhere but the shown world.
+    pub fn has_flights(&self) -> bool {
+        self.outdoors
+            .iter()
+            .chain(self.houses.values())
+            .any(|w| ops::has_flights(&w.engine))
+    }
+
+    /// Deliver messages birds left for nests in other worlds, then wake
+    /// robots waiting on those nests. Mail for a nest that is gone is lost.
+    pub fn deliver_mail(&mut self, engine: &mut EngineState, scheduler: &mut Scheduler) {
+        let mut mail = std::mem::take(&mut engine.outbox);
+        for world in self.outdoors.iter_mut().chain(self.houses.values_mut()) {
+            mail.append(&mut world.engine.outbox);
+        }
+        if mail.is_empty() {
+            return;
+        }
+        for ops::Mail {
+            nest: nest_id,
+            message,
+        } in mail
+        {
+            let worlds = self
+                .outdoors
+                .iter_mut()
+                .chain(self.houses.values_mut())
+                .chain(self.loads.values_mut())
+                .map(|w| &mut w.engine);
+            let nest = std::iter::once(&mut *engine).chain(worlds).find_map(|e| {
+                match e.widgets.get_mut(&nest_id) {
+                    Some(WidgetItem::Nest(nest)) => Some(nest),
+                    _ => None,
+                }
+            });
+            match nest {
+                Some(nest) => nest.receive(message),
+                None => log::info!("Nest {} is gone; message lost", nest_id),
+            }
+        }
+        scheduler.wake(engine);
+        for world in self.outdoors.iter_mut().chain(self.houses.values_mut()) {
+            world.scheduler.wake(&mut world.engine);
+        }
+    }
+
+    /// Deal with bombs that went off: tear down every house a bomb went
+    /// off in (going out first if it is the one shown), then mark nests
+    /// with no bird left anywhere, so robots waiting on them stop.
+    /// Returns true if a house came down.
+    pub fn detonate(&mut self, engine: &mut EngineState, scheduler: &mut Scheduler) -> bool {
+        while engine.bombed && self.leave(engine, scheduler) {}
+        engine.bombed = false;
+
+        let mut silenced = std::mem::take(&mut engine.silenced);
+        for world in self.worlds_mut() {
+            silenced.append(&mut world.engine.silenced);
+        }
+        let bombed: Vec<WidgetId> = self
+            .houses
+            .iter()
+            .filter(|(_, w)| w.engine.bombed)
+            .map(|(&id, _)| id)
+            .collect();
+        for &house in &bombed {
+            self.demolish(engine, house, &mut silenced);
+        }
+        if !silenced.is_empty() {
+            self.silence(engine, scheduler, &silenced);
+        }
+        !bombed.is_empty()
+    }
+
+    /// Tear a house down: its world and every house and truck load inside
+    /// it are dropped, and its widget is reclaimed from where it stands.
+    /// Nests of the birds that went are added to `silenced`.
+    fn demolish(
+        &mut self,
+        engine: &mut EngineState,
+        house: WidgetId,
+        silenced: &mut Vec<WidgetId>,
+    ) {
+        let mut doomed = vec![house];
+        let mut next = 0;
+        while let Some(&id) = doomed.get(next) {
+            next += 1;
+            self.parents.remove(&id);
+            let Some(world) = self.houses.remove(&id).or_else(|| self.loads.remove(&id)) else {
+                continue;
+            };
+            for (&wid, item) in &world.engine.widgets {
+                match item {
+                    WidgetItem::Bird(bird) => silenced.extend(bird.nest_id()),
+                    WidgetItem::Sensor(sensor) => silenced.extend(sensor.nest_id()),
+                    w if w.is_house() || w.is_truck() => doomed.push(wid),
+                    _ => {}
+                }
+            }
+        }
+        let engines = std::iter::once(engine).chain(self.worlds_mut().map(|w| &mut w.engine));
+        for e in engines {
+            if e.widgets.contains_key(&house) {
+                silenced.extend(ops::reclaim(e, &[house]));
+            }
+        }
+        log::info!("House {} was torn down", house);
+    }
+
+    /// Mark the `silenced` nests no bird or sensor anywhere delivers to
+    /// any more, and stop the robots waiting on them.
+    fn silence(
+        &mut self,
+        engine: &mut EngineState,
+        scheduler: &mut Scheduler,
+        silenced: &[WidgetId],
+    ) {
+        let senders: HashSet<WidgetId> = std::iter::once(&*engine)
+            .chain(self.worlds().map(|w| &w.engine))
+            .flat_map(|e| {
+                let mail = e.outbox.iter().flat_map(|m| ops::senders_in(&*m.message));
+                e.widgets.values().flat_map(ops::senders_of).chain(mail)
+            })
+            .collect();
+        let engines = std::iter::once(&mut *engine).chain(self.worlds_mut().map(|w| &mut w.engine));
+        for e in engines {
+            for id in silenced.iter().filter(|id| !senders.contains(id)) {
+                if let Some(WidgetItem::Nest(nest)) = e.widgets.get_mut(id) {
+                    log::info!("Nest {} has no birds left", id);
+                    nest.mark_no_senders();
+                }
+            }
+        }
+        scheduler.wake(engine);
+        for world in self.worlds_mut() {
+            world.scheduler.wake(&mut world.engine);
+        }
+    }
+
+    /// Show each house's status on its widget, and forget the worlds of
+    /// houses (and loads of trucks) that no longer stand anywhere.
+    pub fn refresh(&mut self, engine: &mut EngineState, scheduler: &Scheduler) {
+        loop {
+            let standing: HashSet<WidgetId> = std::iter::once(&*engine)
+                .chain(self.outdoors.iter().map(|w| &w.engine))
+                .chain(self.houses.values().map(|w| &w.engine))
+                .chain(self.loads.values().map(|w| &w.engine))
+                .flat_map(|e| e.widgets.iter())
+                .filter(|(_, w)| w.is_house() || w.is_truck())
+                .map(|(&id, _)| id)
+                .collect();
+            let gone: Vec<WidgetId> = self
+                .houses
+                .keys()
+                .chain(self.loads.keys())
+                .copied()
+                .filter(|id| !standing.contains(id))
+                .collect();
+            if gone.is_empty() {
+                break;
+            }
+            for id in gone {
+                log::info!("House or truck {} is gone", id);
+                self.houses.remove(&id);
+                self.parents.remove(&id);
+                self.loads.remove(&id);
+            }
+        }
+
+        let mut statuses: HashMap<WidgetId, HouseStatus> = self
+            .houses
+            .iter()
+            .map(|(&id, w)| (id, w.status()))
+            .collect();
+        if let Some(inside) = self.inside {
+            statuses.insert(inside, status_of(scheduler));
+        }
+        let engines = std::iter::once(engine)
+            .chain(self.outdoors.iter_mut().map(|w| &mut w.engine))
+            .chain(self.houses.values_mut().map(|w| &mut w.engine))
+            .chain(self.loads.values_mut().map(|w| &mut w.engine));
+        for e in engines {
+            for (id, w) in e.widgets.iter_mut() {
+                if let (WidgetItem::House(house), Some(&status)) = (w, statuses.get(id)) {
+                    house.set_status(status);
+                }
+            }
+        }
+    }
+
+    /// Every world but the shown one, truck loads included.
+    fn worlds(&self) -> impl Iterator<Item = &World> {
+        self.outdoors
+            .iter()
+            .chain(self.houses.values())
+            .chain(self.loads.values())
+    }
+
+    /// Every world but the shown one, truck loads included.
+    fn worlds_mut(&mut self) -> impl Iterator<Item = &mut World> {
+        self.outdoors
+            .iter_mut()
+            .chain(self.houses.values_mut())
+            .chain(self.loads.values_mut())
+    }
+
+    /// Houses among `moved` now stand in `place` (a house or a truck).
+    fn move_houses(&mut self, moved: &[WidgetId], place: Option<WidgetId>) {
+        for id in moved {
+            if let Some(parent) = self.parents.get_mut(id) {
+                *parent = place;
+            }
+        }
+    }
+
+    /// Put the shown world away and show `world` instead.
+    fn store_shown(&mut self, engine: &mut EngineState, scheduler: &mut Scheduler, world: World) {
+        let here = World {
+            engine: std::mem::replace(engine, world.engine),
+            scheduler: std::mem::replace(scheduler, world.scheduler),
+        };
+        match self.inside {
+            Some(current) => {
+                self.houses.insert(current, here);
+            }
+            None => self.outdoors = Some(here),
+        }
+    }
+}
+
+fn status_of(scheduler: &Scheduler) -> HouseStatus {
+    if scheduler.is_running() {
+        HouseStatus::Running
+    } else if scheduler.processes().iter().any(|p| p.is_live()) {
+        HouseStatus::Waiting
+    } else {
+        HouseStatus::Quiet
+    }
+}
+
+fn is_house(engine: &EngineState, id: WidgetId) -> bool {
+    matches!(engine.widgets.get(&id), Some(WidgetItem::House(h)) if !h.is_copy_source())
+}
+
+fn is_carriable(engine: &EngineState, id: WidgetId) -> bool {
+    match engine.widgets.get(&id) {
+        Some(WidgetItem::Robot(r)) => r.state() == RobotState::Idle,
+        Some(w) => !w.is_copy_source(),
+        None => engine.boxes.contains_key(&id),
+    }
+}
+
+/// Take a widget or box out of the hole it sits in, if any.
+fn detach(engine: &mut EngineState, id: WidgetId) {
+    if let Some((box_id, hole)) = engine.widget_in_box.remove(&id) {
+        if let Some(b) = engine.boxes.get_mut(&box_id) {
+            b.clear_hole(hole);
+        }
+    }
+}
+
+/// Move a widget or box, and whatever is in the box, between worlds.
+/// IDs are unique everywhere, so they stay the same.
+fn transfer(from: &mut EngineState, to: &mut EngineState, id: WidgetId, moved: &mut Vec<WidgetId>) {
+    if let Some(pos) = from.positions.remove(&id) {
+        to.positions.insert(id, pos);
+    }
+    if let Some(slot) = from.widget_in_box.remove(&id) {
+        to.widget_in_box.insert(id, slot);
+    }
+    if let Some(item) = from.widgets.remove(&id) {
+        to.widgets.insert(id, item);
+    } else if let Some(b) = from.boxes.remove(&id) {
+        let contents: Vec<WidgetId> = b.contents.values().copied().collect();
+        to.boxes.insert(id, b);
+        for child in contents {
+            transfer(from, to, child, moved);
+        }
+    }
+    moved.push(id);
+}
@@ -0,0 +1,31 @@
+//! tt-rs-engine: Headless workspace engine.
+//!
+//! Owns the workspace model (widgets, boxes, positions, box membership)
+//! and the semantics of every drop, copy, erase and robot step. Nothing
+//! here depends on Yew or the DOM: the app hit-tests to find out what was
+//! dropped where, then calls into the engine to decide what happens.
+//!
+//! # Module Organization
+//!
+//! - [`EngineState`] - workspace model
+//! - [`BoxState`] - reference-based box contents
+//! - [`WidgetItem`] - enum over every widget type
+//! - [`matching`] - structural pattern matching against workspace contents
+//! - [`ops`] - drop, split, join, copy, erase and delivery operations
+//! - [`robot_exec`] - robot training, teams and action replay
+//! - [`scheduler`] - run queue for many robots working at once
+//! - [`city`] - houses, each with its own workspace and robots
+
+mod box_state;
+pub mod city;
+pub mod matching;
+pub mod ops;
+pub mod robot_exec;
+pub mod scheduler;
+mod state;
+mod widget_item;
+
+pub use box_state::BoxState;
+pub use state::{EngineState, DEFAULT_STEP_BUDGET};
+pub use tt_rs_state::Position;
+pub use widget_item::WidgetItem;
@@ -0,0 +1,75 @@
+//! Structural matching of patterns against workspace contents.
+//!
+//! Workspace boxes hold widget IDs; patterns are owned widget trees.
+//! To match, a box is snapshotted into a `ToonBox` whose holes hold
+//! clones of the live widgets (same IDs), then the pattern's own
+//! `Widget::matches` does the structural comparison.
+//!
+//! A nest in a hole stands for its top message (see `tt_rs_box`), so a
+//! robot trained on a box learns the message it saw, not the nest.
+
+use crate::state::EngineState;
+use crate::widget_item::WidgetItem;
+use tt_rs_box::ToonBox;
+use tt_rs_core::{MatchResult, Widget, WidgetId};
+
+/// Builds an owned widget tree for a workspace widget or box.
+/// Widgets inside keep their IDs so results refer to live widgets.
+pub fn snapshot(state: &EngineState, id: WidgetId) -> Option<Box<dyn Widget>> {
+    if let Some(w) = state.widgets.get(&id) {
+        return Some(w.to_boxed_widget());
+    }
+    snapshot_box(state, id).map(|b| Box::new(b) as Box<dyn Widget>)
+}
+
+/// Builds a `ToonBox` mirroring a workspace box, with the same ID.
+pub fn snapshot_box(state: &EngineState, id: WidgetId) -> Option<ToonBox> {
+    tree_of(state, id, snapshot)
+}
+
+/// Builds the pattern a robot learns from a workspace box: a snapshot
+/// where each nest with mail, however deep, shows its top message instead.
+pub fn pattern_box(state: &EngineState, id: WidgetId) -> Option<ToonBox> {
+    tree_of(state, id, pattern_of)
+}
+
+/// What a robot learns from a widget or box in a hole.
+fn pattern_of(state: &EngineState, id: WidgetId) -> Option<Box<dyn Widget>> {
+    match state.widgets.get(&id) {
+        Some(WidgetItem::Nest(nest)) if nest.peek_top().is_some() => {
+            nest.peek_top().map(|message| message.copy())
+        }
+        Some(w) => Some(w.to_boxed_widget()),
+        None => pattern_box(state, id).map(|b| Box::new(b) as Box<dyn Widget>),
+    }
+}
+
+/// Builds a `ToonBox` with the same ID and holes as a workspace box,
+/// filling each hole with `child` of what is in it.
+fn tree_of(
+    state: &EngineState,
+    id: WidgetId,
+    child: fn(&EngineState, WidgetId) -> Option<Box<dyn Widget>>,
+) -> Option<ToonBox> {
+    let b = state.boxes.get(&id)?;
+    let mut tree = if b.erased {
+        ToonBox::erased_with_size(b.num_holes)
+    } else {
+        ToonBox::new(b.num_holes)
+    }
+    .with_id(id);
+    for (&hole, &wid) in &b.contents {
+        if let Some(child) = child(state, wid) {
+            tree.set_hole(hole, child);
+        }
+    }
+    Some(tree)
+}
+
+/// Matches a pattern against a workspace widget or box.
+pub fn match_pattern(state: &EngineState, pattern: &dyn Widget, id: WidgetId) -> MatchResult {
+    match snapshot(state, id) {
+        Some(target) => pattern.matches(target.as_ref()),
+        None => MatchResult::NoMatch,
+    }
+}
@@ -0,0 +1,60 @@
+//! Bomb operations: set one off, and reclaim what an ended process
+//! leaves behind.
+
+use tt_rs_core::WidgetId;
+use tt_rs_robot::Action;
+
+use super::messaging::senders_of;
+use crate::robot_exec::widget_path;
+use crate::state::EngineState;
+use crate::widget_item::WidgetItem;
+
+/// Set off a bomb. A robot in training only remembers doing it; otherwise
+/// the bomb is used up and the workspace is marked bombed, which ends the
+/// process (and the house) it went off in.
+pub fn set_off_bomb(state: &mut EngineState, bomb_id: WidgetId) -> bool {
+    if !matches!(state.widgets.get(&bomb_id), Some(WidgetItem::Bomb(b)) if !b.is_copy_source()) {
+        return false;
+    }
+    if state.training_robot_id.is_some() {
+        let path = widget_path(state, bomb_id);
+        state.record_action(Action::SetOffBomb { path });
+        return true;
+    }
+    reclaim(state, &[bomb_id]);
+    state.bombed = true;
+    log::info!("Bomb {} went off", bomb_id);
+    true
+}
+
+/// Remove widgets and boxes, with everything in them, for good: their
+/// positions, box memberships and any messages waiting in their nests go
+/// too. Returns the nests of the birds and sensors that went, riding in
+/// mail or not, which may have no one left to deliver to them.
+pub fn reclaim(state: &mut EngineState, ids: &[WidgetId]) -> Vec<WidgetId> {
+    let mut silenced = Vec::new();
+    for &id in ids {
+        if let Some((box_id, hole)) = state.widget_in_box.get(&id).copied() {
+            if let Some(b) = state.boxes.get_mut(&box_id) {
+                b.clear_hole(hole);
+            }
+        }
+        remove_all(state, id, &mut silenced);
+    }
+    silenced
+}
+
+fn remove_all(state: &mut EngineState, id: WidgetId, silenced: &mut Vec<WidgetId>) {
+    state.positions.remove(&id);
+    state.widget_in_box.remove(&id);
+    match state.widgets.remove(&id) {
+        Some(item) => silenced.extend(senders_of(&item)),
+        None => {
+            if let Some(b) = state.boxes.remove(&id) {
+                for child in b.contents.into_values() {
+                    remove_all(state, child, silenced);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,328 @@
+//! Box operations: create, split, join, copy.
+
+use std::collections::{HashMap, HashSet};
+
+use tt_rs_bird::Bird;
+use tt_rs_core::{Widget, WidgetId};
+use tt_rs_nest::Nest;
+use tt_rs_robot::Action;
+use tt_rs_state::Position;
+
+use crate::box_state::BoxState;
+use crate::robot_exec::widget_path;
+use crate::state::EngineState;
+use crate::widget_item::WidgetItem;
+
+/// Deep copy a box including all its contents.
+/// Returns the new box and updates state with copied widgets.
+///
+/// Birds and nests are copied as pairs: a bird (or sensor) copied along
+/// with its nest delivers to the copy of the nest, while one whose nest
+/// stays behind keeps sending to it. A nest copied without any of its
+/// senders hatches a fresh bird beside it, so the copy never shares mail
+/// with the original.
+pub fn deep_copy_box(state: &mut EngineState, src: &BoxState) -> BoxState {
+    let mut new_box = BoxState::new(src.num_holes);
+    let mut nests = HashMap::new();
+    let mut senders = Vec::new();
+
+    for (hole, &widget_id) in &src.contents {
+        if let Some(widget) = state.widgets.get(&widget_id) {
+            let copied = widget.copy_widget();
+            let copied_id = copied.id();
+            match &copied {
+                WidgetItem::Nest(nest) => {
+                    nests.insert(widget_id, nest.clone());
+                }
+                WidgetItem::Bird(_) | WidgetItem::Sensor(_) => senders.push(copied_id),
+                _ => {}
+            }
+            state.widgets.insert(copied_id, copied);
+            // Position copied widgets relative to original
+            if let Some(pos) = state.positions.get(&widget_id) {
+                state.positions.insert(copied_id, pos.offset(30.0, 30.0));
+            }
+            new_box.place_in_hole(*hole, copied_id);
+            state.widget_in_box.insert(copied_id, (new_box.id(), *hole));
+        }
+    }
+
+    let mut bonded = HashSet::new();
+    for sender_id in senders {
+        match state.widgets.get_mut(&sender_id) {
+            Some(WidgetItem::Bird(bird)) => {
+                if let Some(copy) = bird.nest_id().and_then(|n| nests.get(&n)) {
+                    bird.bond(copy);
+                    bonded.insert(copy.id());
+                }
+            }
+            Some(WidgetItem::Sensor(sensor)) => {
+                if let Some(copy) = sensor.nest_id().and_then(|n| nests.get(&n)) {
+                    sensor.bond(copy);
+                    bonded.insert(copy.id());
+                }
+            }
+            _ => {}
+        }
+    }
+    for nest in nests.values() {
+        if !bonded.contains(&nest.id()) {
+            hatch_bird(state, nest);
+        }
+    }
+
+    new_box
+}
+
+/// Give a copied nest with no birds a fresh one, free beside it.
+fn hatch_bird(state: &mut EngineState, nest: &Nest) {
+    let at = state.positions.get(&nest.id()).copied().unwrap_or_default();
+    state.add_widget(WidgetItem::Bird(Bird::hatch(nest)), at.offset(60.0, 0.0));
+}
+
+/// Create a new empty box with the given number of holes.
+pub fn create_box(state: &mut EngineState, num_holes: usize, at: Position) -> WidgetId {
+    state.record_action(Action::NewBox { holes: num_holes });
+    let id = state.add_box(BoxState::new(num_holes), at);
+    state.note_created(id);
+    log::info!("Created box {} with {} holes", id, num_holes);
+    id
+}
+
+/// Copy a box and its contents, placing the copy offset by `(dx, dy)`.
+pub fn copy_box(state: &mut EngineState, box_id: WidgetId, dx: f64, dy: f64) -> Option<WidgetId> {
+    let src = state.boxes.get(&box_id).cloned()?;
+    state.record_action(Action::Copy {
+        path: widget_path(state, box_id),
+    });
+    let copied = deep_copy_box(state, &src);
+    let pos = state.positions.get(&box_id).copied().unwrap_or_default();
+    let id = state.add_box(copied, Position::new(pos.x + dx, pos.y + dy));
+    state.note_created(id);
+    Some(id)
+}
+
+/// Drop a box on a number: split it at that index (negative counts from
+/// the right), or copy it with its contents when the number is 0.
+/// `fallback` is used when the box has no recorded position.
+pub fn split_box(
+    state: &mut EngineState,
+    box_id: WidgetId,
+    num_id: WidgetId,
+    fallback: Position,
+) -> bool {
+    // Get effective numerator (accounts for operator) to handle negative indices
+    // A "- 1" widget has operator=Subtract, numerator=1, so effective_numerator() returns -1
+    let raw_split = match state.widgets.get(&num_id) {
+        Some(WidgetItem::Number(n)) if !n.is_copy_source() => n.effective_numerator(),
+        _ => return false,
+    };
+
+    let path = widget_path(state, box_id);
+    if !split_box_at(state, box_id, raw_split, fallback) {
+        return false;
+    }
+    state.record_action(Action::SplitBox {
+        path,
+        at: raw_split,
+    });
+    state.remove_widget(num_id);
+    true
+}
+
+/// Split a box as if dropped on a number with value `raw_split`. The box
+/// keeps the left half, and the right half (or the copy, for 0) becomes a
+/// new box.
+pub fn split_box_at(
+    state: &mut EngineState,
+    box_id: WidgetId,
+    raw_split: i64,
+    fallback: Position,
+) -> bool {
+    let box_state = match state.boxes.get(&box_id) {
+        Some(b) => b.clone(),
+        None => return false,
+    };
+
+    // Handle special case: drop on 0 creates a deep copy of the box
+    if raw_split == 0 {
+        copy_box_with_contents(state, &box_state, fallback);
+        return true;
+    }
+
+    let split_at = match split_index(box_state.num_holes, raw_split) {
+        Some(at) => at,
+        None => return false,
+    };
+
+    let right = split_off(state, box_id, split_at);
+    let pos = state.positions.get(&box_id).copied().unwrap_or(fallback);
+    let right_id = state.add_box(right, Position::new(pos.x + 100.0, pos.y));
+    state.note_created(right_id);
+    true
+}
+
+/// Join two boxes: the source's holes are appended to the target's, and
+/// the source is gone. The target keeps its ID, so a robot joining a box
+/// into the one it was given still works on it.
+pub fn join_boxes(state: &mut EngineState, src_id: WidgetId, tgt_id: WidgetId) -> bool {
+    if src_id == tgt_id || !state.boxes.contains_key(&tgt_id) {
+        return false;
+    }
+    let src = match state.boxes.get(&src_id) {
+        Some(b) => b.clone(),
+        None => return false,
+    };
+
+    state.record_pick_up(src_id);
+    state.record_drop(widget_path(state, tgt_id));
+
+    if let Some((parent, hole)) = state.widget_in_box.remove(&src_id) {
+        if let Some(b) = state.boxes.get_mut(&parent) {
+            b.clear_hole(hole);
+        }
+    }
+    state.boxes.remove(&src_id);
+    state.positions.remove(&src_id);
+    append_contents(state, &src, tgt_id);
+    true
+}
+
+/// Convert a split number to a hole index.
+/// Negative splits from the right: -2 on 8-hole box → split at 6.
+/// Positive: split_at holes on left, rest on right.
+fn split_index(num_holes: usize, raw_split: i64) -> Option<usize> {
+    let at = if raw_split < 0 {
+        let from_right = (-raw_split) as usize;
+        if from_right >= num_holes {
+            return None;
+        }
+        num_holes - from_right
+    } else {
+        let pos = raw_split as usize;
+        if pos >= num_holes {
+            return None;
+        }
+        pos
+    };
+    Some(at)
+}
+
+/// Copy a box with all its contents when dropped on 0.
+/// The original box remains, a copy is created offset from it.
+fn copy_box_with_contents(state: &mut EngineState, src: &BoxState, fallback: Position) {
+    let copied = deep_copy_box(state, src);
+    let pos = state.positions.get(&src.id()).copied().unwrap_or(fallback);
+    let id = state.add_box(copied, Position::new(pos.x + 50.0, pos.y + 50.0));
+    state.note_created(id);
+}
+
+/// Move the holes of a box from `at` on into a new box, leaving the box
+/// with the holes before `at`.
+fn split_off(state: &mut EngineState, box_id: WidgetId, at: usize) -> BoxState {
+    let num_holes = state.boxes.get(&box_id).map_or(at, |b| b.num_holes);
+    let mut right = BoxState::new(num_holes - at);
+    if let Some(left) = state.boxes.get_mut(&box_id) {
+        for i in at..num_holes {
+            if let Some(wid) = left.clear_hole(i) {
+                right.place_in_hole(i - at, wid);
+                state.widget_in_box.insert(wid, (right.id(), i - at));
+            }
+        }
+        left.num_holes = at;
+    }
+    right
+}
+
+/// Append the holes of `src` to the target box's.
+fn append_contents(state: &mut EngineState, src: &BoxState, tgt_id: WidgetId) {
+    let Some(tgt) = state.boxes.get_mut(&tgt_id) else {
+        return;
+    };
+    let offset = tgt.num_holes;
+    tgt.num_holes += src.num_holes;
+    for i in 0..src.num_holes {
+        if let Some(wid) = src.contents.get(&i).copied() {
+            tgt.place_in_hole(offset + i, wid);
+            state.widget_in_box.insert(wid, (tgt_id, offset + i));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that negative index splitting works correctly.
+    /// A 6-hole box [1, 2, _, _, _, 6] dropped on -1 should produce:
+    /// - Left: 5-hole box [1, 2, _, _, _]
+    /// - Right: 1-hole box [6]
+    #[test]
+    fn test_negative_split_takes_from_right() {
+        // Create a mock scenario:
+        // - 6-hole box with contents at holes 0, 1, and 5
+        // - Split at -1 means take 1 hole from the right
+        // - Result: left has 5 holes (with contents at 0, 1), right has 1 hole (with content at 0, originally hole 5)
+
+        // The split_at calculation for -1 on a 6-hole box:
+        // raw_split = -1
+        // from_right = 1
+        // split_at = 6 - 1 = 5
+        // So left gets holes 0..5 (5 holes), right gets holes 5..6 (1 hole)
+
+        let num_holes = 6;
+        let raw_split: i64 = -1;
+
+        let split_at = if raw_split < 0 {
+            let from_right = (-raw_split) as usize;
+            assert!(from_right < num_holes && from_right > 0);
+            num_holes - from_right
+        } else {
+            raw_split as usize
+        };
+
+        // split_at should be 5, meaning left gets 5 holes, right gets 1 hole
+        assert_eq!(
+            split_at, 5,
+            "Split at -1 on 6-hole box should split at position 5"
+        );
+        assert_eq!(split_index(num_holes, raw_split), Some(split_at));
+
+        // After split:
+        // - Left box: 5 holes (indices 0-4 from original)
+        // - Right box: 1 hole (index 5 from original, now index 0)
+        let left_holes = split_at;
+        let right_holes = num_holes - split_at;
+        assert_eq!(left_holes, 5);
+        assert_eq!(right_holes, 1);
+    }
+
+    /// Test that -2 on an 8-hole box splits correctly.
+    /// An 8-hole box dropped on -2 should produce:
+    /// - Left: 6-hole box
+    /// - Right: 2-hole box
+    #[test]
+    fn test_negative_2_split() {
+        let num_holes = 8;
+        let raw_split: i64 = -2;
+
+        let split_at = if raw_split < 0 {
+            let from_right = (-raw_split) as usize;
+            assert!(from_right < num_holes && from_right > 0);
+            num_holes - from_right
+        } else {
+            raw_split as usize
+        };
+
+        assert_eq!(
+            split_at, 6,
+            "Split at -2 on 8-hole box should split at position 6"
+        );
+        assert_eq!(split_index(num_holes, raw_split), Some(split_at));
+
+        let left_holes = split_at;
+        let right_holes = num_holes - split_at;
+        assert_eq!(left_holes, 6);
+        assert_eq!(right_holes, 2);
+    }
+}
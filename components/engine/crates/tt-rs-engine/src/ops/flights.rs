@@ -0,0 +1,162 @@
+//! Bird flights: a bird given a message flies to its nest, drops the
+//! message in and flies back home.
+//!
+//! Flights take time: every [`fly_birds`] call moves each bird that is
+//! away one step along its way, and a leg takes [`FLIGHT_STEPS`] steps.
+//! A bird sitting in a box hole doesn't move on screen, but its trip
+//! takes just as long. A nest in another house is out of sight, so the
+//! bird flies off upwards and its message goes into the outbox.
+
+use tt_rs_bird::BirdState;
+use tt_rs_core::{Widget, WidgetId};
+use tt_rs_state::Position;
+
+use crate::state::EngineState;
+use crate::widget_item::WidgetItem;
+
+/// Steps each leg of a flight (out to the nest, or back) takes.
+pub const FLIGHT_STEPS: usize = 10;
+
+/// How far up a bird flies to reach a nest in another house.
+const OUT_OF_SIGHT: f64 = 300.0;
+
+/// A message left in the outbox for a nest in another house.
+#[derive(Debug)]
+pub struct Mail {
+    pub nest: WidgetId,
+    pub message: Box<dyn Widget>,
+}
+
+impl Clone for Mail {
+    fn clone(&self) -> Self {
+        Self {
+            nest: self.nest,
+            message: self.message.copy(),
+        }
+    }
+}
+
+/// A bird's trip to its nest and back.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Flight {
+    /// Where the bird set out from (None: it sits in a box hole).
+    pub home: Option<Position>,
+    /// Steps taken on the current leg.
+    pub step: usize,
+}
+
+/// Move every bird that is away one step. A bird reaching its nest
+/// delivers its message; one reaching home sets off again if it was
+/// given more messages meanwhile. Returns true if any bird is still away.
+pub fn fly_birds(state: &mut EngineState) -> bool {
+    let away: Vec<WidgetId> = state
+        .widgets
+        .iter()
+        .filter(|(_, w)| matches!(w, WidgetItem::Bird(b) if b.is_away()))
+        .map(|(&id, _)| id)
+        .collect();
+    state.flights.retain(|id, _| away.contains(id));
+
+    for bird_id in away {
+        let home = if state.widget_in_box.contains_key(&bird_id) {
+            None
+        } else {
+            state.positions.get(&bird_id).copied()
+        };
+        let flight = state
+            .flights
+            .entry(bird_id)
+            .or_insert(Flight { home, step: 0 });
+        flight.step += 1;
+        let flight = *flight;
+        if flight.step < FLIGHT_STEPS {
+            place_bird(state, bird_id, flight);
+        } else {
+            finish_leg(state, bird_id, flight);
+        }
+    }
+    !state.flights.is_empty()
+}
+
+/// Whether any bird is away delivering.
+pub fn has_flights(state: &EngineState) -> bool {
+    state
+        .widgets
+        .values()
+        .any(|w| matches!(w, WidgetItem::Bird(b) if b.is_away()))
+}
+
+/// Stand a free bird where it has got to on this leg.
+fn place_bird(state: &mut EngineState, bird_id: WidgetId, flight: Flight) {
+    let (Some(home), Some(bird_state)) = (flight.home, bird_state(state, bird_id)) else {
+        return;
+    };
+    let nest = nest_spot(state, bird_id, home);
+    let t = flight.step as f64 / FLIGHT_STEPS as f64;
+    let (from, to) = match bird_state {
+        BirdState::Returning => (nest, home),
+        _ => (home, nest),
+    };
+    let at = Position::new(from.x + (to.x - from.x) * t, from.y + (to.y - from.y) * t);
+    state.positions.insert(bird_id, at);
+}
+
+/// At the nest: drop the message and turn back. At home: land, and set
+/// off again with the next waiting message, if any.
+fn finish_leg(state: &mut EngineState, bird_id: WidgetId, flight: Flight) {
+    let Some(WidgetItem::Bird(bird)) = state.widgets.get_mut(&bird_id) else {
+        return;
+    };
+    if bird.state() == BirdState::Returning {
+        let again = bird.arrive_home();
+        if let Some(home) = flight.home {
+            state.positions.insert(bird_id, home);
+        }
+        if !again {
+            state.flights.remove(&bird_id);
+            return;
+        }
+    } else {
+        let nest_id = bird.nest_id();
+        if let (Some(message), Some(nest_id)) = (bird.start_returning(), nest_id) {
+            deliver(state, nest_id, message);
+            log::info!("Bird {} delivered a message to nest {}", bird_id, nest_id);
+        }
+    }
+    if let Some(flight) = state.flights.get_mut(&bird_id) {
+        flight.step = 0;
+    }
+}
+
+/// Put a message in the nest, or in the outbox if the nest is elsewhere.
+fn deliver(state: &mut EngineState, nest_id: WidgetId, message: Box<dyn Widget>) {
+    match state.widgets.get_mut(&nest_id) {
+        Some(WidgetItem::Nest(nest)) => nest.receive(message),
+        _ => state.outbox.push(Mail {
+            nest: nest_id,
+            message,
+        }),
+    }
+}
+
+fn bird_state(state: &EngineState, bird_id: WidgetId) -> Option<BirdState> {
+    match state.widgets.get(&bird_id) {
+        Some(WidgetItem::Bird(b)) => Some(b.state()),
+        _ => None,
+    }
+}
+
+/// Where the bird's nest is: its own spot, its box's spot if it sits in
+/// a hole, or out of sight above home if it is in another house.
+fn nest_spot(state: &EngineState, bird_id: WidgetId, home: Position) -> Position {
+    let nest_id = match state.widgets.get(&bird_id) {
+        Some(WidgetItem::Bird(b)) => b.nest_id(),
+        _ => None,
+    };
+    let spot = nest_id.and_then(|id| match state.widget_in_box.get(&id) {
+        Some((box_id, _)) => state.positions.get(box_id),
+        None => state.positions.get(&id),
+    });
+    spot.copied()
+        .unwrap_or(Position::new(home.x, home.y - OUT_OF_SIGHT))
+}
@@ -0,0 +1,129 @@
+//! Box hole operations: place and erase hole contents.
+
+use tt_rs_core::WidgetId;
+use tt_rs_robot::Action;
+use tt_rs_state::Position;
+
+use super::bombs::reclaim;
+use super::messaging::take_from_nest;
+use crate::robot_exec::box_hole_path;
+use crate::state::EngineState;
+use crate::widget_item::WidgetItem;
+
+/// Drop a widget into a box hole, ejecting any previous occupant to `eject_to`.
+/// Tools are copied into the hole rather than moved.
+pub fn drop_in_hole(
+    state: &mut EngineState,
+    id: WidgetId,
+    box_id: WidgetId,
+    hole: usize,
+    eject_to: Position,
+) -> bool {
+    if !state.widgets.contains_key(&id) || !state.boxes.contains_key(&box_id) {
+        return false;
+    }
+
+    eject_existing(state, box_id, hole, eject_to);
+    state.record_pick_up(id);
+    state.record_drop(box_hole_path(state, box_id, hole));
+
+    let place_id = get_widget_to_place(state, id);
+    if let Some(b) = state.boxes.get_mut(&box_id) {
+        b.place_in_hole(hole, place_id);
+    }
+    state.widget_in_box.insert(place_id, (box_id, hole));
+    state.update_scales_in_box(box_id);
+    true
+}
+
+/// Pick from a box hole as ToonTalk does: a nest with mail in the hole
+/// gives up its top message and stays put; anything else is taken out.
+/// Returns the ID of the widget now on the workspace.
+pub fn pick_from_hole(
+    state: &mut EngineState,
+    box_id: WidgetId,
+    hole: usize,
+    at: Position,
+) -> Option<WidgetId> {
+    let wid = state.boxes.get(&box_id)?.widget_in_hole(hole)?;
+    match state.widgets.get(&wid) {
+        Some(WidgetItem::Nest(nest)) if nest.has_messages() => take_from_nest(state, wid, at),
+        _ => take_from_hole(state, box_id, hole, at),
+    }
+}
+
+/// Take the contents of a box hole out onto the workspace at `at`.
+/// Returns the ID of the widget taken out.
+pub fn take_from_hole(
+    state: &mut EngineState,
+    box_id: WidgetId,
+    hole: usize,
+    at: Position,
+) -> Option<WidgetId> {
+    let path = box_hole_path(state, box_id, hole);
+    let wid = state.boxes.get_mut(&box_id)?.clear_hole(hole)?;
+    state.widget_in_box.remove(&wid);
+    state.positions.insert(wid, at);
+    if state.training_robot_id.is_some() {
+        state.record_action(Action::PickUp { path });
+        state.training_held = Some(wid);
+    }
+    state.note_created(wid);
+    state.update_scales_in_box(box_id);
+    Some(wid)
+}
+
+/// Erase the contents of a box hole for good, with everything inside it.
+pub fn vacuum_hole(state: &mut EngineState, box_id: WidgetId, hole: usize) -> bool {
+    let Some(wid) = state
+        .boxes
+        .get(&box_id)
+        .and_then(|b| b.widget_in_hole(hole))
+    else {
+        return false;
+    };
+    state.record_action(Action::Remove {
+        path: box_hole_path(state, box_id, hole),
+    });
+    let silenced = reclaim(state, &[wid]);
+    state.silenced.extend(silenced);
+    state.update_scales_in_box(box_id);
+    true
+}
+
+fn eject_existing(state: &mut EngineState, box_id: WidgetId, hole: usize, to: Position) {
+    let existing = state
+        .boxes
+        .get(&box_id)
+        .and_then(|b| b.widget_in_hole(hole));
+    if let Some(old_id) = existing {
+        if let Some(b) = state.boxes.get_mut(&box_id) {
+            b.clear_hole(hole);
+        }
+        state.widget_in_box.remove(&old_id);
+        state.positions.insert(old_id, to);
+    }
+}
+
+fn get_widget_to_place(state: &mut EngineState, id: WidgetId) -> WidgetId {
+    let is_tool = matches!(
+        state.widgets.get(&id),
+        Some(
+            WidgetItem::Scales(_)
+                | WidgetItem::Vacuum(_)
+                | WidgetItem::Wand(_)
+                | WidgetItem::Robot(_)
+        )
+    );
+
+    if is_tool {
+        if let Some(w) = state.widgets.get(&id) {
+            let copied = w.copy_widget();
+            let copy_id = copied.id();
+            state.widgets.insert(copy_id, copied);
+            return copy_id;
+        }
+    }
+    state.positions.remove(&id);
+    id
+}
@@ -0,0 +1,257 @@
+//! Bird and Nest operations: message passing.
+//!
+//! ToonTalk Bird/Nest semantics:
+//! - Birds are created paired with a nest (via "hatching")
+//! - A copied bird is another sender to the same nest; a copied nest
+//!   hatches a bird of its own (see `deep_copy_box` for boxes)
+//! - Drop a widget or box ON a bird to give it a message, free or in a
+//!   box hole; a box goes with everything in it
+//! - The bird flies the message to its paired nest (see `flights`)
+//! - Messages queue on the nest until retrieved (click nest to take top);
+//!   a box message (e.g. from a pointer sensor) comes out as a box
+//! - A bird can itself be a message, so a box holding a reply bird makes
+//!   a request; a nest can't, as its copy would share its channel
+
+use tt_rs_bird::Bird;
+use tt_rs_box::ToonBox;
+use tt_rs_core::{Widget, WidgetId};
+use tt_rs_nest::Nest;
+use tt_rs_robot::Action;
+use tt_rs_state::Position;
+
+use crate::box_state::BoxState;
+use crate::matching::snapshot;
+use crate::robot_exec::widget_path;
+use crate::state::EngineState;
+use crate::widget_item::WidgetItem;
+
+/// Give a widget or box to a bird, which flies it to its paired nest. A
+/// bird that is already away takes it along on its next trip.
+pub fn give_to_bird(state: &mut EngineState, id: WidgetId, bird_id: WidgetId) -> bool {
+    let nest_id = match state.widgets.get(&bird_id) {
+        Some(WidgetItem::Bird(bird)) => bird.nest_id(),
+        _ => return false,
+    };
+
+    // Can't deliver nests, houses, or tools, even in a box
+    let Some(message) = message_of(state, id, bird_id) else {
+        return false;
+    };
+
+    // Bird must be paired with a nest
+    let nest_id = match nest_id {
+        Some(id) => id,
+        None => {
+            log::info!("Bird {} has no nest to deliver to", bird_id);
+            return false;
+        }
+    };
+
+    state.record_pick_up(id);
+    state.record_drop(widget_path(state, bird_id));
+
+    // Remove the dropped widget or box from workspace (bird consumes it)
+    remove_delivered(state, id);
+
+    // The bird carries the message; it reaches the nest when the bird does
+    if let Some(WidgetItem::Bird(b)) = state.widgets.get_mut(&bird_id) {
+        b.give(message);
+    }
+
+    log::info!("Bird {} is taking a message to nest {}", bird_id, nest_id);
+    true
+}
+
+/// Drop a widget or box directly on a nest, adding it to the message
+/// queue as if a bird had delivered it.
+pub fn give_to_nest(state: &mut EngineState, id: WidgetId, nest_id: WidgetId) -> bool {
+    if !matches!(state.widgets.get(&nest_id), Some(WidgetItem::Nest(_))) {
+        return false;
+    }
+    let Some(message) = message_of(state, id, nest_id) else {
+        return false;
+    };
+
+    state.record_pick_up(id);
+    state.record_drop(widget_path(state, nest_id));
+    remove_delivered(state, id);
+    if let Some(WidgetItem::Nest(nest)) = state.widgets.get_mut(&nest_id) {
+        nest.receive(message);
+    }
+    true
+}
+
+/// Drop a widget on the bird or nest sitting in a box hole: a bird takes
+/// it to whichever nest it belongs to, a nest keeps it. Returns false,
+/// leaving the widget alone, if the hole holds neither.
+pub fn give_in_hole(state: &mut EngineState, id: WidgetId, box_id: WidgetId, hole: usize) -> bool {
+    let Some(target) = state
+        .boxes
+        .get(&box_id)
+        .and_then(|b| b.widget_in_hole(hole))
+    else {
+        return false;
+    };
+    match state.widgets.get(&target) {
+        Some(WidgetItem::Bird(_)) => give_to_bird(state, id, target),
+        Some(WidgetItem::Nest(_)) => give_to_nest(state, id, target),
+        _ => false,
+    }
+}
+
+/// Take the top message from a nest and place it on the workspace.
+/// Returns the ID of the extracted widget (or box).
+pub fn take_from_nest(
+    state: &mut EngineState,
+    nest_id: WidgetId,
+    at: Position,
+) -> Option<WidgetId> {
+    let path = widget_path(state, nest_id);
+    let message = match state.widgets.get_mut(&nest_id) {
+        Some(WidgetItem::Nest(nest)) => nest.take()?,
+        _ => return None,
+    };
+    state.record_action(Action::TakeFromNest { path });
+
+    let new_id = unpack(state, message, at);
+    state.note_created(new_id);
+    log::info!("Took message {} from nest {}", new_id, nest_id);
+    Some(new_id)
+}
+
+/// Place a message on the workspace at `at`: a box message becomes a
+/// box holding its contents, anything else a widget. Returns its ID.
+fn unpack(state: &mut EngineState, message: Box<dyn Widget>, at: Position) -> WidgetId {
+    let Some(tree) = message.as_any().downcast_ref::<ToonBox>() else {
+        return state.add_widget(WidgetItem::from_boxed_widget(message), at);
+    };
+    let mut b = BoxState::new(tree.len());
+    let box_id = b.id();
+    for hole in tree.holes() {
+        if let Some(content) = hole.content() {
+            let id = unpack(state, content.copy(), at);
+            state.widget_in_box.insert(id, (box_id, hole.index()));
+            b.place_in_hole(hole.index(), id);
+        }
+    }
+    state.add_box(b, at)
+}
+
+/// Place a new nest at `at` with a bird bonded to it beside it ("hatching").
+/// Returns the ID of the nest.
+pub fn hatch_nest(state: &mut EngineState, nest: Nest, at: Position) -> WidgetId {
+    let bird = Bird::hatch(&nest);
+    let nest_id = state.add_widget(WidgetItem::Nest(nest), at);
+    let bird_id = state.add_widget(WidgetItem::Bird(bird), at.offset(60.0, 0.0));
+    log::info!("Hatched: Nest {} with Bird {}", nest_id, bird_id);
+    nest_id
+}
+
+/// The message a widget or box given to `target` makes: a box goes with
+/// everything in it. None (and a note in the log) for palette items,
+/// for what can't be delivered, alone or in a box, and for a box that
+/// holds the target itself.
+fn message_of(state: &EngineState, id: WidgetId, target: WidgetId) -> Option<Box<dyn Widget>> {
+    if id == target || holds(state, id, target) {
+        return None;
+    }
+    if !is_deliverable(state, id) {
+        log::info!("{} can't be delivered by a bird", id);
+        return None;
+    }
+    snapshot(state, id)
+}
+
+fn is_deliverable(state: &EngineState, id: WidgetId) -> bool {
+    match state.widgets.get(&id) {
+        Some(w) => !w.is_copy_source() && !is_undeliverable(w),
+        None => state.boxes.get(&id).is_some_and(|b| {
+            b.contents
+                .values()
+                .all(|&inner| is_deliverable(state, inner))
+        }),
+    }
+}
+
+/// Whether `target` sits in a hole of box `id`, however deep.
+fn holds(state: &EngineState, id: WidgetId, target: WidgetId) -> bool {
+    let mut current = target;
+    while let Some(&(box_id, _)) = state.widget_in_box.get(&current) {
+        if box_id == id {
+            return true;
+        }
+        current = box_id;
+    }
+    false
+}
+
+/// Take a delivered widget or box, with everything in it, off the
+/// workspace.
+fn remove_delivered(state: &mut EngineState, id: WidgetId) {
+    if let Some((box_id, hole)) = state.widget_in_box.remove(&id) {
+        if let Some(b) = state.boxes.get_mut(&box_id) {
+            b.clear_hole(hole);
+        }
+    }
+    if state.remove_widget(id).is_some() {
+        return;
+    }
+    state.positions.remove(&id);
+    if let Some(b) = state.boxes.remove(&id) {
+        for inner in b.contents.into_values() {
+            remove_delivered(state, inner);
+        }
+    }
+}
+
+/// A bird goes unless it is away, with mail of its own to deliver.
+fn is_undeliverable(item: &WidgetItem) -> bool {
+    match item {
+        WidgetItem::Bird(bird) => bird.is_away(),
+        WidgetItem::Nest(_)
+        | WidgetItem::House(_)
+        | WidgetItem::Truck(_)
+        | WidgetItem::Bomb(_)
+        | WidgetItem::Sensor(_)
+        | WidgetItem::Vacuum(_)
+        | WidgetItem::Wand(_) => true,
+        _ => false,
+    }
+}
+
+/// The nests a widget delivers to: its own if it is a bird or sensor,
+/// and those of the birds riding in the mail it holds or carries.
+pub fn senders_of(item: &WidgetItem) -> Vec<WidgetId> {
+    let mut nests = Vec::new();
+    match item {
+        WidgetItem::Bird(bird) => {
+            nests.extend(bird.nest_id());
+            for message in bird.carrying().into_iter().chain(bird.queued()) {
+                nests.extend(senders_in(message));
+            }
+        }
+        WidgetItem::Sensor(sensor) => nests.extend(sensor.nest_id()),
+        WidgetItem::Nest(nest) => {
+            for message in nest.messages() {
+                nests.extend(senders_in(message));
+            }
+        }
+        _ => {}
+    }
+    nests
+}
+
+/// The nests of the birds riding in a message, however deep in boxes.
+pub fn senders_in(message: &dyn Widget) -> Vec<WidgetId> {
+    if let Some(bird) = message.as_any().downcast_ref::<Bird>() {
+        return bird.nest_id().into_iter().collect();
+    }
+    match message.as_any().downcast_ref::<ToonBox>() {
+        Some(tree) => tree
+            .holes()
+            .filter_map(|hole| hole.content())
+            .flat_map(senders_in)
+            .collect(),
+        None => Vec::new(),
+    }
+}
@@ -0,0 +1,26 @@
+//! Workspace operations organized by concern.
+//!
+//! Each operation takes the already-resolved target (the app finds it by
+//! hit-testing the DOM) and returns whether anything happened.
+
+mod bombs;
+mod boxes;
+mod flights;
+mod holes;
+mod messaging;
+mod robots;
+mod sensors;
+mod tools;
+mod values;
+
+pub use bombs::{reclaim, set_off_bomb};
+pub use boxes::{copy_box, create_box, deep_copy_box, join_boxes, split_box, split_box_at};
+pub use flights::{fly_birds, has_flights, Flight, Mail, FLIGHT_STEPS};
+pub use holes::{drop_in_hole, pick_from_hole, take_from_hole, vacuum_hole};
+pub use messaging::{
+    give_in_hole, give_to_bird, give_to_nest, hatch_nest, senders_in, senders_of, take_from_nest,
+};
+pub use robots::{copy_team, erase_pattern, join_team};
+pub use sensors::{hatch_sensor, sense_click, sense_key, toggle_sensor};
+pub use tools::{vacuum_widget, wand_copy_widget};
+pub use values::{apply_number, place_on_scales, Pan};
@@ -0,0 +1,77 @@
+//! Robot operations: join robots into a team, copy whole teams, and
+//! generalize a robot by erasing parts of its pattern.
+
+use tt_rs_core::WidgetId;
+use tt_rs_robot::RobotState;
+use tt_rs_state::Position;
+
+use crate::robot_exec::team;
+use crate::state::EngineState;
+use crate::widget_item::WidgetItem;
+
+/// Drop a robot (and its own team) on another robot, appending it to
+/// the end of that robot's team. The added robots stack behind the lead.
+pub fn join_team(state: &mut EngineState, robot_id: WidgetId, lead_id: WidgetId) -> bool {
+    if !is_idle_robot(state, robot_id) || !is_idle_robot(state, lead_id) {
+        return false;
+    }
+    let members = team(state, lead_id);
+    let joining = team(state, robot_id);
+    if joining.iter().any(|id| members.contains(id)) {
+        return false;
+    }
+
+    let last = members.last().copied().unwrap_or(lead_id);
+    if let Some(WidgetItem::Robot(r)) = state.widgets.get_mut(&last) {
+        r.set_next_robot(Some(robot_id));
+    }
+    let pos = state.positions.get(&lead_id).copied().unwrap_or_default();
+    for id in joining {
+        state.positions.insert(id, pos);
+    }
+    log::info!("Robot {} joined robot {}'s team", robot_id, lead_id);
+    true
+}
+
+/// Copy a robot together with the rest of its team, placing the copy
+/// offset by `(dx, dy)`. Returns the ID of the copied lead.
+pub fn copy_team(state: &mut EngineState, lead_id: WidgetId, dx: f64, dy: f64) -> Option<WidgetId> {
+    let pos = state.positions.get(&lead_id).copied()?;
+    let at = Position::new(pos.x + dx, pos.y + dy);
+
+    // Copy from the back so each copy can point at the next one's copy
+    let mut next = None;
+    for id in team(state, lead_id).into_iter().rev() {
+        let mut copy = match state.widgets.get(&id) {
+            Some(WidgetItem::Robot(r)) => r.copy_robot(),
+            _ => return None,
+        };
+        copy.set_next_robot(next);
+        next = Some(state.add_widget(WidgetItem::Robot(copy), at));
+    }
+    next
+}
+
+/// Vacuum part of an idle robot's thought bubble: erase the pattern part
+/// at `path` (hole indices; empty for the whole box) one level further.
+pub fn erase_pattern(state: &mut EngineState, robot_id: WidgetId, path: &[usize]) -> bool {
+    if !is_idle_robot(state, robot_id) {
+        return false;
+    }
+    let Some(WidgetItem::Robot(r)) = state.widgets.get_mut(&robot_id) else {
+        return false;
+    };
+    let Some(mut pattern) = r.pattern().cloned() else {
+        return false;
+    };
+    if !pattern.erase_at(path) {
+        return false;
+    }
+    log::info!("Erased robot {} pattern at {:?}", robot_id, path);
+    r.set_pattern(pattern);
+    true
+}
+
+fn is_idle_robot(state: &EngineState, id: WidgetId) -> bool {
+    matches!(state.widgets.get(&id), Some(WidgetItem::Robot(r)) if r.state() == RobotState::Idle)
+}
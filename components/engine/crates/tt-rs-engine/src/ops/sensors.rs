@@ -0,0 +1,116 @@
+//! Sensor operations: hatch a sensor with its nest, pause it, and feed
+//! it what the user does.
+//!
+//! A sensor reports to its nest the way a bird delivers, except that no
+//! one gives it messages: each key press or click becomes one. Nothing
+//! is sensed while the sensor is paused or the workspace's sensors are
+//! switched off (see [`EngineState::sensors_on`]).
+//!
+//! ToonTalk sends what a sensor senses through a bird to the nest. Here
+//! the sensor is its own sender: the message goes straight onto the nest
+//! (or into the outbox for a nest in another house) with no flight. A
+//! sensor has no bird to fly, and a robot waiting on the nest shouldn't
+//! miss a key press because the last one is still on its way, so
+//! messages arrive in the order they were sensed, at once.
+
+use tt_rs_box::ToonBox;
+use tt_rs_core::{Widget, WidgetId};
+use tt_rs_nest::Nest;
+use tt_rs_number::Number;
+use tt_rs_sensors::{Sensor, SensorKind};
+use tt_rs_state::Position;
+use tt_rs_text::Text;
+
+use super::flights::Mail;
+use crate::state::EngineState;
+use crate::widget_item::WidgetItem;
+
+/// Place a new sensor of this kind at `at`, bonded to a new nest beside
+/// it. Returns the ID of the sensor.
+pub fn hatch_sensor(state: &mut EngineState, kind: SensorKind, at: Position) -> WidgetId {
+    let nest = Nest::new();
+    let sensor = Sensor::for_nest(kind, &nest);
+    let sensor_id = state.add_widget(WidgetItem::Sensor(sensor), at);
+    let nest_id = state.add_widget(WidgetItem::Nest(nest), at.offset(60.0, 0.0));
+    log::info!("Hatched: Sensor {} with Nest {}", sensor_id, nest_id);
+    sensor_id
+}
+
+/// Pause a sensor, or resume a paused one.
+pub fn toggle_sensor(state: &mut EngineState, sensor_id: WidgetId) -> bool {
+    match state.widgets.get_mut(&sensor_id) {
+        Some(WidgetItem::Sensor(sensor)) if !sensor.is_copy_source() => {
+            sensor.toggle_paused();
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Report a key press to the nest of every active keyboard sensor, as a
+/// text message naming the key, put straight on the nest rather than
+/// flown there. Nests in other houses get theirs from the outbox.
+/// Returns how many sensors reported it.
+pub fn sense_key(state: &mut EngineState, key: &str) -> usize {
+    sense(state, SensorKind::Keyboard, || Box::new(Text::new(key)))
+}
+
+/// Report a click at `at` on `target` (None: the bare workspace) to the
+/// nest of every active pointer sensor, as a box of the x and y clicked
+/// and the name of what was clicked, put straight on the nest rather
+/// than flown there. Nests in other houses get theirs from the outbox.
+/// Returns how many sensors reported it.
+pub fn sense_click(state: &mut EngineState, at: Position, target: Option<WidgetId>) -> usize {
+    let name = target_name(state, target);
+    sense(state, SensorKind::Pointer, || {
+        let mut click = ToonBox::new(3);
+        click.set_hole(0, Box::new(Number::new(at.x.round() as i64)));
+        click.set_hole(1, Box::new(Number::new(at.y.round() as i64)));
+        click.set_hole(2, Box::new(Text::new(name)));
+        Box::new(click)
+    })
+}
+
+/// Names what was clicked the way a robot's pattern would: "box", the
+/// widget's type ("number", "nest", ...), or "workspace".
+fn target_name(state: &EngineState, target: Option<WidgetId>) -> &'static str {
+    match target {
+        Some(id) if state.boxes.contains_key(&id) => "box",
+        Some(id) => state
+            .widgets
+            .get(&id)
+            .map_or("workspace", |w| w.to_boxed_widget().type_name()),
+        None => "workspace",
+    }
+}
+
+fn sense(
+    state: &mut EngineState,
+    kind: SensorKind,
+    message: impl Fn() -> Box<dyn Widget>,
+) -> usize {
+    if !state.sensors_on {
+        return 0;
+    }
+    let nests: Vec<WidgetId> = state
+        .widgets
+        .values()
+        .filter_map(|w| match w {
+            WidgetItem::Sensor(s) if s.kind() == kind && s.is_active() => s.nest_id(),
+            _ => None,
+        })
+        .collect();
+    let mut reported = 0;
+    for nest_id in nests {
+        let message = message();
+        match state.widgets.get_mut(&nest_id) {
+            Some(WidgetItem::Nest(nest)) => nest.receive(message),
+            _ => state.outbox.push(Mail {
+                nest: nest_id,
+                message,
+            }),
+        }
+        reported += 1;
+    }
+    reported
+}
@@ -0,0 +1,64 @@
+//! Tool operations: vacuum (erase) and wand (copy) on free widgets.
+
+use tt_rs_core::WidgetId;
+use tt_rs_robot::Action;
+use tt_rs_state::Position;
+
+use super::messaging::hatch_nest;
+use super::robots::copy_team;
+use crate::robot_exec::widget_path;
+use crate::state::EngineState;
+use crate::widget_item::WidgetItem;
+
+/// Vacuum a free widget off the workspace.
+/// Tools, robots and copy sources can't be vacuumed.
+pub fn vacuum_widget(state: &mut EngineState, target_id: WidgetId) -> bool {
+    if !is_deletable(state, target_id) {
+        return false;
+    }
+    state.record_action(Action::Remove {
+        path: widget_path(state, target_id),
+    });
+    state.remove_widget(target_id);
+    true
+}
+
+/// Copy a free widget with the wand, placing the copy next to it.
+pub fn wand_copy_widget(state: &mut EngineState, target_id: WidgetId) -> Option<WidgetId> {
+    let copied = match state.widgets.get(&target_id) {
+        Some(w) if !matches!(w, WidgetItem::Number(n) if n.is_copy_source()) => w.copy_widget(),
+        _ => return None,
+    };
+
+    state.record_action(Action::Copy {
+        path: widget_path(state, target_id),
+    });
+    // A robot is copied along with the rest of its team, and a nest
+    // hatches a bird of its own so nothing sent to the original lands here
+    let pos = state.positions.get(&target_id).copied().unwrap_or_default();
+    let at = Position::new(pos.x + 30.0, pos.y + 30.0);
+    let id = match copied {
+        WidgetItem::Robot(_) => copy_team(state, target_id, 30.0, 30.0)?,
+        WidgetItem::Nest(nest) => hatch_nest(state, nest, at),
+        _ => state.add_widget(copied, at),
+    };
+    state.note_created(id);
+    Some(id)
+}
+
+fn is_deletable(state: &EngineState, id: WidgetId) -> bool {
+    state
+        .widgets
+        .get(&id)
+        .map(|w| {
+            !matches!(
+                w,
+                WidgetItem::Vacuum(_) | WidgetItem::Wand(_) | WidgetItem::Robot(_)
+            ) && !matches!(w, WidgetItem::Number(n) if n.is_copy_source())
+                && !matches!(w, WidgetItem::House(h) if h.is_copy_source())
+                && !matches!(w, WidgetItem::Truck(t) if t.is_copy_source())
+                && !matches!(w, WidgetItem::Bomb(b) if b.is_copy_source())
+                && !matches!(w, WidgetItem::Sensor(s) if s.is_copy_source())
+        })
+        .unwrap_or(false)
+}
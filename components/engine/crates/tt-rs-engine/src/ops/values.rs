@@ -0,0 +1,74 @@
+//! Value operations: arithmetic and scales.
+
+use tt_rs_core::WidgetId;
+use tt_rs_robot::Action;
+
+use crate::robot_exec::widget_path;
+use crate::state::EngineState;
+use crate::widget_item::WidgetItem;
+
+/// Which pan of a scales a number is placed on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pan {
+    Left,
+    Right,
+}
+
+/// Apply a dropped number to a target number (arithmetic).
+pub fn apply_number(state: &mut EngineState, id: WidgetId, target_id: WidgetId) -> bool {
+    if id == target_id {
+        return false;
+    }
+
+    let dropped = match state.widgets.get(&id) {
+        Some(WidgetItem::Number(n)) => n.clone(),
+        _ => return false,
+    };
+
+    let mut target = match state.widgets.get(&target_id) {
+        Some(WidgetItem::Number(n)) if !n.is_copy_source() => n.clone(),
+        _ => return false,
+    };
+
+    if target.apply(&dropped).is_none() {
+        return false;
+    }
+
+    record_arithmetic(state, &dropped, target_id);
+    state.widgets.insert(target_id, WidgetItem::Number(target));
+    state.remove_widget(id);
+    true
+}
+
+/// Place a number on one pan of a scales, consuming the number.
+pub fn place_on_scales(
+    state: &mut EngineState,
+    id: WidgetId,
+    scales_id: WidgetId,
+    pan: Pan,
+) -> bool {
+    let value = match state.widgets.get(&id) {
+        Some(WidgetItem::Number(n)) if !n.is_copy_source() => n.numerator(),
+        _ => return false,
+    };
+
+    if let Some(WidgetItem::Scales(scales)) = state.widgets.get_mut(&scales_id) {
+        match pan {
+            Pan::Left => scales.set_left(value),
+            Pan::Right => scales.set_right(value),
+        }
+        state.remove_widget(id);
+        return true;
+    }
+    false
+}
+
+fn record_arithmetic(state: &mut EngineState, dropped: &tt_rs_number::Number, target_id: WidgetId) {
+    let op = dropped.operator().symbol().chars().next().unwrap_or('+');
+    state.record_action(Action::ApplyArithmetic {
+        operator: op,
+        numerator: dropped.numerator(),
+        denominator: dropped.denominator() as i64,
+        target_path: widget_path(state, target_id),
+    });
+}
@@ -0,0 +1,178 @@
+//! Individual robot action handlers.
+
+use tt_rs_core::WidgetId;
+use tt_rs_number::{ArithOperator, Number};
+use tt_rs_state::Position;
+
+use super::path_parse::{resolve_box_hole_path, resolve_widget_path};
+use crate::ops;
+use crate::state::EngineState;
+use crate::widget_item::WidgetItem;
+
+pub fn execute_arithmetic(
+    state: &mut EngineState,
+    (op, num, den): (char, i64, i64),
+    path: &str,
+    input: Option<WidgetId>,
+) {
+    let target_id = match resolve_widget_path(state, path, input) {
+        Some(id) => id,
+        None => return,
+    };
+    // A hand-made program may hold a step no number could record
+    let (Some(operator), Ok(den @ 1..)) = (char_to_op(op), u64::try_from(den)) else {
+        log::warn!("Skipped arithmetic step {}{}/{}", op, num, den);
+        return;
+    };
+    let tool = Number::rational(num, den).with_operator(operator);
+
+    if let Some(WidgetItem::Number(n)) = state.widgets.get(&target_id) {
+        let mut target = n.clone();
+        if target.apply(&tool).is_some() {
+            state.widgets.insert(target_id, WidgetItem::Number(target));
+        }
+    }
+}
+
+pub fn execute_copy(state: &mut EngineState, path: &str, input: Option<WidgetId>) {
+    let target_id = match resolve_widget_path(state, path, input) {
+        Some(id) => id,
+        None => return,
+    };
+    if state.boxes.contains_key(&target_id) {
+        ops::copy_box(state, target_id, 30.0, 30.0);
+    } else {
+        ops::wand_copy_widget(state, target_id);
+    }
+}
+
+pub fn execute_remove(state: &mut EngineState, path: &str, input: Option<WidgetId>) {
+    match resolve_box_hole_path(state, path, input) {
+        Some((box_id, hole)) => {
+            ops::vacuum_hole(state, box_id, hole);
+        }
+        None => {
+            if let Some(id) = resolve_widget_path(state, path, input) {
+                ops::vacuum_widget(state, id);
+            }
+        }
+    }
+}
+
+pub fn execute_new_box(state: &mut EngineState, holes: usize, input: Option<WidgetId>) {
+    let at = input.map(|id| beside(state, id)).unwrap_or_default();
+    ops::create_box(state, holes, at);
+}
+
+pub fn execute_split(state: &mut EngineState, path: &str, at: i64, input: Option<WidgetId>) {
+    if let Some(box_id) = resolve_widget_path(state, path, input) {
+        ops::split_box_at(state, box_id, at, Position::default());
+    }
+}
+
+pub fn execute_take_from_nest(state: &mut EngineState, path: &str, input: Option<WidgetId>) {
+    if let Some(nest_id) = resolve_widget_path(state, path, input) {
+        let at = beside(state, nest_id);
+        ops::take_from_nest(state, nest_id, at);
+    }
+}
+
+pub fn execute_set_off_bomb(state: &mut EngineState, path: &str, input: Option<WidgetId>) {
+    if let Some(bomb_id) = resolve_widget_path(state, path, input) {
+        ops::set_off_bomb(state, bomb_id);
+    }
+}
+
+/// The widget a robot is carrying between a PickUp and a Drop.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Hand {
+    held: Option<WidgetId>,
+}
+
+impl Hand {
+    /// Whether the robot is carrying something.
+    pub fn is_holding(&self) -> bool {
+        self.held.is_some()
+    }
+
+    /// The widget being carried, if any.
+    pub fn held(&self) -> Option<WidgetId> {
+        self.held
+    }
+}
+
+/// Pick up the widget in a hole (taking it out, or taking the top message
+/// of a nest there) or a free widget.
+pub fn execute_pick_up(
+    state: &mut EngineState,
+    path: &str,
+    input: Option<WidgetId>,
+    hand: &mut Hand,
+) {
+    hand.held = match resolve_box_hole_path(state, path, input) {
+        Some((box_id, hole)) => {
+            let at = beside(state, box_id);
+            ops::pick_from_hole(state, box_id, hole, at)
+        }
+        None => resolve_widget_path(state, path, input)
+            .filter(|id| state.widgets.contains_key(id) || state.boxes.contains_key(id)),
+    };
+    if hand.held.is_none() {
+        log::info!("Robot found nothing to pick up at {}", path);
+    }
+}
+
+/// Drop the held widget into a hole or onto a number, bird, nest or box.
+/// A filled hole passes the drop on to its occupant when that makes sense.
+pub fn execute_drop(state: &mut EngineState, path: &str, input: Option<WidgetId>, hand: &mut Hand) {
+    let held = match hand.held.take() {
+        Some(id) => id,
+        None => {
+            log::info!("Robot drop at {} with an empty hand", path);
+            return;
+        }
+    };
+    if let Some((box_id, hole)) = resolve_box_hole_path(state, path, input) {
+        // A bird in the hole sends it on; a nest there keeps it
+        if ops::give_in_hole(state, held, box_id, hole) {
+            return;
+        }
+        let occupant = state
+            .boxes
+            .get(&box_id)
+            .and_then(|b| b.widget_in_hole(hole));
+        if !occupant.is_some_and(|target| drop_onto(state, held, target)) {
+            let eject_to = beside(state, box_id);
+            ops::drop_in_hole(state, held, box_id, hole, eject_to);
+        }
+    } else if let Some(target) = resolve_widget_path(state, path, input) {
+        drop_onto(state, held, target);
+    }
+}
+
+fn drop_onto(state: &mut EngineState, held: WidgetId, target: WidgetId) -> bool {
+    if state.boxes.contains_key(&target) {
+        return ops::join_boxes(state, held, target);
+    }
+    match state.widgets.get(&target) {
+        Some(WidgetItem::Number(_)) => ops::apply_number(state, held, target),
+        Some(WidgetItem::Bird(_)) => ops::give_to_bird(state, held, target),
+        Some(WidgetItem::Nest(_)) => ops::give_to_nest(state, held, target),
+        _ => false,
+    }
+}
+
+fn beside(state: &EngineState, id: WidgetId) -> Position {
+    let pos = state.positions.get(&id).copied().unwrap_or_default();
+    Position::new(pos.x, pos.y + 80.0)
+}
+
+fn char_to_op(c: char) -> Option<ArithOperator> {
+    match c {
+        '+' => Some(ArithOperator::Add),
+        '-' => Some(ArithOperator::Subtract),
+        '*' => Some(ArithOperator::Multiply),
+        '/' => Some(ArithOperator::Divide),
+        _ => None,
+    }
+}
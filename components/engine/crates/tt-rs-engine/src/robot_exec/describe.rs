@@ -0,0 +1,185 @@
+//! Robot programs in plain language, for the robot's backside.
+//!
+//! Paths are told the way a teacher would say them: `input/hole:1` is
+//! "the thing in hole 2 of the box I was given" and `new:0` names what
+//! the step that made it made, as in "the new box I made". Holes count
+//! from 1 here. `Action`'s `Display` keeps the paths, for debugging.
+
+use tt_rs_robot::Action;
+
+use super::path_parse::{INPUT, NEW};
+
+/// What a step made, as in "the second {noun} I {verb}".
+#[derive(Clone, Copy, PartialEq)]
+struct Made {
+    noun: &'static str,
+    verb: &'static str,
+}
+
+const NEW_BOX: Made = Made {
+    noun: "new box",
+    verb: "made",
+};
+const COPY: Made = Made {
+    noun: "copy",
+    verb: "made",
+};
+const SPLIT_OFF: Made = Made {
+    noun: "box",
+    verb: "split off",
+};
+const MESSAGE: Made = Made {
+    noun: "message",
+    verb: "took",
+};
+const TAKEN_OUT: Made = Made {
+    noun: "thing",
+    verb: "took out",
+};
+
+/// Each step of a program in plain language. Later steps name what
+/// earlier ones made by the step that made it.
+pub fn describe_program(actions: &[Action]) -> Vec<String> {
+    let made: Vec<Made> = actions.iter().filter_map(made_by).collect();
+    actions
+        .iter()
+        .map(|action| describe(action, &made))
+        .collect()
+}
+
+fn describe(action: &Action, made: &[Made]) -> String {
+    let thing = |path: &str| thing_at(path, made);
+    match action {
+        Action::PickUp { path } => format!("Pick up {}", thing(path)),
+        Action::Drop { path } => match hole_at(path, made) {
+            Some(hole) => format!("Put it in {}", hole),
+            None => format!("Drop it on {}", thing(path)),
+        },
+        Action::Copy { path } => format!("Copy {} with the wand", thing(path)),
+        Action::Remove { path } => format!("Vacuum up {}", thing(path)),
+        Action::ApplyArithmetic {
+            operator,
+            numerator,
+            denominator,
+            target_path,
+        } => {
+            let amount = match denominator {
+                1 => numerator.to_string(),
+                _ => format!("{}/{}", numerator, denominator),
+            };
+            let target = thing(target_path);
+            match operator {
+                '+' => format!("Add {} to {}", amount, target),
+                '-' => format!("Take {} away from {}", amount, target),
+                '*' => format!("Multiply {} by {}", target, amount),
+                '/' => format!("Divide {} by {}", target, amount),
+                _ => format!("Change {} with {}{}", target, operator, amount),
+            }
+        }
+        Action::NewBox { holes: 1 } => "Make a box with 1 hole".to_string(),
+        Action::NewBox { holes } => format!("Make a box with {} holes", holes),
+        Action::SplitBox { path, at: 0 } => {
+            format!("Copy {} with everything in it", thing(path))
+        }
+        Action::SplitBox { path, at } if *at < 0 => format!(
+            "Split the last {} off {}",
+            holes(at.unsigned_abs()),
+            thing(path)
+        ),
+        Action::SplitBox { path, at } => format!(
+            "Split {} after its first {}",
+            thing(path),
+            holes(at.unsigned_abs())
+        ),
+        Action::TakeFromNest { path } => format!("Take a message from {}", thing(path)),
+        Action::SetOffBomb { path } => format!("Set off {}", thing(path)),
+    }
+}
+
+/// What an action leaves on the floor for later steps to use as `new:K`.
+fn made_by(action: &Action) -> Option<Made> {
+    match action {
+        Action::NewBox { .. } => Some(NEW_BOX),
+        Action::Copy { .. } | Action::SplitBox { at: 0, .. } => Some(COPY),
+        Action::SplitBox { .. } => Some(SPLIT_OFF),
+        Action::TakeFromNest { .. } => Some(MESSAGE),
+        Action::PickUp { path } if absolute_hole(path).is_some() || hole_path(path).is_some() => {
+            Some(TAKEN_OUT)
+        }
+        _ => None,
+    }
+}
+
+fn holes(n: u64) -> String {
+    match n {
+        1 => "1 hole".to_string(),
+        _ => format!("{} holes", n),
+    }
+}
+
+/// "the thing in hole 2 of the box I was given", "the new box I made", ...
+fn thing_at(path: &str, made: &[Made]) -> String {
+    if let Some(id) = path.strip_prefix("widget:") {
+        return format!("thing #{}", id);
+    }
+    match hole_at(path, made) {
+        Some(hole) => format!("the thing in {}", hole),
+        None => root(path, made),
+    }
+}
+
+/// "hole 2 of the box I was given", or None if the path isn't to a hole.
+fn hole_at(path: &str, made: &[Made]) -> Option<String> {
+    if let Some((id, hole)) = absolute_hole(path) {
+        return Some(format!("hole {} of box #{}", hole + 1, id));
+    }
+    let (head, holes) = hole_path(path)?;
+    let (&last, outer) = holes.split_last()?;
+    let container = outer.iter().fold(root(head, made), |phrase, hole| {
+        format!("the box in hole {} of {}", hole + 1, phrase)
+    });
+    Some(format!("hole {} of {}", last + 1, container))
+}
+
+/// "box:45:hole:0" -> ("45", 0).
+fn absolute_hole(path: &str) -> Option<(&str, usize)> {
+    let (id, hole) = path.strip_prefix("box:")?.split_once(":hole:")?;
+    Some((id, hole.parse().ok()?))
+}
+
+/// "input/hole:1/hole:0" -> ("input", [1, 0]), if it has any holes.
+fn hole_path(path: &str) -> Option<(&str, Vec<usize>)> {
+    let (head, rest) = path.split_once('/')?;
+    let holes = rest
+        .split('/')
+        .map(|seg| seg.strip_prefix("hole:")?.parse().ok())
+        .collect::<Option<Vec<_>>>()?;
+    Some((head, holes))
+}
+
+/// The phrase for a path's root: the given box or something made.
+fn root(head: &str, made: &[Made]) -> String {
+    if head == INPUT {
+        return "the box I was given".to_string();
+    }
+    let made_k = head
+        .strip_prefix(NEW)
+        .and_then(|rest| rest.strip_prefix(':'))
+        .and_then(|k| k.parse::<usize>().ok())
+        .filter(|&k| k < made.len());
+    let Some(k) = made_k else {
+        return "something I made".to_string();
+    };
+    let kind = &made[k];
+    let nth = made[..k].iter().filter(|m| *m == kind).count();
+    let alike = made.iter().filter(|m| *m == kind).count();
+    match (alike, ORDINALS.get(nth)) {
+        (1, _) => format!("the {} I {}", kind.noun, kind.verb),
+        (_, Some(ordinal)) => format!("the {} {} I {}", ordinal, kind.noun, kind.verb),
+        _ => format!("{} #{} I {}", kind.noun, nth + 1, kind.verb),
+    }
+}
+
+const ORDINALS: [&str; 10] = [
+    "first", "second", "third", "fourth", "fifth", "sixth", "seventh", "eighth", "ninth", "tenth",
+];
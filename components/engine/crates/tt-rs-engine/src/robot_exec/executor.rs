@@ -0,0 +1,56 @@
+//! Robot action dispatch, shared by robot runs and training replays.
+
+use tt_rs_core::WidgetId;
+use tt_rs_robot::Action;
+
+use super::actions::{self, Hand};
+use crate::state::EngineState;
+use crate::widget_item::WidgetItem;
+
+pub(super) fn get_actions(state: &EngineState, id: WidgetId) -> Vec<Action> {
+    state
+        .widgets
+        .get(&id)
+        .and_then(|w| match w {
+            WidgetItem::Robot(r) => Some(r.actions().to_vec()),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+pub(super) fn set_working(state: &mut EngineState, id: WidgetId, working: bool) {
+    if let Some(WidgetItem::Robot(r)) = state.widgets.get_mut(&id) {
+        if working {
+            r.start_working();
+        } else {
+            r.stop_working();
+        }
+    }
+}
+
+pub(super) fn execute_action(
+    state: &mut EngineState,
+    action: &Action,
+    input: Option<WidgetId>,
+    hand: &mut Hand,
+) {
+    match action {
+        Action::ApplyArithmetic {
+            operator,
+            numerator,
+            denominator,
+            target_path,
+        } => {
+            let tool = (*operator, *numerator, *denominator);
+            actions::execute_arithmetic(state, tool, target_path, input)
+        }
+        Action::Drop { path } => actions::execute_drop(state, path, input, hand),
+        Action::Copy { path } => actions::execute_copy(state, path, input),
+        Action::Remove { path } => actions::execute_remove(state, path, input),
+        Action::PickUp { path } => actions::execute_pick_up(state, path, input, hand),
+        Action::NewBox { holes } => actions::execute_new_box(state, *holes, input),
+        Action::SplitBox { path, at } => actions::execute_split(state, path, *at, input),
+        Action::TakeFromNest { path } => actions::execute_take_from_nest(state, path, input),
+        Action::SetOffBomb { path } => actions::execute_set_off_bomb(state, path, input),
+    }
+}
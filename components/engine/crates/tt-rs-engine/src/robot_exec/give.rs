@@ -0,0 +1,84 @@
+//! Giving a box to a robot: train on it, or match and run.
+
+use tt_rs_core::WidgetId;
+use tt_rs_robot::{RobotState, StopReason};
+
+use super::stepper::RobotRun;
+use super::training::{resume_training, start_training};
+use crate::matching::pattern_box;
+use crate::state::EngineState;
+use crate::widget_item::WidgetItem;
+
+/// What happened when a box was given to a robot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RobotOutcome {
+    /// The robot was untrained, so it started training on the box.
+    Training,
+    /// The team ran `iterations` full passes, then stopped; `robot` is
+    /// the member that worked last.
+    Ran {
+        robot: WidgetId,
+        iterations: usize,
+        stopped: StopReason,
+    },
+    /// A robot's pattern is waiting on an empty nest in the box.
+    Suspended { robot: WidgetId, nest: WidgetId },
+    /// No robot in the team matched the box.
+    NoMatch,
+}
+
+/// A box given to a robot for watching: either it is already settled,
+/// or a team run is ready to be stepped.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Given {
+    /// Nothing to watch: training started, or no robot could start.
+    Settled(RobotOutcome),
+    /// The team is about to work; step it to see each action.
+    Running(RobotRun),
+}
+
+/// Give a box to a robot.
+///
+/// An untrained robot takes the box as its pattern and starts training;
+/// one waiting to retrain replays its kept steps on the box first.
+/// A trained robot runs its team on the box until it stops.
+/// Returns None if the robot or box doesn't exist or the robot is busy.
+pub fn give_box_to_robot(
+    state: &mut EngineState,
+    robot_id: WidgetId,
+    box_id: WidgetId,
+) -> Option<RobotOutcome> {
+    match give_box(state, robot_id, box_id)? {
+        Given::Settled(outcome) => Some(outcome),
+        Given::Running(run) => Some(run.finish_all(state)),
+    }
+}
+
+/// Give a box to a robot without running it: like [`give_box_to_robot`],
+/// but a trained team is returned as a [`RobotRun`] to step through.
+pub fn give_box(state: &mut EngineState, robot_id: WidgetId, box_id: WidgetId) -> Option<Given> {
+    let (trained, retrain_at) = match state.widgets.get(&robot_id) {
+        Some(WidgetItem::Robot(r)) if r.state() == RobotState::Idle => {
+            (!r.actions().is_empty(), r.retrain_at())
+        }
+        _ => return None,
+    };
+    let pattern = pattern_box(state, box_id)?;
+
+    if let Some(step) = retrain_at {
+        resume_training(state, robot_id, step, Some(box_id));
+        return Some(Given::Settled(RobotOutcome::Training));
+    }
+    if !trained {
+        start_training(state, robot_id);
+        if let Some(WidgetItem::Robot(r)) = state.widgets.get_mut(&robot_id) {
+            r.set_pattern(pattern);
+        }
+        state.training_input = Some(box_id);
+        return Some(Given::Settled(RobotOutcome::Training));
+    }
+    Some(match RobotRun::start(state, robot_id, Some(box_id)) {
+        Ok(run) => Given::Running(run),
+        Err(outcome) => Given::Settled(outcome),
+    })
+}
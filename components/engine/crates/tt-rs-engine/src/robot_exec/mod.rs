@@ -0,0 +1,28 @@
+//! Robot training and execution.
+//!
+//! Robots are trained by giving them a box (which becomes the pattern in
+//! their thought bubble) and demonstrating actions. A trained robot runs
+//! when given a box matching its pattern.
+
+mod actions;
+mod describe;
+mod executor;
+mod give;
+mod path_parse;
+mod path_record;
+mod progress;
+mod run;
+mod stepper;
+mod team;
+mod training;
+
+pub use describe::describe_program;
+pub use give::{give_box, give_box_to_robot, Given, RobotOutcome};
+pub use path_parse::{
+    parse_box_hole_path, parse_widget_path, resolve_box_hole_path, resolve_widget_path,
+};
+pub use path_record::{box_hole_path, widget_path};
+pub use run::{match_robot, run_team};
+pub use stepper::{ActionTarget, RobotRun};
+pub use team::team;
+pub use training::{click_robot, retrain_from, start_training, stop_training};
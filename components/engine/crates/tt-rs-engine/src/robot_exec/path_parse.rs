@@ -0,0 +1,123 @@
+//! Path resolution for robot actions.
+//!
+//! Paths recorded relative to the robot's input box look like
+//! `input`, `input/hole:1` or `input/hole:1/hole:0` and are resolved
+//! against whatever box the robot is working on. Paths rooted at `new:K`
+//! name the K-th widget made during the pass. Absolute paths
+//! (`widget:123`, `box:45:hole:0`) are still accepted.
+
+use tt_rs_core::WidgetId;
+
+use crate::state::EngineState;
+
+/// The root segment of paths relative to a robot's input box.
+pub const INPUT: &str = "input";
+
+/// The root segment of paths to widgets made during training.
+pub const NEW: &str = "new";
+
+/// Resolve a widget path: "widget:123", "input/hole:N/..." or "new:K/...".
+pub fn resolve_widget_path(
+    state: &EngineState,
+    path: &str,
+    input: Option<WidgetId>,
+) -> Option<WidgetId> {
+    resolve_widget_in(state, path, input, created(state))
+}
+
+/// Resolve a hole path: "box:123:hole:0", "input/.../hole:N" or "new:K/.../hole:N".
+pub fn resolve_box_hole_path(
+    state: &EngineState,
+    path: &str,
+    input: Option<WidgetId>,
+) -> Option<(WidgetId, usize)> {
+    resolve_box_hole_in(state, path, input, created(state))
+}
+
+/// Like [`resolve_widget_path`], with `new:K` indexing `created`.
+pub(super) fn resolve_widget_in(
+    state: &EngineState,
+    path: &str,
+    input: Option<WidgetId>,
+    created: &[WidgetId],
+) -> Option<WidgetId> {
+    let (root, rest) = match resolve_root(path, input, created) {
+        Some(split) => split,
+        None => return parse_widget_path(path),
+    };
+    if rest.is_empty() {
+        return root;
+    }
+    let (box_id, hole) = resolve_box_hole_in(state, path, input, created)?;
+    state.boxes.get(&box_id)?.widget_in_hole(hole)
+}
+
+/// Like [`resolve_box_hole_path`], with `new:K` indexing `created`.
+pub(super) fn resolve_box_hole_in(
+    state: &EngineState,
+    path: &str,
+    input: Option<WidgetId>,
+    created: &[WidgetId],
+) -> Option<(WidgetId, usize)> {
+    let (root, rest) = match resolve_root(path, input, created) {
+        Some(split) => split,
+        None => return parse_box_hole_path(path),
+    };
+    let holes = rest
+        .split('/')
+        .skip(1)
+        .map(|seg| seg.strip_prefix("hole:")?.parse::<usize>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    let (&last, outer) = holes.split_last()?;
+
+    let mut box_id = root?;
+    for &hole in outer {
+        box_id = state.boxes.get(&box_id)?.widget_in_hole(hole)?;
+    }
+    Some((box_id, last))
+}
+
+/// Split a relative path into its root widget and the "/hole:N" rest.
+/// Returns None for absolute paths.
+fn resolve_root<'a>(
+    path: &'a str,
+    input: Option<WidgetId>,
+    created: &[WidgetId],
+) -> Option<(Option<WidgetId>, &'a str)> {
+    let (head, rest) = path.split_at(path.find('/').unwrap_or(path.len()));
+    if head == INPUT {
+        return Some((input, rest));
+    }
+    let k = head
+        .strip_prefix(NEW)?
+        .strip_prefix(':')?
+        .parse::<usize>()
+        .ok()?;
+    Some((created.get(k).copied(), rest))
+}
+
+fn created(state: &EngineState) -> &[WidgetId] {
+    state.created.as_deref().unwrap_or(&[])
+}
+
+/// Parse "widget:123" -> WidgetId.
+pub fn parse_widget_path(path: &str) -> Option<WidgetId> {
+    let parts: Vec<&str> = path.split(':').collect();
+    if parts.len() == 2 && parts[0] == "widget" {
+        parts[1].parse::<u64>().ok().map(WidgetId::from_u64)
+    } else {
+        None
+    }
+}
+
+/// Parse "box:123:hole:0" -> (WidgetId, usize).
+pub fn parse_box_hole_path(path: &str) -> Option<(WidgetId, usize)> {
+    let parts: Vec<&str> = path.split(':').collect();
+    if parts.len() == 4 && parts[0] == "box" && parts[2] == "hole" {
+        let box_id = parts[1].parse::<u64>().ok().map(WidgetId::from_u64)?;
+        let hole = parts[3].parse::<usize>().ok()?;
+        Some((box_id, hole))
+    } else {
+        None
+    }
+}
@@ -0,0 +1,49 @@
+//! Building action paths while a robot is being trained.
+//!
+//! Anything inside the training robot's input box is recorded relative
+//! to it, so the robot can replay on any box matching its pattern.
+//! Widgets made during training are recorded as `new:K` by creation order.
+
+use tt_rs_core::WidgetId;
+
+use super::path_parse::{INPUT, NEW};
+use crate::state::EngineState;
+
+/// Path to a widget: "input/hole:N/..." or "new:K/..." if known to the
+/// robot, else "widget:N".
+pub fn widget_path(state: &EngineState, id: WidgetId) -> String {
+    relative_path(state, id).unwrap_or_else(|| format!("widget:{}", id))
+}
+
+/// Path to a box hole: "input/.../hole:N" or "new:K/.../hole:N" if known
+/// to the robot, else "box:B:hole:N".
+pub fn box_hole_path(state: &EngineState, box_id: WidgetId, hole: usize) -> String {
+    match relative_path(state, box_id) {
+        Some(prefix) => format!("{}/hole:{}", prefix, hole),
+        None => format!("box:{}:hole:{}", box_id, hole),
+    }
+}
+
+fn relative_path(state: &EngineState, id: WidgetId) -> Option<String> {
+    let mut holes = Vec::new();
+    let mut current = id;
+    let mut path = loop {
+        if state.training_input == Some(current) {
+            break INPUT.to_string();
+        }
+        if let Some(k) = created_index(state, current) {
+            break format!("{}:{}", NEW, k);
+        }
+        let &(parent, hole) = state.widget_in_box.get(&current)?;
+        holes.push(hole);
+        current = parent;
+    };
+    for hole in holes.iter().rev() {
+        path.push_str(&format!("/hole:{}", hole));
+    }
+    Some(path)
+}
+
+fn created_index(state: &EngineState, id: WidgetId) -> Option<usize> {
+    state.created.as_ref()?.iter().position(|&c| c == id)
+}
@@ -0,0 +1,78 @@
+//! No-progress detection for robot runs.
+//!
+//! A team that leaves the workspace exactly as an earlier pass left it
+//! will go round the same passes forever, so the run is stopped there
+//! instead of spending the rest of its step budget.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use tt_rs_box::ToonBox;
+use tt_rs_core::{Widget, WidgetId};
+
+use crate::state::EngineState;
+use crate::widget_item::WidgetItem;
+
+/// A hash of everything a robot pass can change: each widget and box,
+/// where it sits (a hole, or a spot on the workspace), what it shows,
+/// and the messages queued on nests and birds. Robots are left out.
+pub(super) fn fingerprint(state: &EngineState) -> u64 {
+    let widgets = state
+        .widgets
+        .iter()
+        .filter(|(_, w)| !w.is_robot())
+        .map(|(&id, _)| id);
+    // Summing per-item hashes makes the result independent of map order
+    widgets
+        .chain(state.boxes.keys().copied())
+        .fold(0u64, |sum, id| sum.wrapping_add(item_hash(state, id)))
+}
+
+fn item_hash(state: &EngineState, id: WidgetId) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    id.hash(&mut hasher);
+    match state.widget_in_box.get(&id) {
+        Some(slot) => slot.hash(&mut hasher),
+        None => state
+            .positions
+            .get(&id)
+            .map(|p| (p.x.to_bits(), p.y.to_bits()))
+            .hash(&mut hasher),
+    }
+    if let Some(b) = state.boxes.get(&id) {
+        (b.num_holes, b.erased).hash(&mut hasher);
+        let mut contents: Vec<_> = b.contents.iter().collect();
+        contents.sort_by_key(|(hole, _)| **hole);
+        contents.hash(&mut hasher);
+    }
+    match state.widgets.get(&id) {
+        Some(WidgetItem::Nest(nest)) => {
+            nest.channel().hash(&mut hasher);
+            nest.messages().for_each(|m| hash_message(m, &mut hasher));
+        }
+        Some(WidgetItem::Bird(bird)) => {
+            (bird.nest_id(), bird.state()).hash(&mut hasher);
+            bird.carrying()
+                .into_iter()
+                .chain(bird.queued())
+                .for_each(|m| hash_message(m, &mut hasher));
+        }
+        Some(w) => w.to_boxed_widget().description().hash(&mut hasher),
+        None => {}
+    }
+    hasher.finish()
+}
+
+/// Hashes a message by identity and value, looking inside box messages.
+fn hash_message(message: &dyn Widget, hasher: &mut DefaultHasher) {
+    message.id().hash(hasher);
+    message.description().hash(hasher);
+    if let Some(b) = message.as_any().downcast_ref::<ToonBox>() {
+        for hole in b.holes() {
+            hole.index().hash(hasher);
+            if let Some(content) = hole.content() {
+                hash_message(content, hasher);
+            }
+        }
+    }
+}
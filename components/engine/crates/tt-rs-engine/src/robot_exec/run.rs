@@ -0,0 +1,37 @@
+//! The robot run loop: act, re-match, repeat.
+//!
+//! Like ToonTalk robots, a matching team keeps working on its box: after
+//! each pass over one robot's actions the (possibly changed) box is
+//! matched again from the lead down, and the first matching robot goes
+//! round once more. This stops when no robot matches, the matching robot
+//! has to wait on a nest, or the state's per-run step budget is spent.
+
+use tt_rs_core::{MatchResult, WidgetId};
+
+use super::give::RobotOutcome;
+use super::stepper::RobotRun;
+use crate::matching::match_pattern;
+use crate::state::EngineState;
+use crate::widget_item::WidgetItem;
+
+/// Matches a box against a robot's pattern.
+/// Returns None if the widget isn't a robot or it has no pattern.
+pub fn match_robot(
+    state: &EngineState,
+    robot_id: WidgetId,
+    box_id: WidgetId,
+) -> Option<MatchResult> {
+    match state.widgets.get(&robot_id) {
+        Some(WidgetItem::Robot(r)) => Some(match_pattern(state, r.pattern()?, box_id)),
+        _ => None,
+    }
+}
+
+/// Run a robot's team on a box until it stops, recording why on the
+/// last robot that worked. The whole team shares one step budget.
+pub fn run_team(state: &mut EngineState, lead: WidgetId, box_id: WidgetId) -> RobotOutcome {
+    match RobotRun::start(state, lead, Some(box_id)) {
+        Ok(run) => run.finish_all(state),
+        Err(outcome) => outcome,
+    }
+}
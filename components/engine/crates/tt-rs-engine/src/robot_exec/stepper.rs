@@ -0,0 +1,275 @@
+//! Step-by-step robot runs.
+//!
+//! A [`RobotRun`] is a team's run on a box advanced one action at a time,
+//! so the app can show each action before the next one happens. Running
+//! it to the end is exactly what giving a box to a robot does.
+//!
+//! A run never goes on forever: it stops when the state's step budget is
+//! spent, or when a pass leaves the workspace as an earlier pass did.
+//! It also stops at once when one of its actions sets off a bomb.
+
+use std::collections::HashSet;
+
+use tt_rs_core::{MatchResult, WidgetId};
+use tt_rs_robot::{Action, StopReason};
+
+use super::actions::Hand;
+use super::executor::{execute_action, get_actions, set_working};
+use super::give::RobotOutcome;
+use super::path_parse::{resolve_box_hole_in, resolve_widget_in};
+use super::progress::fingerprint;
+use super::run::match_robot;
+use super::team::team;
+use crate::state::EngineState;
+use crate::widget_item::WidgetItem;
+
+/// Where an action happens, for showing the robot at work.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionTarget {
+    /// A widget or box.
+    Widget(WidgetId),
+    /// A hole in a box.
+    Hole(WidgetId, usize),
+}
+
+/// A robot team working on a box, one action per [`RobotRun::step`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RobotRun {
+    team: Vec<WidgetId>,
+    input: Option<WidgetId>,
+    robot: WidgetId,
+    actions: Vec<Action>,
+    next: usize,
+    budget: usize,
+    iterations: usize,
+    hand: Hand,
+    created: Vec<WidgetId>,
+    /// Fingerprints of the workspace between passes.
+    seen: HashSet<u64>,
+    /// The nest the run stopped to wait on, if any.
+    waiting_on: Option<WidgetId>,
+}
+
+/// Who works next, or why the run is over.
+enum Pick {
+    Work(WidgetId),
+    Wait(WidgetId),
+    Stop(StopReason),
+}
+
+impl RobotRun {
+    /// Start a team on a box (or, without a box, a lead without a pattern
+    /// on one pass).
+    /// Fails with the outcome if no member can start: nothing matched,
+    /// or the matching member is waiting on a nest.
+    pub fn start(
+        state: &mut EngineState,
+        lead: WidgetId,
+        input: Option<WidgetId>,
+    ) -> Result<Self, RobotOutcome> {
+        let mut run = Self {
+            team: team(state, lead),
+            input,
+            robot: lead,
+            actions: Vec::new(),
+            next: 0,
+            budget: state.step_budget,
+            iterations: 0,
+            hand: Hand::default(),
+            created: Vec::new(),
+            seen: HashSet::new(),
+            waiting_on: None,
+        };
+        let robot = match input {
+            Some(box_id) => match run.first_match(state, box_id) {
+                Some((robot, MatchResult::Suspended { waiting_on })) => {
+                    return Err(RobotOutcome::Suspended {
+                        robot,
+                        nest: waiting_on,
+                    })
+                }
+                Some((robot, _)) => robot,
+                None => return Err(RobotOutcome::NoMatch),
+            },
+            // Robots with a pattern only run when given a box
+            None if get_actions(state, lead).is_empty() || has_pattern(state, lead) => {
+                return Err(RobotOutcome::NoMatch)
+            }
+            None => lead,
+        };
+        run.seen.insert(fingerprint(state));
+        run.begin_pass(state, robot);
+        Ok(run)
+    }
+
+    /// The team's lead robot.
+    pub fn lead(&self) -> WidgetId {
+        self.team.first().copied().unwrap_or(self.robot)
+    }
+
+    /// The robot currently working.
+    pub fn robot(&self) -> WidgetId {
+        self.robot
+    }
+
+    /// The box being worked on.
+    pub fn input(&self) -> Option<WidgetId> {
+        self.input
+    }
+
+    /// The working robot's actions.
+    pub fn actions(&self) -> &[Action] {
+        &self.actions
+    }
+
+    /// Index of the action the next step performs.
+    pub fn position(&self) -> usize {
+        self.next
+    }
+
+    /// Whether the working robot is carrying something.
+    pub fn is_holding(&self) -> bool {
+        self.hand.is_holding()
+    }
+
+    /// The empty nest the run stopped to wait on, if it did.
+    pub fn waiting_on(&self) -> Option<WidgetId> {
+        self.waiting_on
+    }
+
+    /// Where the next action happens, if it names a place that exists.
+    pub fn target(&self, state: &EngineState) -> Option<ActionTarget> {
+        let path = self.actions.get(self.next)?.path()?;
+        if let Some((box_id, hole)) = resolve_box_hole_in(state, path, self.input, &self.created) {
+            return Some(ActionTarget::Hole(box_id, hole));
+        }
+        resolve_widget_in(state, path, self.input, &self.created).map(ActionTarget::Widget)
+    }
+
+    /// Perform the next action. Returns the outcome once the run stops.
+    pub fn step(&mut self, state: &mut EngineState) -> Option<RobotOutcome> {
+        if self.budget == 0 {
+            return Some(self.finish(state, StopReason::BudgetExhausted));
+        }
+        self.budget -= 1;
+
+        // A robot in training must not record what this robot does,
+        // and `new:K` paths refer to what this pass created
+        let training = state.training_robot_id.take();
+        let outer = state.created.replace(std::mem::take(&mut self.created));
+        let action = self.actions[self.next].clone();
+        // Only a bomb this action set off ends this run
+        let bombed = std::mem::take(&mut state.bombed);
+        execute_action(state, &action, self.input, &mut self.hand);
+        self.created = std::mem::replace(&mut state.created, outer).unwrap_or_default();
+        state.training_robot_id = training;
+        let exploded = state.bombed;
+        state.bombed |= bombed;
+        if exploded {
+            return Some(self.finish(state, StopReason::Exploded));
+        }
+
+        self.next += 1;
+        if self.next < self.actions.len() {
+            return None;
+        }
+        self.iterations += 1;
+        match self.pick_next(state) {
+            Pick::Work(_) if !self.seen.insert(fingerprint(state)) => {
+                Some(self.finish(state, StopReason::NoProgress))
+            }
+            Pick::Work(robot) => {
+                set_working(state, self.robot, false);
+                self.begin_pass(state, robot);
+                None
+            }
+            Pick::Wait(nest) => {
+                self.waiting_on = Some(nest);
+                Some(self.finish(state, StopReason::WaitingOnNest))
+            }
+            Pick::Stop(reason) => Some(self.finish(state, reason)),
+        }
+    }
+
+    /// Perform up to `steps` actions, so a long run can be spread over
+    /// several frames. Returns the outcome once the run stops.
+    pub fn step_many(&mut self, state: &mut EngineState, steps: usize) -> Option<RobotOutcome> {
+        (0..steps.max(1)).find_map(|_| self.step(state))
+    }
+
+    /// Run to the end and return the outcome.
+    pub fn finish_all(mut self, state: &mut EngineState) -> RobotOutcome {
+        loop {
+            if let Some(outcome) = self.step(state) {
+                return outcome;
+            }
+        }
+    }
+
+    /// After a pass: re-match the box from the lead down.
+    fn pick_next(&self, state: &EngineState) -> Pick {
+        let box_id = match self.input {
+            Some(id) => id,
+            None => return Pick::Stop(StopReason::PatternFailed),
+        };
+        // Without a pattern there is nothing to re-match: one pass only
+        if match_robot(state, self.robot, box_id).is_none() {
+            return Pick::Stop(StopReason::PatternFailed);
+        }
+        match self.first_match(state, box_id) {
+            Some((_, MatchResult::Suspended { waiting_on })) => Pick::Wait(waiting_on),
+            Some((robot, _)) => Pick::Work(robot),
+            None => Pick::Stop(StopReason::PatternFailed),
+        }
+    }
+
+    /// The first trained team member whose pattern doesn't reject the box.
+    /// A robot trained without a box accepts any box.
+    fn first_match(
+        &self,
+        state: &EngineState,
+        box_id: WidgetId,
+    ) -> Option<(WidgetId, MatchResult)> {
+        self.team.iter().find_map(|&robot_id| {
+            if get_actions(state, robot_id).is_empty() {
+                return None;
+            }
+            match match_robot(state, robot_id, box_id).unwrap_or(MatchResult::matched(box_id)) {
+                MatchResult::NoMatch => None,
+                result => Some((robot_id, result)),
+            }
+        })
+    }
+
+    /// Each pass starts with an empty hand and no created widgets.
+    fn begin_pass(&mut self, state: &mut EngineState, robot: WidgetId) {
+        self.robot = robot;
+        self.actions = get_actions(state, robot);
+        self.next = 0;
+        self.hand = Hand::default();
+        self.created.clear();
+        set_working(state, robot, true);
+    }
+
+    fn finish(&mut self, state: &mut EngineState, stopped: StopReason) -> RobotOutcome {
+        set_working(state, self.robot, false);
+        if let Some(WidgetItem::Robot(r)) = state.widgets.get_mut(&self.robot) {
+            r.set_stop_reason(stopped);
+        }
+        log::info!(
+            "Robot {} stopped after {} passes: {:?}",
+            self.robot,
+            self.iterations,
+            stopped
+        );
+        RobotOutcome::Ran {
+            robot: self.robot,
+            iterations: self.iterations,
+            stopped,
+        }
+    }
+}
+
+fn has_pattern(state: &EngineState, id: WidgetId) -> bool {
+    matches!(state.widgets.get(&id), Some(WidgetItem::Robot(r)) if r.pattern().is_some())
+}
@@ -0,0 +1,26 @@
+//! Robot teams: robots chained through `next_robot`.
+//!
+//! Dropping a robot on another appends it to that robot's team. A box
+//! given to the lead goes to the first member whose pattern matches.
+
+use std::collections::HashSet;
+use tt_rs_core::WidgetId;
+
+use crate::state::EngineState;
+use crate::widget_item::WidgetItem;
+
+/// The lead robot followed by the rest of its team, in chain order.
+/// A chain that loops back on itself stops at the first repeat.
+pub fn team(state: &EngineState, lead: WidgetId) -> Vec<WidgetId> {
+    let mut members = Vec::new();
+    let mut seen = HashSet::new();
+    let mut current = Some(lead);
+    while let Some(id) = current.filter(|id| seen.insert(*id)) {
+        current = match state.widgets.get(&id) {
+            Some(WidgetItem::Robot(r)) => r.next_robot(),
+            _ => break,
+        };
+        members.push(id);
+    }
+    members
+}
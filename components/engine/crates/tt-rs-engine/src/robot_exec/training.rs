@@ -0,0 +1,110 @@
+//! Robot training toggles.
+
+use tt_rs_core::WidgetId;
+use tt_rs_robot::RobotState;
+
+use super::actions::Hand;
+use super::executor::{execute_action, get_actions};
+use super::stepper::RobotRun;
+use crate::state::EngineState;
+use crate::widget_item::WidgetItem;
+
+/// Handle a click on a robot: stop training, run, or start training.
+/// Robots with a box pattern only run when given a matching box.
+/// Returns false if the widget isn't a robot.
+pub fn click_robot(state: &mut EngineState, id: WidgetId) -> bool {
+    let (robot_state, has_actions, has_pattern) = match state.widgets.get(&id) {
+        Some(WidgetItem::Robot(r)) => (r.state(), !r.actions().is_empty(), r.pattern().is_some()),
+        _ => return false,
+    };
+
+    match robot_state {
+        RobotState::Training => stop_training(state, id),
+        RobotState::Idle if has_pattern => log::info!("Robot {} is waiting for a box", id),
+        RobotState::Idle if has_actions => {
+            if let Ok(run) = RobotRun::start(state, id, None) {
+                run.finish_all(state);
+            }
+        }
+        RobotState::Idle => start_training(state, id),
+        RobotState::Working => log::info!("Robot {} is working", id),
+    }
+    true
+}
+
+/// Put a robot into training mode, stopping any other robot in training.
+pub fn start_training(state: &mut EngineState, id: WidgetId) {
+    if let Some(old_id) = state.training_robot_id {
+        stop_training(state, old_id);
+    }
+    if let Some(WidgetItem::Robot(r)) = state.widgets.get_mut(&id) {
+        r.start_training();
+    }
+    state.training_robot_id = Some(id);
+    state.training_held = None;
+    state.created = Some(Vec::new());
+}
+
+/// Take a robot out of training mode.
+pub fn stop_training(state: &mut EngineState, id: WidgetId) {
+    if let Some(WidgetItem::Robot(robot)) = state.widgets.get_mut(&id) {
+        robot.stop_training();
+    }
+    state.training_robot_id = None;
+    state.training_input = None;
+    state.training_held = None;
+    state.created = None;
+}
+
+/// Retrain a robot from `step`, keeping the actions before it.
+///
+/// A robot with a pattern waits for a box to retrain on; one without
+/// starts at once. Returns false if the robot isn't idle or has fewer
+/// than `step` actions.
+pub fn retrain_from(state: &mut EngineState, id: WidgetId, step: usize) -> bool {
+    let has_pattern = match state.widgets.get(&id) {
+        Some(WidgetItem::Robot(r))
+            if r.state() == RobotState::Idle && step <= r.actions().len() =>
+        {
+            r.pattern().is_some()
+        }
+        _ => return false,
+    };
+    if has_pattern {
+        if let Some(WidgetItem::Robot(r)) = state.widgets.get_mut(&id) {
+            r.set_retrain_at(Some(step));
+        }
+        log::info!(
+            "Robot {} will retrain from step {} when given a box",
+            id,
+            step
+        );
+    } else {
+        resume_training(state, id, step, None);
+    }
+    true
+}
+
+/// Replay a robot's first `step` actions on `input`, then train from there.
+pub(super) fn resume_training(
+    state: &mut EngineState,
+    id: WidgetId,
+    step: usize,
+    input: Option<WidgetId>,
+) {
+    if let Some(old_id) = state.training_robot_id {
+        stop_training(state, old_id);
+    }
+    let kept = get_actions(state, id);
+    let mut hand = Hand::default();
+    state.created = Some(Vec::new());
+    for action in kept.iter().take(step) {
+        execute_action(state, action, input, &mut hand);
+    }
+    if let Some(WidgetItem::Robot(r)) = state.widgets.get_mut(&id) {
+        r.resume_training(step);
+    }
+    state.training_robot_id = Some(id);
+    state.training_input = input;
+    state.training_held = hand.held();
+}
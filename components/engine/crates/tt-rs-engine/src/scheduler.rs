@@ -0,0 +1,295 @@
+//! Many robots working at once.
+//!
+//! The [`Scheduler`] keeps a run queue of processes, each a robot team
+//! working on a box. Every tick the running processes take turns, one
+//! action each, so no robot hogs the workspace. A process whose pattern
+//! waits on an empty nest is parked until a bird (or the user) delivers
+//! something to that nest; it then matches its box again and carries on.
+//! If no bird is left to deliver there, or the nest itself is gone, the
+//! process stops instead.
+//!
+//! A process that sets off a bomb ends at once, and its robots and box
+//! (with everything in it) are reclaimed.
+
+use tt_rs_core::WidgetId;
+use tt_rs_robot::StopReason;
+
+use crate::ops;
+use crate::robot_exec::{team, RobotOutcome, RobotRun};
+use crate::state::EngineState;
+use crate::widget_item::WidgetItem;
+
+/// Identifies a process in the scheduler.
+pub type ProcessId = usize;
+
+/// What a process is doing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessState {
+    /// Taking turns performing actions.
+    Running,
+    /// Parked until a message arrives in the nest.
+    Waiting { nest: WidgetId },
+    /// Stopped for good.
+    Finished,
+}
+
+impl ProcessState {
+    /// Display name for the state.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProcessState::Running => "running",
+            ProcessState::Waiting { .. } => "waiting",
+            ProcessState::Finished => "finished",
+        }
+    }
+}
+
+/// A robot team working on a box.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Process {
+    id: ProcessId,
+    lead: WidgetId,
+    input: Option<WidgetId>,
+    state: ProcessState,
+    run: Option<RobotRun>,
+    outcome: Option<RobotOutcome>,
+}
+
+impl Process {
+    /// The process's ID.
+    pub fn id(&self) -> ProcessId {
+        self.id
+    }
+
+    /// The team's lead robot.
+    pub fn lead(&self) -> WidgetId {
+        self.lead
+    }
+
+    /// The box being worked on.
+    pub fn input(&self) -> Option<WidgetId> {
+        self.input
+    }
+
+    /// What the process is doing.
+    pub fn state(&self) -> ProcessState {
+        self.state
+    }
+
+    /// The run in progress, while running.
+    pub fn run(&self) -> Option<&RobotRun> {
+        self.run.as_ref()
+    }
+
+    /// How the last run ended, once it has.
+    pub fn outcome(&self) -> Option<&RobotOutcome> {
+        self.outcome.as_ref()
+    }
+
+    /// Whether the process is not finished.
+    pub fn is_live(&self) -> bool {
+        self.state != ProcessState::Finished
+    }
+
+    /// Whether the last run ended by setting off a bomb.
+    fn has_exploded(&self) -> bool {
+        matches!(
+            self.outcome,
+            Some(RobotOutcome::Ran {
+                stopped: StopReason::Exploded,
+                ..
+            })
+        )
+    }
+
+    /// Take the outcome of a run (or of a failed start).
+    fn settle(&mut self, outcome: RobotOutcome, waiting_on: Option<WidgetId>) {
+        self.run = None;
+        let nest = match outcome {
+            RobotOutcome::Suspended { nest, .. } => Some(nest),
+            _ => waiting_on,
+        };
+        self.state = match nest {
+            Some(nest) => ProcessState::Waiting { nest },
+            None => ProcessState::Finished,
+        };
+        self.outcome = Some(outcome);
+    }
+}
+
+/// The run queue: every robot process, in the order they started.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Scheduler {
+    processes: Vec<Process>,
+    next_id: ProcessId,
+}
+
+impl Scheduler {
+    /// Creates an empty run queue.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All processes, oldest first.
+    pub fn processes(&self) -> &[Process] {
+        &self.processes
+    }
+
+    /// A process by ID.
+    pub fn process(&self, id: ProcessId) -> Option<&Process> {
+        self.processes.iter().find(|p| p.id == id)
+    }
+
+    /// Whether any process is running.
+    pub fn is_running(&self) -> bool {
+        self.processes
+            .iter()
+            .any(|p| p.state == ProcessState::Running)
+    }
+
+    /// The unfinished process led by a robot, if any.
+    pub fn live_process_of(&self, lead: WidgetId) -> Option<&Process> {
+        self.processes
+            .iter()
+            .find(|p| p.lead == lead && p.is_live())
+    }
+
+    /// Start a team on a box (or a lead without a pattern on no box).
+    /// A team that can't start yet waits on its nest; one that doesn't
+    /// match at all is finished at once.
+    pub fn spawn(
+        &mut self,
+        state: &mut EngineState,
+        lead: WidgetId,
+        input: Option<WidgetId>,
+    ) -> ProcessId {
+        let id = self.push(lead, input);
+        let process = self.processes.last_mut().expect("just pushed");
+        match RobotRun::start(state, lead, input) {
+            Ok(run) => process.run = Some(run),
+            Err(outcome) => {
+                if let RobotOutcome::Suspended { robot, .. } = outcome {
+                    set_stop_reason(state, robot, StopReason::WaitingOnNest);
+                }
+                process.settle(outcome, None);
+            }
+        }
+        id
+    }
+
+    /// Queue a run that has already started.
+    pub fn spawn_run(&mut self, run: RobotRun) -> ProcessId {
+        let id = self.push(run.lead(), run.input());
+        if let Some(process) = self.processes.last_mut() {
+            process.run = Some(run);
+        }
+        id
+    }
+
+    /// Give every running process up to `rounds` turns of one action
+    /// each, waking waiting processes whose nest has received something.
+    pub fn tick(&mut self, state: &mut EngineState, rounds: usize) {
+        for _ in 0..rounds.max(1) {
+            self.wake(state);
+            if !self.is_running() {
+                return;
+            }
+            for process in &mut self.processes {
+                let Some(run) = process.run.as_mut() else {
+                    continue;
+                };
+                if let Some(outcome) = run.step(state) {
+                    let waiting_on = run.waiting_on();
+                    process.settle(outcome, waiting_on);
+                    if process.has_exploded() {
+                        reclaim(state, process);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Restart waiting processes whose nest now holds a message, and
+    /// stop those whose empty nest no bird can deliver to any more, or
+    /// whose nest is gone. Returns true if any woke or stopped.
+    pub fn wake(&mut self, state: &mut EngineState) -> bool {
+        let mut woke = false;
+        for process in &mut self.processes {
+            let ProcessState::Waiting { nest } = process.state else {
+                continue;
+            };
+            let stopped = match state.widgets.get(&nest) {
+                Some(WidgetItem::Nest(n)) if n.has_messages() => None,
+                Some(WidgetItem::Nest(n)) if n.has_no_senders() => Some(StopReason::NoSenders),
+                Some(WidgetItem::Nest(_)) => continue,
+                _ => Some(StopReason::NestGone),
+            };
+            if let Some(stopped) = stopped {
+                log::info!(
+                    "Process {} stopped waiting on nest {}: {:?}",
+                    process.id,
+                    nest,
+                    stopped
+                );
+                woke = true;
+                set_stop_reason(state, process.lead, stopped);
+                let outcome = RobotOutcome::Ran {
+                    robot: process.lead,
+                    iterations: 0,
+                    stopped,
+                };
+                process.settle(outcome, None);
+                continue;
+            }
+            log::info!("Process {} woke: nest {} has mail", process.id, nest);
+            woke = true;
+            match RobotRun::start(state, process.lead, process.input) {
+                Ok(run) => {
+                    process.run = Some(run);
+                    process.state = ProcessState::Running;
+                    process.outcome = None;
+                }
+                Err(outcome) => process.settle(outcome, None),
+            }
+        }
+        woke
+    }
+
+    /// Drop finished processes from the queue, returning them.
+    pub fn remove_finished(&mut self) -> Vec<Process> {
+        let (finished, live) = std::mem::take(&mut self.processes)
+            .into_iter()
+            .partition(|p| !p.is_live());
+        self.processes = live;
+        finished
+    }
+
+    fn push(&mut self, lead: WidgetId, input: Option<WidgetId>) -> ProcessId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.processes.push(Process {
+            id,
+            lead,
+            input,
+            state: ProcessState::Running,
+            run: None,
+            outcome: None,
+        });
+        id
+    }
+}
+
+/// Remove a bombed process's team and box, noting nests that lost birds.
+fn reclaim(state: &mut EngineState, process: &Process) {
+    let mut doomed = team(state, process.lead);
+    doomed.extend(process.input);
+    let silenced = ops::reclaim(state, &doomed);
+    state.silenced.extend(silenced);
+    log::info!("Process {} ended by a bomb", process.id);
+}
+
+fn set_stop_reason(state: &mut EngineState, robot: WidgetId, reason: StopReason) {
+    if let Some(WidgetItem::Robot(r)) = state.widgets.get_mut(&robot) {
+        r.set_stop_reason(reason);
+    }
+}
@@ -0,0 +1,168 @@
+//! Engine state: the workspace model shared by the UI and robots.
+
+use std::collections::HashMap;
+use tt_rs_core::WidgetId;
+use tt_rs_robot::Action;
+use tt_rs_scales::Scales;
+use tt_rs_state::Position;
+
+use crate::box_state::BoxState;
+use crate::ops::{Flight, Mail};
+use crate::robot_exec::widget_path;
+use crate::widget_item::WidgetItem;
+
+/// The workspace model: every widget, box and where it sits.
+#[derive(Clone)]
+pub struct EngineState {
+    pub widgets: HashMap<WidgetId, WidgetItem>,
+    pub boxes: HashMap<WidgetId, BoxState>,
+    pub positions: HashMap<WidgetId, Position>,
+    /// Widgets sitting in box holes (widget_id -> (box_id, hole)).
+    pub widget_in_box: HashMap<WidgetId, (WidgetId, usize)>,
+    /// Robot currently recording actions, if any.
+    pub training_robot_id: Option<WidgetId>,
+    /// Box the training robot was given; recorded paths are relative to it.
+    pub training_input: Option<WidgetId>,
+    /// Widget the training user is carrying (its PickUp is already recorded).
+    pub training_held: Option<WidgetId>,
+    /// Widgets made while training or during a robot pass, in order;
+    /// `new:K` paths index into it.
+    pub created: Option<Vec<WidgetId>>,
+    /// Most actions a robot may perform in one run.
+    pub step_budget: usize,
+    /// Birds away delivering, by bird ID.
+    pub flights: HashMap<WidgetId, Flight>,
+    /// Messages birds carried off to nests in other houses.
+    pub outbox: Vec<Mail>,
+    /// Set when a bomb goes off, until the house it went off in is torn
+    /// down.
+    pub bombed: bool,
+    /// Nests whose birds were reclaimed; the city checks whether any
+    /// bird is left to deliver to them.
+    pub silenced: Vec<WidgetId>,
+    /// Whether sensors in this workspace report what they sense.
+    pub sensors_on: bool,
+}
+
+/// Default for [`EngineState::step_budget`].
+pub const DEFAULT_STEP_BUDGET: usize = 1000;
+
+impl Default for EngineState {
+    fn default() -> Self {
+        Self {
+            widgets: HashMap::new(),
+            boxes: HashMap::new(),
+            positions: HashMap::new(),
+            widget_in_box: HashMap::new(),
+            training_robot_id: None,
+            training_input: None,
+            training_held: None,
+            created: None,
+            step_budget: DEFAULT_STEP_BUDGET,
+            flights: HashMap::new(),
+            outbox: Vec::new(),
+            bombed: false,
+            silenced: Vec::new(),
+            sensors_on: true,
+        }
+    }
+}
+
+impl EngineState {
+    /// Creates an empty workspace.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a widget at a position and returns its ID.
+    pub fn add_widget(&mut self, item: WidgetItem, pos: Position) -> WidgetId {
+        let id = item.id();
+        self.positions.insert(id, pos);
+        self.widgets.insert(id, item);
+        id
+    }
+
+    /// Adds a box at a position and returns its ID.
+    pub fn add_box(&mut self, b: BoxState, pos: Position) -> WidgetId {
+        let id = b.id();
+        self.positions.insert(id, pos);
+        self.boxes.insert(id, b);
+        id
+    }
+
+    /// Records an action on the robot being trained, if any.
+    pub fn record_action(&mut self, action: Action) {
+        if let Some(rid) = self.training_robot_id {
+            if let Some(WidgetItem::Robot(r)) = self.widgets.get_mut(&rid) {
+                r.record_action(action);
+            }
+        }
+    }
+
+    /// Records picking up a widget for a drag, unless the training
+    /// hand already holds it (e.g. it was just taken out of a hole).
+    pub fn record_pick_up(&mut self, id: WidgetId) {
+        if self.training_robot_id.is_none() || self.training_held == Some(id) {
+            return;
+        }
+        let path = widget_path(self, id);
+        self.record_action(Action::PickUp { path });
+        self.training_held = Some(id);
+    }
+
+    /// Records dropping the held widget at a path, emptying the hand.
+    pub fn record_drop(&mut self, path: String) {
+        self.record_action(Action::Drop { path });
+        self.training_held = None;
+    }
+
+    /// Notes a widget made by an operation, so later actions can find it.
+    pub fn note_created(&mut self, id: WidgetId) {
+        if let Some(created) = self.created.as_mut() {
+            created.push(id);
+        }
+    }
+
+    /// Refreshes any scales in a box from their neighbouring holes.
+    pub fn update_scales_in_box(&mut self, box_id: WidgetId) {
+        let contents = match self.boxes.get(&box_id) {
+            Some(b) => b.contents.clone(),
+            None => return,
+        };
+
+        for (&hole, &wid) in &contents {
+            if let Some(WidgetItem::Scales(s)) = self.widgets.get(&wid) {
+                let updated = update_scales(s, &contents, hole, &self.widgets);
+                self.widgets.insert(wid, WidgetItem::Scales(updated));
+            }
+        }
+    }
+
+    /// Removes a free widget from the workspace.
+    pub fn remove_widget(&mut self, id: WidgetId) -> Option<WidgetItem> {
+        self.positions.remove(&id);
+        self.widgets.remove(&id)
+    }
+}
+
+fn update_scales(
+    scales: &Scales,
+    contents: &HashMap<usize, WidgetId>,
+    hole: usize,
+    widgets: &HashMap<WidgetId, WidgetItem>,
+) -> Scales {
+    let mut s = scales.clone();
+    if hole > 0 {
+        if let Some(&lid) = contents.get(&(hole - 1)) {
+            if let Some(WidgetItem::Number(n)) = widgets.get(&lid) {
+                s.set_left(n.numerator());
+            }
+        }
+    }
+    if let Some(&rid) = contents.get(&(hole + 1)) {
+        if let Some(WidgetItem::Number(n)) = widgets.get(&rid) {
+            s.set_right(n.numerator());
+        }
+    }
+    s
+}
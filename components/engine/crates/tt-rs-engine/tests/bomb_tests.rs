@@ -0,0 +1,182 @@
+//! Native tests for bombs: ending processes and reclaiming their widgets.
+
+use tt_rs_bird::Bird;
+use tt_rs_bomb::Bomb;
+use tt_rs_box::ToonBox;
+use tt_rs_core::WidgetId;
+use tt_rs_engine::city::{City, Loaded};
+use tt_rs_engine::robot_exec::{self, RobotOutcome};
+use tt_rs_engine::scheduler::{ProcessState, Scheduler};
+use tt_rs_engine::{ops, BoxState, EngineState, Position, WidgetItem};
+use tt_rs_house::House;
+use tt_rs_nest::{Channel, Nest};
+use tt_rs_number::Number;
+use tt_rs_robot::{Action, Robot, StopReason};
+use tt_rs_truck::Truck;
+
+fn origin() -> Position {
+    Position::new(0.0, 0.0)
+}
+
+/// A robot that sets off the bomb in hole 1 of any two-hole box.
+fn bomber_robot(state: &mut EngineState) -> WidgetId {
+    let mut robot = Robot::new();
+    robot.set_pattern(ToonBox::erased_with_size(2));
+    robot.set_actions(vec![Action::SetOffBomb {
+        path: "input/hole:1".to_string(),
+    }]);
+    state.add_widget(WidgetItem::Robot(robot), origin())
+}
+
+/// A two-hole box with `first` in hole 0 and a bomb in hole 1.
+fn bomb_box(state: &mut EngineState, first: WidgetId) -> (WidgetId, WidgetId) {
+    let box_id = state.add_box(BoxState::new(2), origin());
+    let bomb = state.add_widget(WidgetItem::Bomb(Bomb::new()), origin());
+    ops::drop_in_hole(state, first, box_id, 0, origin());
+    ops::drop_in_hole(state, bomb, box_id, 1, origin());
+    (box_id, bomb)
+}
+
+fn nest_of(state: &EngineState, id: WidgetId) -> &Nest {
+    match state.widgets.get(&id) {
+        Some(WidgetItem::Nest(n)) => n,
+        _ => panic!("not a nest"),
+    }
+}
+
+#[test]
+fn test_bombed_process_reclaims_its_widgets() {
+    let mut state = EngineState::new();
+    let n = state.add_widget(WidgetItem::Number(Number::new(7)), origin());
+    let (box_id, bomb) = bomb_box(&mut state, n);
+    let robot = bomber_robot(&mut state);
+    let bystander = state.add_widget(WidgetItem::Number(Number::new(1)), origin());
+
+    let mut scheduler = Scheduler::new();
+    let pid = scheduler.spawn(&mut state, robot, Some(box_id));
+    scheduler.tick(&mut state, 1);
+
+    let process = scheduler.process(pid).expect("process");
+    assert_eq!(process.state(), ProcessState::Finished);
+    assert!(matches!(
+        process.outcome(),
+        Some(RobotOutcome::Ran {
+            stopped: StopReason::Exploded,
+            ..
+        })
+    ));
+    assert!(state.bombed);
+    for id in [robot, box_id, n, bomb] {
+        assert!(!state.widgets.contains_key(&id));
+        assert!(!state.boxes.contains_key(&id));
+        assert!(!state.positions.contains_key(&id));
+        assert!(!state.widget_in_box.contains_key(&id));
+    }
+    assert!(state.widgets.contains_key(&bystander));
+    assert!(state.positions.contains_key(&bystander));
+}
+
+#[test]
+fn test_training_only_records_setting_off_a_bomb() {
+    let mut state = EngineState::new();
+    let robot = state.add_widget(WidgetItem::Robot(Robot::new()), origin());
+    let bomb = state.add_widget(WidgetItem::Bomb(Bomb::new()), origin());
+    robot_exec::start_training(&mut state, robot);
+
+    assert!(ops::set_off_bomb(&mut state, bomb));
+    assert!(!state.bombed);
+    assert!(state.widgets.contains_key(&bomb));
+    match state.widgets.get(&robot) {
+        Some(WidgetItem::Robot(r)) => {
+            assert!(matches!(r.actions(), [Action::SetOffBomb { .. }]))
+        }
+        _ => panic!("robot missing"),
+    }
+}
+
+#[test]
+fn test_bomb_tears_down_the_house_and_its_nest_queues() {
+    let mut engine = EngineState::new();
+    let mut scheduler = Scheduler::new();
+    let house = engine.add_widget(WidgetItem::House(House::new()), origin());
+    let mut city = City::default();
+
+    // Inside: a nest with mail waiting, and a bomb the user sets off
+    assert!(city.enter(&mut engine, &mut scheduler, house));
+    let mut nest = Nest::new();
+    nest.receive(Box::new(Number::new(3)));
+    let nest_id = engine.add_widget(WidgetItem::Nest(nest), origin());
+    let bomb = engine.add_widget(WidgetItem::Bomb(Bomb::new()), origin());
+    assert!(ops::set_off_bomb(&mut engine, bomb));
+
+    assert!(city.detonate(&mut engine, &mut scheduler));
+    assert_eq!(city.inside(), None);
+    assert!(!engine.bombed);
+    assert!(!engine.widgets.contains_key(&house));
+    assert!(!engine.positions.contains_key(&house));
+    assert!(city.world(house).is_none());
+    assert!(!engine.widgets.contains_key(&nest_id));
+
+    // Mail for the nest that went is lost
+    let bird = engine.add_widget(
+        WidgetItem::Bird(Bird::with_nest(nest_id, Channel::default())),
+        origin(),
+    );
+    let message = engine.add_widget(WidgetItem::Number(Number::new(4)), origin());
+    assert!(ops::give_to_bird(&mut engine, message, bird));
+    city.deliver_mail(&mut engine, &mut scheduler);
+    assert!(engine.outbox.is_empty());
+}
+
+#[test]
+fn test_nest_without_birds_stops_its_waiting_robot() {
+    let mut engine = EngineState::new();
+    let mut scheduler = Scheduler::new();
+    let nest = engine.add_widget(WidgetItem::Nest(Nest::new()), origin());
+    let bird = engine.add_widget(
+        WidgetItem::Bird(Bird::with_nest(nest, Channel::default())),
+        origin(),
+    );
+
+    // Outdoors: a robot waiting for mail in the nest
+    let waiting_box = engine.add_box(BoxState::new(1), origin());
+    ops::drop_in_hole(&mut engine, nest, waiting_box, 0, origin());
+    let mut reader = Robot::new();
+    let mut pattern = ToonBox::new(1);
+    pattern.set_hole(0, Box::new(Number::erased()));
+    reader.set_pattern(pattern);
+    reader.set_actions(vec![Action::TakeFromNest {
+        path: "input/hole:0".to_string(),
+    }]);
+    let reader = engine.add_widget(WidgetItem::Robot(reader), origin());
+    let pid = scheduler.spawn(&mut engine, reader, Some(waiting_box));
+    assert!(matches!(
+        scheduler.process(pid).map(|p| p.state()),
+        Some(ProcessState::Waiting { .. })
+    ));
+
+    // The only bird drives off with a robot that blows up its house
+    let truck = engine.add_widget(WidgetItem::Truck(Truck::new()), origin());
+    let (box_id, _) = bomb_box(&mut engine, bird);
+    let bomber = bomber_robot(&mut engine);
+    let mut city = City::default();
+    city.load_truck(&mut engine, box_id, truck);
+    let Some(Loaded::Built(house)) = city.load_truck(&mut engine, bomber, truck) else {
+        panic!("a full truck builds a house");
+    };
+    city.tick(1);
+    assert!(city.world(house).is_some_and(|w| w.engine.bombed));
+
+    assert!(city.detonate(&mut engine, &mut scheduler));
+    assert!(!engine.widgets.contains_key(&house));
+    assert!(nest_of(&engine, nest).has_no_senders());
+    let process = scheduler.process(pid).expect("process");
+    assert_eq!(process.state(), ProcessState::Finished);
+    assert!(matches!(
+        process.outcome(),
+        Some(RobotOutcome::Ran {
+            stopped: StopReason::NoSenders,
+            ..
+        })
+    ));
+}
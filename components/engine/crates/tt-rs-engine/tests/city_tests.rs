@@ -0,0 +1,238 @@
+//! Native tests for houses and the city.
+
+use tt_rs_bird::Bird;
+use tt_rs_box::ToonBox;
+use tt_rs_core::{Widget, WidgetId};
+use tt_rs_engine::city::{City, Loaded};
+use tt_rs_engine::scheduler::{ProcessState, Scheduler};
+use tt_rs_engine::{ops, BoxState, EngineState, Position, WidgetItem};
+use tt_rs_house::{House, HouseStatus};
+use tt_rs_nest::{Channel, Nest};
+use tt_rs_number::Number;
+use tt_rs_robot::{Action, Robot};
+use tt_rs_truck::Truck;
+
+fn origin() -> Position {
+    Position::new(0.0, 0.0)
+}
+
+fn house_status(state: &EngineState, id: WidgetId) -> HouseStatus {
+    match state.widgets.get(&id) {
+        Some(WidgetItem::House(h)) => h.status(),
+        _ => panic!("not a house"),
+    }
+}
+
+#[test]
+fn test_enter_and_leave_swap_worlds() {
+    let mut engine = EngineState::new();
+    let mut scheduler = Scheduler::new();
+    let house = engine.add_widget(WidgetItem::House(House::new()), origin());
+    let outside = engine.add_widget(WidgetItem::Number(Number::new(1)), origin());
+    let mut city = City::default();
+
+    assert!(city.enter(&mut engine, &mut scheduler, house));
+    assert_eq!(city.inside(), Some(house));
+    assert_eq!(city.path(), vec![house]);
+    assert!(!engine.widgets.contains_key(&outside));
+    let inside = engine.add_widget(WidgetItem::Number(Number::new(2)), origin());
+
+    assert!(city.leave(&mut engine, &mut scheduler));
+    assert_eq!(city.inside(), None);
+    assert!(engine.widgets.contains_key(&outside));
+    assert!(city
+        .world(house)
+        .is_some_and(|w| w.engine.widgets.contains_key(&inside)));
+    assert!(!city.leave(&mut engine, &mut scheduler));
+}
+
+#[test]
+fn test_carry_in_moves_a_box_and_its_contents() {
+    let mut engine = EngineState::new();
+    let house = engine.add_widget(WidgetItem::House(House::new()), origin());
+    let box_id = engine.add_box(BoxState::new(1), origin());
+    let n = engine.add_widget(WidgetItem::Number(Number::new(3)), origin());
+    ops::drop_in_hole(&mut engine, n, box_id, 0, origin());
+    let mut city = City::default();
+
+    assert!(city.carry_in(&mut engine, box_id, house));
+    assert!(!engine.boxes.contains_key(&box_id));
+    assert!(!engine.widgets.contains_key(&n));
+    assert!(!engine.widget_in_box.contains_key(&n));
+
+    let world = &city.world(house).expect("house world").engine;
+    assert!(world.boxes.contains_key(&box_id));
+    assert!(world.positions.contains_key(&box_id));
+    assert_eq!(world.widget_in_box.get(&n), Some(&(box_id, 0)));
+    assert!(!city.carry_in(&mut engine, house, house));
+}
+
+#[test]
+fn test_bird_carries_mail_into_a_house() {
+    let mut engine = EngineState::new();
+    let mut scheduler = Scheduler::new();
+    let house = engine.add_widget(WidgetItem::House(House::new()), origin());
+    let nest = Nest::new();
+    let nest_id = nest.id();
+    let bird = engine.add_widget(
+        WidgetItem::Bird(Bird::with_nest(nest_id, Channel::default())),
+        origin(),
+    );
+
+    // Inside: a box holding the nest, and a robot that takes its messages
+    let mut city = City::default();
+    assert!(city.enter(&mut engine, &mut scheduler, house));
+    let box_id = engine.add_box(BoxState::new(1), origin());
+    let nest_widget = engine.add_widget(WidgetItem::Nest(nest), origin());
+    ops::drop_in_hole(&mut engine, nest_widget, box_id, 0, origin());
+    let mut robot = Robot::new();
+    let mut pattern = ToonBox::new(1);
+    pattern.set_hole(0, Box::new(Number::erased()));
+    robot.set_pattern(pattern);
+    robot.set_actions(vec![Action::TakeFromNest {
+        path: "input/hole:0".to_string(),
+    }]);
+    let rid = engine.add_widget(WidgetItem::Robot(robot), origin());
+    let pid = scheduler.spawn(&mut engine, rid, Some(box_id));
+    assert!(city.leave(&mut engine, &mut scheduler));
+    city.refresh(&mut engine, &scheduler);
+    assert_eq!(house_status(&engine, house), HouseStatus::Waiting);
+
+    let message = engine.add_widget(WidgetItem::Number(Number::new(5)), origin());
+    assert!(ops::give_to_bird(&mut engine, message, bird));
+    assert!(engine.outbox.is_empty());
+    while ops::fly_birds(&mut engine) {}
+    assert_eq!(engine.outbox.len(), 1);
+    city.deliver_mail(&mut engine, &mut scheduler);
+    assert!(engine.outbox.is_empty());
+
+    let world = city.world(house).expect("house world");
+    assert_eq!(
+        world.scheduler.process(pid).map(|p| p.state()),
+        Some(ProcessState::Running)
+    );
+    assert!(city.is_running());
+    assert_eq!(city.running_houses(), vec![house]);
+    city.refresh(&mut engine, &scheduler);
+    assert_eq!(house_status(&engine, house), HouseStatus::Running);
+
+    city.tick(1);
+    let world = city.world(house).expect("house world");
+    assert!(world
+        .engine
+        .widgets
+        .values()
+        .any(|w| matches!(w, WidgetItem::Number(n) if n.numerator() == 5)));
+}
+
+/// A robot that sends whatever is in hole 0 off with the bird in hole 1.
+fn sender_robot(state: &mut EngineState) -> WidgetId {
+    let mut robot = Robot::new();
+    robot.set_pattern(ToonBox::erased_with_size(2));
+    robot.set_actions(vec![
+        Action::PickUp {
+            path: "input/hole:0".to_string(),
+        },
+        Action::Drop {
+            path: "input/hole:1".to_string(),
+        },
+    ]);
+    state.add_widget(WidgetItem::Robot(robot), origin())
+}
+
+#[test]
+fn test_truck_builds_a_house_where_the_robot_works() {
+    let mut engine = EngineState::new();
+    let truck = engine.add_widget(WidgetItem::Truck(Truck::new()), Position::new(40.0, 50.0));
+    let robot = sender_robot(&mut engine);
+    let box_id = engine.add_box(BoxState::new(2), origin());
+    let n = engine.add_widget(WidgetItem::Number(Number::new(9)), origin());
+    ops::drop_in_hole(&mut engine, n, box_id, 0, origin());
+    let mut city = City::default();
+
+    assert_eq!(
+        city.load_truck(&mut engine, robot, truck),
+        Some(Loaded::Waiting)
+    );
+    assert!(!engine.widgets.contains_key(&robot));
+    assert!(matches!(
+        engine.widgets.get(&truck),
+        Some(WidgetItem::Truck(t)) if t.robot() == Some(robot) && t.cargo().is_none()
+    ));
+    // A second robot doesn't fit
+    let other = sender_robot(&mut engine);
+    assert_eq!(city.load_truck(&mut engine, other, truck), None);
+    assert!(engine.widgets.contains_key(&other));
+
+    let Some(Loaded::Built(house)) = city.load_truck(&mut engine, box_id, truck) else {
+        panic!("a full truck builds a house");
+    };
+    assert!(!engine.widgets.contains_key(&truck));
+    assert!(!engine.boxes.contains_key(&box_id));
+    assert_eq!(
+        engine.positions.get(&house),
+        Some(&Position::new(40.0, 50.0))
+    );
+    let world = city.world(house).expect("house world");
+    assert!(world.scheduler.live_process_of(robot).is_some());
+    assert_eq!(world.engine.widget_in_box.get(&n), Some(&(box_id, 0)));
+    assert_eq!(city.running_houses(), vec![house]);
+}
+
+#[test]
+fn test_spawned_process_reports_back_through_its_bird() {
+    let mut engine = EngineState::new();
+    let mut scheduler = Scheduler::new();
+    let truck = engine.add_widget(WidgetItem::Truck(Truck::new()), origin());
+    let nest = engine.add_widget(WidgetItem::Nest(Nest::new()), origin());
+    let bird = engine.add_widget(
+        WidgetItem::Bird(Bird::with_nest(nest, Channel::default())),
+        origin(),
+    );
+    let box_id = engine.add_box(BoxState::new(2), origin());
+    let n = engine.add_widget(WidgetItem::Number(Number::new(4)), origin());
+    ops::drop_in_hole(&mut engine, n, box_id, 0, origin());
+    ops::drop_in_hole(&mut engine, bird, box_id, 1, origin());
+    let robot = sender_robot(&mut engine);
+    let mut city = City::default();
+
+    city.load_truck(&mut engine, box_id, truck);
+    let Some(Loaded::Built(house)) = city.load_truck(&mut engine, robot, truck) else {
+        panic!("a full truck builds a house");
+    };
+    city.tick(4);
+    assert!(city.has_flights());
+    while city.fly_birds() {}
+    city.deliver_mail(&mut engine, &mut scheduler);
+
+    match engine.widgets.get(&nest) {
+        Some(WidgetItem::Nest(n)) => assert_eq!(n.message_count(), 1),
+        _ => panic!("nest missing"),
+    }
+    let world = city.world(house).expect("house world");
+    assert!(!world.engine.widgets.contains_key(&n));
+}
+
+#[test]
+fn test_removed_house_is_forgotten() {
+    let mut engine = EngineState::new();
+    let scheduler = Scheduler::new();
+    let house = engine.add_widget(WidgetItem::House(House::new()), origin());
+    let mut city = City::default();
+    city.build(house);
+    assert!(city.world(house).is_some());
+
+    ops::vacuum_widget(&mut engine, house);
+    city.refresh(&mut engine, &scheduler);
+    assert!(city.world(house).is_none());
+}
+
+#[test]
+fn test_ticks_and_bird_steps_are_counted_apart() {
+    let mut city = City::default();
+    city.tick(1);
+    city.fly_birds();
+    city.fly_birds();
+    assert_eq!(city.ticks(), 1);
+    assert_eq!(city.flight_steps(), 2);
+}
@@ -0,0 +1,140 @@
+//! Native tests for structural matching against workspace contents.
+
+use tt_rs_box::ToonBox;
+use tt_rs_core::{MatchResult, Widget};
+use tt_rs_engine::{matching, ops, BoxState, EngineState, Position, WidgetItem};
+use tt_rs_nest::Nest;
+use tt_rs_number::Number;
+use tt_rs_text::Text;
+
+fn origin() -> Position {
+    Position::new(0.0, 0.0)
+}
+
+fn box_with(state: &mut EngineState, items: Vec<Option<WidgetItem>>) -> tt_rs_core::WidgetId {
+    let box_id = state.add_box(BoxState::new(items.len()), origin());
+    for (hole, item) in items.into_iter().enumerate() {
+        if let Some(item) = item {
+            let id = state.add_widget(item, origin());
+            ops::drop_in_hole(state, id, box_id, hole, origin());
+        }
+    }
+    box_id
+}
+
+#[test]
+fn test_box_pattern_matches_workspace_box() {
+    let mut state = EngineState::new();
+    let box_id = box_with(
+        &mut state,
+        vec![
+            Some(WidgetItem::Number(Number::new(1))),
+            Some(WidgetItem::Text(Text::new("hi"))),
+        ],
+    );
+
+    let mut pattern = ToonBox::new(2);
+    pattern.set_hole(0, Box::new(Number::new(1)));
+    assert!(matching::match_pattern(&state, &pattern, box_id).is_match());
+
+    pattern.set_hole(1, Box::new(Text::new("bye")));
+    assert_eq!(
+        matching::match_pattern(&state, &pattern, box_id),
+        MatchResult::NoMatch
+    );
+}
+
+#[test]
+fn test_size_mismatch_fails() {
+    let mut state = EngineState::new();
+    let box_id = box_with(&mut state, vec![None, None, None]);
+
+    assert_eq!(
+        matching::match_pattern(&state, &ToonBox::new(2), box_id),
+        MatchResult::NoMatch
+    );
+    assert!(matching::match_pattern(&state, &ToonBox::erased(), box_id).is_match());
+}
+
+#[test]
+fn test_snapshot_keeps_widget_ids() {
+    let mut state = EngineState::new();
+    let n = state.add_widget(WidgetItem::Number(Number::new(4)), origin());
+    let snap = matching::snapshot(&state, n).unwrap();
+    assert_eq!(snap.id(), n);
+    assert!(matching::match_pattern(&state, &Number::erased(), n).is_match());
+}
+
+#[test]
+fn test_empty_nest_in_box_suspends() {
+    let mut state = EngineState::new();
+    let nest = Nest::new();
+    let nest_id = nest.id();
+    let box_id = box_with(&mut state, vec![Some(WidgetItem::Nest(nest))]);
+
+    let mut pattern = ToonBox::new(1);
+    pattern.set_hole(0, Box::new(Number::erased()));
+    assert_eq!(
+        matching::match_pattern(&state, &pattern, box_id),
+        MatchResult::Suspended {
+            waiting_on: nest_id
+        }
+    );
+}
+
+#[test]
+fn test_bindings_refer_to_workspace_widgets() {
+    let mut state = EngineState::new();
+    let box_id = box_with(&mut state, vec![Some(WidgetItem::Number(Number::new(8)))]);
+    let number_id = state.boxes[&box_id].widget_in_hole(0).unwrap();
+
+    let result = matching::match_pattern(&state, &ToonBox::new(1), box_id);
+    let bindings = result.bindings().expect("should match");
+    assert_eq!(bindings.get(""), Some(box_id));
+    assert_eq!(bindings.get("hole:0"), Some(number_id));
+}
+
+#[test]
+fn test_learned_pattern_sees_top_message_of_nest() {
+    let mut state = EngineState::new();
+    let mut nest = Nest::new();
+    nest.receive(Box::new(Number::new(5)));
+    let empty = Nest::new();
+    let box_id = box_with(
+        &mut state,
+        vec![Some(WidgetItem::Nest(nest)), Some(WidgetItem::Nest(empty))],
+    );
+
+    let pattern = matching::pattern_box(&state, box_id).unwrap();
+    let hole = |i: usize| pattern.hole(i).and_then(|h| h.content()).unwrap();
+    assert_eq!(hole(0).description(), "number +5");
+    assert_eq!(hole(1).type_name(), "nest");
+    assert!(matching::match_pattern(&state, &pattern, box_id).is_match());
+}
+
+#[test]
+fn test_learned_pattern_sees_top_message_of_nest_in_inner_box() {
+    let mut state = EngineState::new();
+    let mut nest = Nest::new();
+    nest.receive(Box::new(Number::new(5)));
+    let inner = box_with(&mut state, vec![Some(WidgetItem::Nest(nest))]);
+    let outer = box_with(&mut state, vec![None]);
+    let middle = box_with(&mut state, vec![None]);
+    // outer > middle > inner > nest
+    for (child, parent) in [(inner, middle), (middle, outer)] {
+        if let Some(b) = state.boxes.get_mut(&parent) {
+            b.place_in_hole(0, child);
+        }
+        state.widget_in_box.insert(child, (parent, 0));
+    }
+
+    let pattern = matching::pattern_box(&state, outer).unwrap();
+    let inner_box = |b: &ToonBox| -> ToonBox {
+        let content = b.hole(0).and_then(|h| h.content()).unwrap();
+        content.as_any().downcast_ref::<ToonBox>().unwrap().clone()
+    };
+    let innermost = inner_box(&inner_box(&pattern));
+    let top = innermost.hole(0).and_then(|h| h.content()).unwrap();
+    assert_eq!(top.description(), "number +5");
+    assert!(matching::match_pattern(&state, &pattern, outer).is_match());
+}
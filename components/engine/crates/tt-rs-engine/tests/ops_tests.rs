@@ -0,0 +1,426 @@
+//! Native tests for workspace operations.
+
+use tt_rs_bird::{Bird, BirdState};
+use tt_rs_core::Widget;
+use tt_rs_engine::ops::FLIGHT_STEPS;
+use tt_rs_engine::{ops, BoxState, EngineState, Position, WidgetItem};
+use tt_rs_nest::{Channel, Nest};
+use tt_rs_number::Number;
+
+fn origin() -> Position {
+    Position::new(0.0, 0.0)
+}
+
+fn add_number(state: &mut EngineState, n: i64) -> tt_rs_core::WidgetId {
+    state.add_widget(WidgetItem::Number(Number::new(n)), origin())
+}
+
+fn number_value(state: &EngineState, id: tt_rs_core::WidgetId) -> Option<i64> {
+    match state.widgets.get(&id) {
+        Some(WidgetItem::Number(n)) => Some(n.numerator()),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_drop_in_hole_moves_widget_into_box() {
+    let mut state = EngineState::new();
+    let box_id = state.add_box(BoxState::new(2), origin());
+    let n = add_number(&mut state, 5);
+
+    assert!(ops::drop_in_hole(&mut state, n, box_id, 1, origin()));
+    assert_eq!(state.boxes[&box_id].widget_in_hole(1), Some(n));
+    assert_eq!(state.widget_in_box.get(&n), Some(&(box_id, 1)));
+    assert!(!state.positions.contains_key(&n));
+}
+
+#[test]
+fn test_drop_in_hole_ejects_previous_occupant() {
+    let mut state = EngineState::new();
+    let box_id = state.add_box(BoxState::new(1), origin());
+    let first = add_number(&mut state, 1);
+    let second = add_number(&mut state, 2);
+
+    ops::drop_in_hole(&mut state, first, box_id, 0, origin());
+    ops::drop_in_hole(&mut state, second, box_id, 0, Position::new(9.0, 9.0));
+
+    assert_eq!(state.boxes[&box_id].widget_in_hole(0), Some(second));
+    assert!(!state.widget_in_box.contains_key(&first));
+    assert_eq!(state.positions.get(&first), Some(&Position::new(9.0, 9.0)));
+}
+
+#[test]
+fn test_apply_number_consumes_dropped() {
+    let mut state = EngineState::new();
+    let target = add_number(&mut state, 3);
+    let dropped = add_number(&mut state, 4);
+
+    assert!(ops::apply_number(&mut state, dropped, target));
+    assert_eq!(number_value(&state, target), Some(7));
+    assert!(!state.widgets.contains_key(&dropped));
+}
+
+#[test]
+fn test_split_and_join_boxes() {
+    let mut state = EngineState::new();
+    let box_id = state.add_box(BoxState::new(3), origin());
+    let n = add_number(&mut state, 9);
+    ops::drop_in_hole(&mut state, n, box_id, 2, origin());
+    let two = add_number(&mut state, 2);
+
+    assert!(ops::split_box(&mut state, box_id, two, origin()));
+    assert_eq!(state.boxes.len(), 2);
+    // The box keeps the left half
+    assert_eq!(state.boxes[&box_id].num_holes, 2);
+    let (bin, _) = state.widget_in_box[&n];
+    assert_eq!(state.boxes[&bin].num_holes, 1);
+
+    let ids: Vec<_> = state.boxes.keys().copied().collect();
+    let other = ids.into_iter().find(|id| *id != bin).unwrap();
+    assert!(ops::join_boxes(&mut state, bin, other));
+    assert_eq!(state.boxes.len(), 1);
+    let (joined, hole) = state.widget_in_box[&n];
+    assert_eq!(state.boxes[&joined].num_holes, 3);
+    assert_eq!(hole, 2);
+}
+
+#[test]
+fn test_split_on_zero_copies_contents() {
+    let mut state = EngineState::new();
+    let box_id = state.add_box(BoxState::new(1), origin());
+    let n = add_number(&mut state, 6);
+    ops::drop_in_hole(&mut state, n, box_id, 0, origin());
+    let zero = add_number(&mut state, 0);
+
+    assert!(ops::split_box(&mut state, box_id, zero, origin()));
+    assert_eq!(state.boxes.len(), 2);
+    assert!(state.boxes.contains_key(&box_id));
+    assert_eq!(state.widget_in_box.len(), 2);
+}
+
+fn bird_state(state: &EngineState, id: tt_rs_core::WidgetId) -> BirdState {
+    match state.widgets.get(&id) {
+        Some(WidgetItem::Bird(b)) => b.state(),
+        _ => panic!("not a bird"),
+    }
+}
+
+fn nest_count(state: &EngineState, id: tt_rs_core::WidgetId) -> usize {
+    match state.widgets.get(&id) {
+        Some(WidgetItem::Nest(n)) => n.message_count(),
+        _ => panic!("not a nest"),
+    }
+}
+
+#[test]
+fn test_give_to_bird_delivers_to_nest() {
+    let mut state = EngineState::new();
+    let nest = Nest::new();
+    let nest_id = nest.id();
+    state.add_widget(WidgetItem::Nest(nest), origin());
+    let bird_id = state.add_widget(
+        WidgetItem::Bird(Bird::with_nest(nest_id, Channel::default())),
+        origin(),
+    );
+    let n = add_number(&mut state, 8);
+
+    assert!(ops::give_to_bird(&mut state, n, bird_id));
+    assert!(!state.widgets.contains_key(&n));
+    assert_eq!(bird_state(&state, bird_id), BirdState::Flying);
+    assert_eq!(nest_count(&state, nest_id), 0);
+
+    // The message arrives with the bird, which then flies back
+    for _ in 1..FLIGHT_STEPS {
+        ops::fly_birds(&mut state);
+    }
+    assert_eq!(nest_count(&state, nest_id), 0);
+    ops::fly_birds(&mut state);
+    assert_eq!(nest_count(&state, nest_id), 1);
+    assert_eq!(bird_state(&state, bird_id), BirdState::Returning);
+    while ops::fly_birds(&mut state) {}
+    assert_eq!(bird_state(&state, bird_id), BirdState::Idle);
+
+    let taken = ops::take_from_nest(&mut state, nest_id, origin()).unwrap();
+    assert_eq!(number_value(&state, taken), Some(8));
+    assert!(ops::take_from_nest(&mut state, nest_id, origin()).is_none());
+}
+
+#[test]
+fn test_boxes_go_by_bird_with_everything_in_them() {
+    let mut state = EngineState::new();
+    let nest = ops::hatch_nest(&mut state, Nest::new(), origin());
+    let bird = birds_of(&state, nest)[0];
+    let outer = state.add_box(BoxState::new(2), origin());
+    let inner = state.add_box(BoxState::new(1), origin());
+    let (eight, nine) = (add_number(&mut state, 8), add_number(&mut state, 9));
+    ops::drop_in_hole(&mut state, eight, outer, 0, origin());
+    ops::drop_in_hole(&mut state, nine, inner, 0, origin());
+    if let Some(b) = state.boxes.get_mut(&outer) {
+        b.place_in_hole(1, inner);
+    }
+    state.widget_in_box.insert(inner, (outer, 1));
+
+    assert!(ops::give_to_bird(&mut state, outer, bird));
+    for id in [outer, inner] {
+        assert!(!state.boxes.contains_key(&id));
+    }
+    for id in [eight, nine] {
+        assert!(!state.widgets.contains_key(&id));
+    }
+    while ops::fly_birds(&mut state) {}
+
+    let taken = ops::take_from_nest(&mut state, nest, origin()).unwrap();
+    let hole = |id, i| state.boxes[&id].widget_in_hole(i).unwrap();
+    assert_eq!(number_value(&state, hole(taken, 0)), Some(8));
+    assert_eq!(number_value(&state, hole(hole(taken, 1), 0)), Some(9));
+}
+
+#[test]
+fn test_boxes_holding_nests_or_their_carrier_stay_put() {
+    let mut state = EngineState::new();
+    let nest = ops::hatch_nest(&mut state, Nest::new(), origin());
+    let bird = birds_of(&state, nest)[0];
+    let box_id = state.add_box(BoxState::new(1), origin());
+    ops::drop_in_hole(&mut state, bird, box_id, 0, origin());
+
+    // The bird is in the box, so it can't carry it
+    assert!(!ops::give_to_bird(&mut state, box_id, bird));
+    assert_eq!(state.boxes[&box_id].widget_in_hole(0), Some(bird));
+
+    // A nest can't be delivered, as its copy would share its channel
+    let other = ops::hatch_nest(&mut state, Nest::new(), origin());
+    let other_bird = birds_of(&state, other)[0];
+    let with_nest = state.add_box(BoxState::new(1), origin());
+    ops::drop_in_hole(&mut state, nest, with_nest, 0, origin());
+    assert!(!ops::give_to_bird(&mut state, with_nest, other_bird));
+    assert!(!ops::give_to_nest(&mut state, with_nest, other));
+    assert_eq!(state.boxes[&with_nest].widget_in_hole(0), Some(nest));
+    assert_eq!(nest_count(&state, other), 0);
+}
+
+#[test]
+fn test_a_bird_in_a_box_carries_the_reply_to_a_request() {
+    let mut state = EngineState::new();
+    let client = ops::hatch_nest(&mut state, Nest::new(), origin());
+    let reply_bird = birds_of(&state, client)[0];
+    let server = ops::hatch_nest(&mut state, Nest::new(), origin());
+    let server_bird = birds_of(&state, server)[0];
+
+    // The request: a question and the bird to send the answer back with
+    let request = state.add_box(BoxState::new(2), origin());
+    let question = add_number(&mut state, 20);
+    ops::drop_in_hole(&mut state, question, request, 0, origin());
+    ops::drop_in_hole(&mut state, reply_bird, request, 1, origin());
+    assert!(ops::give_to_bird(&mut state, request, server_bird));
+    assert!(!state.widgets.contains_key(&reply_bird));
+    while ops::fly_birds(&mut state) {}
+
+    let received = ops::take_from_nest(&mut state, server, origin()).unwrap();
+    let bird = state.boxes[&received].widget_in_hole(1).unwrap();
+    assert_eq!(birds_of(&state, client), [bird]);
+
+    let answer = add_number(&mut state, 22);
+    assert!(ops::give_in_hole(&mut state, answer, received, 1));
+    while ops::fly_birds(&mut state) {}
+    let reply = ops::take_from_nest(&mut state, client, origin()).unwrap();
+    assert_eq!(number_value(&state, reply), Some(22));
+}
+
+#[test]
+fn test_bird_flies_to_nest_and_queues_messages_while_away() {
+    let mut state = EngineState::new();
+    let nest_id = state.add_widget(WidgetItem::Nest(Nest::new()), Position::new(100.0, 0.0));
+    let bird_id = state.add_widget(
+        WidgetItem::Bird(Bird::with_nest(nest_id, Channel::default())),
+        origin(),
+    );
+    let (a, b) = (add_number(&mut state, 1), add_number(&mut state, 2));
+    assert!(ops::give_to_bird(&mut state, a, bird_id));
+    assert!(ops::give_to_bird(&mut state, b, bird_id));
+    assert!(matches!(
+        state.widgets.get(&bird_id),
+        Some(WidgetItem::Bird(bird)) if bird.carrying().is_some() && bird.queued_count() == 1
+    ));
+
+    for _ in 0..FLIGHT_STEPS / 2 {
+        ops::fly_birds(&mut state);
+    }
+    let x = state
+        .positions
+        .get(&bird_id)
+        .map(|p| p.x)
+        .unwrap_or_default();
+    assert!(x > 0.0 && x < 100.0);
+
+    while ops::fly_birds(&mut state) {}
+    assert_eq!(nest_count(&state, nest_id), 2);
+    assert_eq!(bird_state(&state, bird_id), BirdState::Idle);
+    assert_eq!(state.positions.get(&bird_id), Some(&origin()));
+    let first = ops::take_from_nest(&mut state, nest_id, origin()).unwrap();
+    assert_eq!(number_value(&state, first), Some(1));
+}
+
+#[test]
+fn test_vacuum_and_wand() {
+    let mut state = EngineState::new();
+    let box_id = state.add_box(BoxState::new(1), origin());
+    let n = add_number(&mut state, 1);
+    ops::drop_in_hole(&mut state, n, box_id, 0, origin());
+    assert!(ops::vacuum_hole(&mut state, box_id, 0));
+    assert!(!state.widgets.contains_key(&n));
+
+    let m = add_number(&mut state, 2);
+    let copy = ops::wand_copy_widget(&mut state, m).unwrap();
+    assert_eq!(number_value(&state, copy), Some(2));
+    assert!(ops::vacuum_widget(&mut state, m));
+    assert!(!state.widgets.contains_key(&m));
+}
+
+#[test]
+fn test_vacuum_hole_takes_a_box_with_everything_in_it() {
+    let mut state = EngineState::new();
+    let outer = state.add_box(BoxState::new(1), origin());
+    let inner = state.add_box(BoxState::new(2), origin());
+    let n = add_number(&mut state, 5);
+    let nest_id = ops::hatch_nest(&mut state, Nest::new(), origin());
+    let bird_id = birds_of(&state, nest_id)[0];
+    ops::drop_in_hole(&mut state, n, inner, 0, origin());
+    ops::drop_in_hole(&mut state, bird_id, inner, 1, origin());
+    if let Some(b) = state.boxes.get_mut(&outer) {
+        b.place_in_hole(0, inner);
+    }
+    state.widget_in_box.insert(inner, (outer, 0));
+
+    assert!(ops::vacuum_hole(&mut state, outer, 0));
+    assert_eq!(state.boxes.len(), 1);
+    assert!(!state.widgets.contains_key(&n));
+    assert!(!state.widgets.contains_key(&bird_id));
+    assert!(state.widget_in_box.is_empty());
+    // No bird is left to bring anything to the nest
+    assert!(state.silenced.contains(&nest_id));
+}
+
+fn birds_of(state: &EngineState, nest: tt_rs_core::WidgetId) -> Vec<tt_rs_core::WidgetId> {
+    state
+        .widgets
+        .values()
+        .filter_map(|w| match w {
+            WidgetItem::Bird(b) if b.nest_id() == Some(nest) => Some(b.id()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_wand_copies_birds_and_nests_as_toontalk_does() {
+    let mut state = EngineState::new();
+    let nest_id = ops::hatch_nest(&mut state, Nest::new(), origin());
+    let bird_id = birds_of(&state, nest_id)[0];
+
+    // A copied bird is another sender to the same nest
+    let bird_copy = ops::wand_copy_widget(&mut state, bird_id).unwrap();
+    assert_eq!(birds_of(&state, nest_id).len(), 2);
+    assert!(birds_of(&state, nest_id).contains(&bird_copy));
+
+    // A copied nest hatches a bird of its own
+    let nest_copy = ops::wand_copy_widget(&mut state, nest_id).unwrap();
+    assert_ne!(nest_copy, nest_id);
+    assert_eq!(birds_of(&state, nest_copy).len(), 1);
+    assert_eq!(birds_of(&state, nest_id).len(), 2);
+
+    // Each nest is a channel of its own, worn by all its birds
+    assert_ne!(channel_of(&state, nest_copy), channel_of(&state, nest_id));
+    for (nest, bird) in [
+        (nest_id, bird_id),
+        (nest_id, bird_copy),
+        (nest_copy, birds_of(&state, nest_copy)[0]),
+    ] {
+        assert_eq!(channel_of(&state, bird), channel_of(&state, nest));
+    }
+}
+
+fn channel_of(state: &EngineState, id: tt_rs_core::WidgetId) -> Option<Channel> {
+    match state.widgets.get(&id) {
+        Some(WidgetItem::Nest(n)) => Some(n.channel()),
+        Some(WidgetItem::Bird(b)) => b.channel(),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_channels_have_distinct_labels() {
+    let labels: Vec<String> = [0, 1, 25, 26, 27, 701, 702]
+        .into_iter()
+        .map(|i| Channel::restore(i).label())
+        .collect();
+    assert_eq!(labels, ["A", "B", "Z", "AA", "AB", "ZZ", "AAA"]);
+    assert_ne!(
+        Channel::restore(3).css_color(),
+        Channel::restore(4).css_color()
+    );
+    // Channels handed out later never repeat a restored one
+    assert!(Channel::next().index() > 702);
+}
+
+#[test]
+fn test_box_copy_relinks_birds_to_copied_nests() {
+    let mut state = EngineState::new();
+    let paired = ops::hatch_nest(&mut state, Nest::new(), origin());
+    let paired_bird = birds_of(&state, paired)[0];
+    let lonely = state.add_widget(WidgetItem::Nest(Nest::new()), origin());
+    let outside = ops::hatch_nest(&mut state, Nest::new(), origin());
+    let outside_bird = birds_of(&state, outside)[0];
+
+    let box_id = state.add_box(BoxState::new(4), origin());
+    for (hole, id) in [paired, paired_bird, lonely, outside_bird]
+        .into_iter()
+        .enumerate()
+    {
+        assert!(ops::drop_in_hole(&mut state, id, box_id, hole, origin()));
+    }
+    let copy = ops::copy_box(&mut state, box_id, 30.0, 30.0).unwrap();
+    let hole = |i: usize| state.boxes[&copy].widget_in_hole(i).unwrap();
+
+    // The copied bird flies to the copied nest, not the original
+    let (nest_copy, bird_copy) = (hole(0), hole(1));
+    assert_eq!(birds_of(&state, nest_copy), vec![bird_copy]);
+    assert_eq!(birds_of(&state, paired), vec![paired_bird]);
+    // A nest copied without its birds gets a fresh one beside it
+    let lonely_copy = hole(2);
+    let fresh = birds_of(&state, lonely_copy);
+    assert_eq!(fresh.len(), 1);
+    assert!(!state.widget_in_box.contains_key(&fresh[0]));
+    assert!(birds_of(&state, lonely).is_empty());
+    // A bird whose nest stayed outside keeps sending to it
+    assert_eq!(birds_of(&state, outside).len(), 2);
+    assert!(birds_of(&state, outside).contains(&hole(3)));
+}
+
+#[test]
+fn test_give_in_hole_sends_through_the_bird_there() {
+    let mut state = EngineState::new();
+    let nest = ops::hatch_nest(&mut state, Nest::new(), origin());
+    let bird = birds_of(&state, nest)[0];
+    let box_id = state.add_box(BoxState::new(3), origin());
+    let other_nest = state.add_widget(WidgetItem::Nest(Nest::new()), origin());
+    let n = add_number(&mut state, 1);
+    ops::drop_in_hole(&mut state, bird, box_id, 0, origin());
+    ops::drop_in_hole(&mut state, other_nest, box_id, 1, origin());
+    ops::drop_in_hole(&mut state, n, box_id, 2, origin());
+
+    let message = add_number(&mut state, 5);
+    assert!(ops::give_in_hole(&mut state, message, box_id, 0));
+    assert!(!state.widgets.contains_key(&message));
+    assert_eq!(state.boxes[&box_id].widget_in_hole(0), Some(bird));
+    while ops::fly_birds(&mut state) {}
+    assert_eq!(nest_count(&state, nest), 1);
+
+    let kept = add_number(&mut state, 6);
+    assert!(ops::give_in_hole(&mut state, kept, box_id, 1));
+    assert_eq!(nest_count(&state, other_nest), 1);
+
+    // A hole holding anything else isn't a recipient
+    let ignored = add_number(&mut state, 7);
+    assert!(!ops::give_in_hole(&mut state, ignored, box_id, 2));
+    assert!(state.widgets.contains_key(&ignored));
+}
@@ -0,0 +1,957 @@
+//! Native tests for robot training and replay.
+
+use tt_rs_bird::Bird;
+use tt_rs_box::ToonBox;
+use tt_rs_engine::robot_exec::{ActionTarget, RobotOutcome};
+use tt_rs_engine::{ops, robot_exec, BoxState, EngineState, Position, WidgetItem};
+use tt_rs_nest::{Channel, Nest};
+use tt_rs_number::{ArithOperator, Number};
+use tt_rs_robot::{Action, Robot, RobotState, StopReason};
+
+fn origin() -> Position {
+    Position::new(0.0, 0.0)
+}
+
+fn robot(state: &EngineState, id: tt_rs_core::WidgetId) -> &Robot {
+    match state.widgets.get(&id) {
+        Some(WidgetItem::Robot(r)) => r,
+        _ => panic!("not a robot"),
+    }
+}
+
+#[test]
+fn test_click_toggles_training() {
+    let mut state = EngineState::new();
+    let rid = state.add_widget(WidgetItem::Robot(Robot::new()), origin());
+
+    assert!(robot_exec::click_robot(&mut state, rid));
+    assert_eq!(robot(&state, rid).state(), RobotState::Training);
+    assert_eq!(state.training_robot_id, Some(rid));
+
+    assert!(robot_exec::click_robot(&mut state, rid));
+    assert_eq!(robot(&state, rid).state(), RobotState::Idle);
+    assert_eq!(state.training_robot_id, None);
+}
+
+#[test]
+fn test_trained_arithmetic_replays() {
+    let mut state = EngineState::new();
+    let rid = state.add_widget(WidgetItem::Robot(Robot::new()), origin());
+    let target = state.add_widget(WidgetItem::Number(Number::new(1)), origin());
+    let one = state.add_widget(WidgetItem::Number(Number::new(1)), origin());
+
+    robot_exec::click_robot(&mut state, rid);
+    ops::apply_number(&mut state, one, target);
+    robot_exec::click_robot(&mut state, rid);
+    assert!(matches!(
+        robot(&state, rid).actions(),
+        [Action::ApplyArithmetic { .. }]
+    ));
+
+    robot_exec::click_robot(&mut state, rid);
+    match state.widgets.get(&target) {
+        Some(WidgetItem::Number(n)) => assert_eq!(n.numerator(), 3),
+        _ => panic!("target number missing"),
+    }
+}
+
+#[test]
+fn test_arithmetic_no_number_could_record_is_skipped() {
+    let mut state = EngineState::new();
+    let target = state.add_widget(WidgetItem::Number(Number::new(1)), origin());
+    let mut r = Robot::new();
+    let step = |operator, denominator| Action::ApplyArithmetic {
+        operator,
+        numerator: 1,
+        denominator,
+        target_path: format!("widget:{}", target),
+    };
+    r.set_actions(vec![
+        step('+', 0),
+        step('+', -2),
+        step('%', 1),
+        step('+', 1),
+    ]);
+    let rid = state.add_widget(WidgetItem::Robot(r), origin());
+
+    robot_exec::click_robot(&mut state, rid);
+    match state.widgets.get(&target) {
+        Some(WidgetItem::Number(n)) => assert_eq!(n.numerator(), 2),
+        _ => panic!("target number missing"),
+    }
+}
+
+#[test]
+fn test_trained_remove_replays() {
+    let mut state = EngineState::new();
+    let rid = state.add_widget(WidgetItem::Robot(Robot::new()), origin());
+    let box_id = state.add_box(BoxState::new(1), origin());
+
+    robot_exec::click_robot(&mut state, rid);
+    let n = state.add_widget(WidgetItem::Number(Number::new(4)), origin());
+    ops::drop_in_hole(&mut state, n, box_id, 0, origin());
+    ops::vacuum_hole(&mut state, box_id, 0);
+    robot_exec::click_robot(&mut state, rid);
+
+    let m = state.add_widget(WidgetItem::Number(Number::new(5)), origin());
+    ops::drop_in_hole(&mut state, m, box_id, 0, origin());
+    robot_exec::click_robot(&mut state, rid);
+    assert_eq!(state.boxes[&box_id].widget_in_hole(0), None);
+    assert!(!state.widgets.contains_key(&m));
+}
+
+fn box_with_number(
+    state: &mut EngineState,
+    value: i64,
+) -> (tt_rs_core::WidgetId, tt_rs_core::WidgetId) {
+    let box_id = state.add_box(BoxState::new(1), origin());
+    let n = state.add_widget(WidgetItem::Number(Number::new(value)), origin());
+    ops::drop_in_hole(state, n, box_id, 0, origin());
+    (box_id, n)
+}
+
+#[test]
+fn test_box_on_untrained_robot_starts_training() {
+    let mut state = EngineState::new();
+    let rid = state.add_widget(WidgetItem::Robot(Robot::new()), origin());
+    let (box_id, _) = box_with_number(&mut state, 1);
+
+    assert_eq!(
+        robot_exec::give_box_to_robot(&mut state, rid, box_id),
+        Some(RobotOutcome::Training)
+    );
+    assert_eq!(robot(&state, rid).state(), RobotState::Training);
+    assert_eq!(robot(&state, rid).pattern().map(|p| p.len()), Some(1));
+}
+
+#[test]
+fn test_trained_robot_runs_on_matching_box() {
+    let mut state = EngineState::new();
+    let rid = state.add_widget(WidgetItem::Robot(Robot::new()), origin());
+    let (box_id, n) = box_with_number(&mut state, 1);
+
+    robot_exec::give_box_to_robot(&mut state, rid, box_id);
+    let one = state.add_widget(WidgetItem::Number(Number::new(1)), origin());
+    ops::apply_number(&mut state, one, n);
+    robot_exec::click_robot(&mut state, rid);
+
+    // The box now holds 2, so the trained pattern [1] no longer matches
+    assert_eq!(
+        robot_exec::give_box_to_robot(&mut state, rid, box_id),
+        Some(RobotOutcome::NoMatch)
+    );
+    if let Some(WidgetItem::Robot(r)) = state.widgets.get_mut(&rid) {
+        r.set_pattern(ToonBox::erased_with_size(1));
+    }
+    state.step_budget = 1;
+    assert_eq!(
+        robot_exec::give_box_to_robot(&mut state, rid, box_id),
+        Some(RobotOutcome::Ran {
+            robot: rid,
+            iterations: 1,
+            stopped: StopReason::BudgetExhausted,
+        })
+    );
+    match state.widgets.get(&n) {
+        Some(WidgetItem::Number(num)) => assert_eq!(num.numerator(), 3),
+        _ => panic!("number missing"),
+    }
+}
+
+#[test]
+fn test_mismatch_passes_box_to_next_robot() {
+    let mut state = EngineState::new();
+    let first = state.add_widget(WidgetItem::Robot(Robot::new()), origin());
+    let (trained_on, n) = box_with_number(&mut state, 1);
+    robot_exec::give_box_to_robot(&mut state, first, trained_on);
+    let one = state.add_widget(WidgetItem::Number(Number::new(1)), origin());
+    ops::apply_number(&mut state, one, n);
+    robot_exec::click_robot(&mut state, first);
+
+    let other = state.add_box(BoxState::new(2), origin());
+    assert_eq!(
+        robot_exec::give_box_to_robot(&mut state, first, other),
+        Some(RobotOutcome::NoMatch)
+    );
+
+    let mut fallback = robot(&state, first).copy_robot();
+    fallback.set_pattern(ToonBox::erased());
+    let second = state.add_widget(WidgetItem::Robot(fallback), origin());
+    if let Some(WidgetItem::Robot(r)) = state.widgets.get_mut(&first) {
+        r.set_next_robot(Some(second));
+    }
+    assert!(matches!(
+        robot_exec::give_box_to_robot(&mut state, first, other),
+        Some(RobotOutcome::Ran { robot, .. }) if robot == second
+    ));
+}
+
+#[test]
+fn test_actions_are_recorded_relative_to_input() {
+    let mut state = EngineState::new();
+    let rid = state.add_widget(WidgetItem::Robot(Robot::new()), origin());
+    let (box_id, n) = box_with_number(&mut state, 1);
+
+    robot_exec::give_box_to_robot(&mut state, rid, box_id);
+    let one = state.add_widget(WidgetItem::Number(Number::new(1)), origin());
+    ops::apply_number(&mut state, one, n);
+    ops::vacuum_hole(&mut state, box_id, 0);
+    robot_exec::click_robot(&mut state, rid);
+
+    match robot(&state, rid).actions() {
+        [Action::ApplyArithmetic { target_path, .. }, Action::Remove { path }] => {
+            assert_eq!(target_path, "input/hole:0");
+            assert_eq!(path, "input/hole:0");
+        }
+        other => panic!("unexpected actions: {:?}", other),
+    }
+}
+
+#[test]
+fn test_trained_robot_works_on_copies() {
+    let mut state = EngineState::new();
+    let rid = state.add_widget(WidgetItem::Robot(Robot::new()), origin());
+    let (trained_on, n) = box_with_number(&mut state, 1);
+    let (copy, m) = box_with_number(&mut state, 1);
+
+    robot_exec::give_box_to_robot(&mut state, rid, trained_on);
+    let one = state.add_widget(WidgetItem::Number(Number::new(1)), origin());
+    ops::apply_number(&mut state, one, n);
+    robot_exec::click_robot(&mut state, rid);
+
+    assert_eq!(
+        robot_exec::give_box_to_robot(&mut state, rid, copy),
+        Some(RobotOutcome::Ran {
+            robot: rid,
+            iterations: 1,
+            stopped: StopReason::PatternFailed,
+        })
+    );
+    let value = |id| match state.widgets.get(&id) {
+        Some(WidgetItem::Number(num)) => num.numerator(),
+        _ => panic!("number missing"),
+    };
+    assert_eq!(value(n), 2);
+    assert_eq!(value(m), 2);
+}
+
+#[test]
+fn test_resolve_relative_paths() {
+    let mut state = EngineState::new();
+    let (box_id, n) = box_with_number(&mut state, 1);
+
+    assert_eq!(
+        robot_exec::resolve_widget_path(&state, "input/hole:0", Some(box_id)),
+        Some(n)
+    );
+    assert_eq!(
+        robot_exec::resolve_box_hole_path(&state, "input/hole:0", Some(box_id)),
+        Some((box_id, 0))
+    );
+    assert_eq!(
+        robot_exec::resolve_widget_path(&state, "input", Some(box_id)),
+        Some(box_id)
+    );
+    assert_eq!(
+        robot_exec::resolve_widget_path(&state, "input/hole:0", None),
+        None
+    );
+    assert_eq!(
+        robot_exec::resolve_widget_path(&state, &format!("widget:{}", n), None),
+        Some(n)
+    );
+}
+
+#[test]
+fn test_robot_repeats_until_budget() {
+    let mut state = EngineState::new();
+    let rid = state.add_widget(WidgetItem::Robot(Robot::new()), origin());
+    let (box_id, n) = box_with_number(&mut state, 0);
+
+    robot_exec::give_box_to_robot(&mut state, rid, box_id);
+    let one = state.add_widget(WidgetItem::Number(Number::new(1)), origin());
+    ops::apply_number(&mut state, one, n);
+    robot_exec::click_robot(&mut state, rid);
+    if let Some(WidgetItem::Robot(r)) = state.widgets.get_mut(&rid) {
+        r.set_pattern(ToonBox::erased_with_size(1));
+    }
+
+    state.step_budget = 8;
+    let outcome = robot_exec::give_box_to_robot(&mut state, rid, box_id);
+    assert!(matches!(
+        outcome,
+        Some(RobotOutcome::Ran {
+            iterations: 8,
+            stopped: StopReason::BudgetExhausted,
+            ..
+        })
+    ));
+    assert_eq!(
+        robot(&state, rid).stop_reason(),
+        Some(StopReason::BudgetExhausted)
+    );
+    match state.widgets.get(&n) {
+        Some(WidgetItem::Number(num)) => assert_eq!(num.numerator(), 9),
+        _ => panic!("number missing"),
+    }
+}
+
+#[test]
+fn test_robot_stops_when_pattern_fails() {
+    let mut state = EngineState::new();
+    let rid = state.add_widget(WidgetItem::Robot(Robot::new()), origin());
+    let (box_id, n) = box_with_number(&mut state, 1);
+
+    robot_exec::give_box_to_robot(&mut state, rid, box_id);
+    ops::vacuum_hole(&mut state, box_id, 0);
+    robot_exec::click_robot(&mut state, rid);
+    assert!(!state.widgets.contains_key(&n));
+
+    let m = state.add_widget(WidgetItem::Number(Number::new(1)), origin());
+    ops::drop_in_hole(&mut state, m, box_id, 0, origin());
+    let outcome = robot_exec::give_box_to_robot(&mut state, rid, box_id);
+    assert!(matches!(
+        outcome,
+        Some(RobotOutcome::Ran {
+            iterations: 1,
+            stopped: StopReason::PatternFailed,
+            ..
+        })
+    ));
+    assert_eq!(
+        robot(&state, rid).stop_reason(),
+        Some(StopReason::PatternFailed)
+    );
+}
+
+#[test]
+fn test_robot_moves_widget_between_holes() {
+    let mut state = EngineState::new();
+    let rid = state.add_widget(WidgetItem::Robot(Robot::new()), origin());
+    let trained_on = state.add_box(BoxState::new(2), origin());
+    let n = state.add_widget(WidgetItem::Number(Number::new(1)), origin());
+    ops::drop_in_hole(&mut state, n, trained_on, 0, origin());
+
+    robot_exec::give_box_to_robot(&mut state, rid, trained_on);
+    let taken = ops::take_from_hole(&mut state, trained_on, 0, origin()).unwrap();
+    ops::drop_in_hole(&mut state, taken, trained_on, 1, origin());
+    robot_exec::click_robot(&mut state, rid);
+    match robot(&state, rid).actions() {
+        [Action::PickUp { path: from }, Action::Drop { path: to }] => {
+            assert_eq!(from, "input/hole:0");
+            assert_eq!(to, "input/hole:1");
+        }
+        other => panic!("unexpected actions: {:?}", other),
+    }
+
+    let other = state.add_box(BoxState::new(2), origin());
+    let m = state.add_widget(WidgetItem::Number(Number::new(1)), origin());
+    ops::drop_in_hole(&mut state, m, other, 0, origin());
+    assert!(matches!(
+        robot_exec::give_box_to_robot(&mut state, rid, other),
+        Some(RobotOutcome::Ran {
+            iterations: 1,
+            stopped: StopReason::PatternFailed,
+            ..
+        })
+    ));
+    assert_eq!(state.boxes[&other].widget_in_hole(0), None);
+    assert_eq!(state.boxes[&other].widget_in_hole(1), Some(m));
+}
+
+#[test]
+fn test_robot_drops_held_widget_on_nest() {
+    let mut state = EngineState::new();
+    let rid = state.add_widget(WidgetItem::Robot(Robot::new()), origin());
+    let nest = state.add_widget(WidgetItem::Nest(Nest::new()), origin());
+    let (trained_on, _) = box_with_number(&mut state, 1);
+
+    robot_exec::give_box_to_robot(&mut state, rid, trained_on);
+    let taken = ops::take_from_hole(&mut state, trained_on, 0, origin()).unwrap();
+    assert!(ops::give_to_nest(&mut state, taken, nest));
+    robot_exec::click_robot(&mut state, rid);
+
+    let (other, m) = box_with_number(&mut state, 1);
+    robot_exec::give_box_to_robot(&mut state, rid, other);
+    assert!(!state.widgets.contains_key(&m));
+    match state.widgets.get(&nest) {
+        Some(WidgetItem::Nest(n)) => assert_eq!(n.message_count(), 2),
+        _ => panic!("nest missing"),
+    }
+}
+
+#[test]
+fn test_replay_is_not_recorded_by_training_robot() {
+    let mut state = EngineState::new();
+    let worker = state.add_widget(WidgetItem::Robot(Robot::new()), origin());
+    let (box_id, n) = box_with_number(&mut state, 1);
+    robot_exec::give_box_to_robot(&mut state, worker, box_id);
+    let one = state.add_widget(WidgetItem::Number(Number::new(1)), origin());
+    ops::apply_number(&mut state, one, n);
+    robot_exec::click_robot(&mut state, worker);
+
+    let trainee = state.add_widget(WidgetItem::Robot(Robot::new()), origin());
+    robot_exec::click_robot(&mut state, trainee);
+    assert!(ops::erase_pattern(&mut state, worker, &[0]));
+    state.step_budget = 1;
+    assert!(matches!(
+        robot_exec::run_team(&mut state, worker, box_id),
+        RobotOutcome::Ran { .. }
+    ));
+    assert!(robot(&state, trainee).actions().is_empty());
+    assert_eq!(state.training_robot_id, Some(trainee));
+}
+
+#[test]
+fn test_training_records_box_operations() {
+    let mut state = EngineState::new();
+    let rid = state.add_widget(WidgetItem::Robot(Robot::new()), origin());
+    let one = state.add_widget(WidgetItem::Number(Number::new(1)), origin());
+
+    robot_exec::click_robot(&mut state, rid);
+    let made = ops::create_box(&mut state, 2, origin());
+    let copy = ops::copy_box(&mut state, made, 30.0, 30.0).unwrap();
+    // The joined box is still the one the robot made
+    assert!(ops::join_boxes(&mut state, copy, made));
+    assert!(ops::split_box(&mut state, made, one, origin()));
+    robot_exec::click_robot(&mut state, rid);
+
+    match robot(&state, rid).actions() {
+        [Action::NewBox { holes: 2 }, Action::Copy { path: copied }, Action::PickUp { path: src }, Action::Drop { path: tgt }, Action::SplitBox { path: split, at: 1 }] =>
+        {
+            assert_eq!(copied, "new:0");
+            assert_eq!(src, "new:1");
+            assert_eq!(tgt, "new:0");
+            assert_eq!(split, "new:0");
+        }
+        other => panic!("unexpected actions: {:?}", other),
+    }
+}
+
+#[test]
+fn test_robot_replays_split_and_join() {
+    let mut state = EngineState::new();
+    let rid = state.add_widget(WidgetItem::Robot(Robot::new()), origin());
+    let trained_on = state.add_box(BoxState::new(2), origin());
+    for (hole, value) in [(0, 1), (1, 2)] {
+        let n = state.add_widget(WidgetItem::Number(Number::new(value)), origin());
+        ops::drop_in_hole(&mut state, n, trained_on, hole, origin());
+    }
+
+    // Swap the two holes: split in half, then join the halves the other way round
+    robot_exec::give_box_to_robot(&mut state, rid, trained_on);
+    let one = state.add_widget(WidgetItem::Number(Number::new(1)), origin());
+    ops::split_box(&mut state, trained_on, one, origin());
+    let right = state.created.as_ref().unwrap()[0];
+    ops::join_boxes(&mut state, trained_on, right);
+    robot_exec::click_robot(&mut state, rid);
+    if let Some(WidgetItem::Robot(r)) = state.widgets.get_mut(&rid) {
+        r.set_pattern(ToonBox::erased_with_size(2));
+    }
+
+    let other = state.add_box(BoxState::new(2), origin());
+    let three = state.add_widget(WidgetItem::Number(Number::new(3)), origin());
+    let four = state.add_widget(WidgetItem::Number(Number::new(4)), origin());
+    ops::drop_in_hole(&mut state, three, other, 0, origin());
+    ops::drop_in_hole(&mut state, four, other, 1, origin());
+    robot_exec::give_box_to_robot(&mut state, rid, other);
+
+    assert!(!state.boxes.contains_key(&other));
+    let (swapped, _) = state.widget_in_box[&four];
+    assert_eq!(state.boxes[&swapped].widget_in_hole(0), Some(four));
+    assert_eq!(state.boxes[&swapped].widget_in_hole(1), Some(three));
+}
+
+#[test]
+fn test_robot_keeps_working_on_its_box_after_joining_into_it() {
+    let mut state = EngineState::new();
+    let input = state.add_box(BoxState::new(1), origin());
+    let five = state.add_widget(WidgetItem::Number(Number::new(5)), origin());
+    ops::drop_in_hole(&mut state, five, input, 0, origin());
+
+    // Join a new box onto the input, then move the 5 into the added hole
+    let mut r = Robot::new();
+    r.set_pattern(ToonBox::erased_with_size(1));
+    let path = |p: &str| p.to_string();
+    r.set_actions(vec![
+        Action::NewBox { holes: 1 },
+        Action::PickUp {
+            path: path("new:0"),
+        },
+        Action::Drop {
+            path: path("input"),
+        },
+        Action::PickUp {
+            path: path("input/hole:0"),
+        },
+        Action::Drop {
+            path: path("input/hole:1"),
+        },
+    ]);
+    let rid = state.add_widget(WidgetItem::Robot(r), origin());
+    state.step_budget = 5;
+    robot_exec::give_box_to_robot(&mut state, rid, input);
+
+    let joined = &state.boxes[&input];
+    assert_eq!(joined.num_holes, 2);
+    assert_eq!(joined.widget_in_hole(0), None);
+    assert_eq!(joined.widget_in_hole(1), Some(five));
+    assert_eq!(state.widget_in_box[&five], (input, 1));
+}
+
+#[test]
+fn test_robot_fills_new_box_from_nest() {
+    let mut state = EngineState::new();
+    let rid = state.add_widget(WidgetItem::Robot(Robot::new()), origin());
+    let nest = state.add_widget(WidgetItem::Nest(Nest::new()), origin());
+    for value in [1, 2] {
+        let n = state.add_widget(WidgetItem::Number(Number::new(value)), origin());
+        ops::give_to_nest(&mut state, n, nest);
+    }
+
+    robot_exec::click_robot(&mut state, rid);
+    let made = ops::create_box(&mut state, 1, origin());
+    let msg = ops::take_from_nest(&mut state, nest, origin()).unwrap();
+    ops::drop_in_hole(&mut state, msg, made, 0, origin());
+    robot_exec::click_robot(&mut state, rid);
+    assert!(matches!(
+        robot(&state, rid).actions(),
+        [
+            Action::NewBox { holes: 1 },
+            Action::TakeFromNest { .. },
+            Action::PickUp { .. },
+            Action::Drop { .. }
+        ]
+    ));
+
+    robot_exec::click_robot(&mut state, rid);
+    match state.widgets.get(&nest) {
+        Some(WidgetItem::Nest(n)) => assert_eq!(n.message_count(), 0),
+        _ => panic!("nest missing"),
+    }
+    let filled = state
+        .boxes
+        .values()
+        .filter(|b| b.id() != made && b.widget_in_hole(0).is_some())
+        .count();
+    assert_eq!(filled, 1);
+}
+
+/// Team of "if zero, erase it" (lead) and "otherwise subtract 1".
+fn countdown_team(state: &mut EngineState) -> tt_rs_core::WidgetId {
+    let on_zero = state.add_widget(WidgetItem::Robot(Robot::new()), origin());
+    let (zero_box, _) = box_with_number(state, 0);
+    robot_exec::give_box_to_robot(state, on_zero, zero_box);
+    ops::vacuum_hole(state, zero_box, 0);
+    robot_exec::click_robot(state, on_zero);
+
+    let otherwise = state.add_widget(WidgetItem::Robot(Robot::new()), origin());
+    let (five_box, _) = box_with_number(state, 5);
+    robot_exec::give_box_to_robot(state, otherwise, five_box);
+    let minus_one = Number::new(1).with_operator(ArithOperator::Subtract);
+    let minus_one = state.add_widget(WidgetItem::Number(minus_one), origin());
+    let five = state.boxes[&five_box].widget_in_hole(0).unwrap();
+    ops::apply_number(state, minus_one, five);
+    robot_exec::click_robot(state, otherwise);
+    let mut any_number = ToonBox::new(1);
+    any_number.set_hole(0, Box::new(Number::erased()));
+    if let Some(WidgetItem::Robot(r)) = state.widgets.get_mut(&otherwise) {
+        r.set_pattern(any_number);
+    }
+
+    assert!(ops::join_team(state, otherwise, on_zero));
+    on_zero
+}
+
+#[test]
+fn test_team_passes_unmatched_box_down_the_chain() {
+    let mut state = EngineState::new();
+    let lead = countdown_team(&mut state);
+    let (box_id, _) = box_with_number(&mut state, 3);
+
+    assert_eq!(
+        robot_exec::give_box_to_robot(&mut state, lead, box_id),
+        Some(RobotOutcome::Ran {
+            robot: lead,
+            iterations: 4,
+            stopped: StopReason::PatternFailed,
+        })
+    );
+    assert_eq!(state.boxes[&box_id].widget_in_hole(0), None);
+}
+
+#[test]
+fn test_join_team_rejects_cycles() {
+    let mut state = EngineState::new();
+    let lead = countdown_team(&mut state);
+    let follower = robot(&state, lead).next_robot().unwrap();
+
+    assert!(!ops::join_team(&mut state, lead, follower));
+    assert!(!ops::join_team(&mut state, follower, lead));
+    assert_eq!(robot_exec::team(&state, lead), vec![lead, follower]);
+}
+
+#[test]
+fn test_wand_copies_whole_team() {
+    let mut state = EngineState::new();
+    let lead = countdown_team(&mut state);
+    let follower = robot(&state, lead).next_robot().unwrap();
+
+    let copy = ops::wand_copy_widget(&mut state, lead).unwrap();
+    let copied_team = robot_exec::team(&state, copy);
+    assert_eq!(copied_team.len(), 2);
+    assert_ne!(copied_team[1], follower);
+    assert_eq!(robot(&state, copied_team[1]).actions().len(), 1);
+}
+
+#[test]
+fn test_stepped_run_shows_each_action() {
+    let mut state = EngineState::new();
+    let rid = state.add_widget(WidgetItem::Robot(Robot::new()), origin());
+    let trained_on = state.add_box(BoxState::new(2), origin());
+    let n = state.add_widget(WidgetItem::Number(Number::new(1)), origin());
+    ops::drop_in_hole(&mut state, n, trained_on, 0, origin());
+    robot_exec::give_box_to_robot(&mut state, rid, trained_on);
+    let taken = ops::take_from_hole(&mut state, trained_on, 0, origin()).unwrap();
+    ops::drop_in_hole(&mut state, taken, trained_on, 1, origin());
+    robot_exec::click_robot(&mut state, rid);
+
+    let other = state.add_box(BoxState::new(2), origin());
+    let m = state.add_widget(WidgetItem::Number(Number::new(1)), origin());
+    ops::drop_in_hole(&mut state, m, other, 0, origin());
+    let mut run = match robot_exec::give_box(&mut state, rid, other) {
+        Some(robot_exec::Given::Running(run)) => run,
+        other => panic!("expected a run, got {:?}", other),
+    };
+
+    assert_eq!(run.position(), 0);
+    assert_eq!(run.target(&state), Some(ActionTarget::Hole(other, 0)));
+    assert_eq!(run.step(&mut state), None);
+    assert!(run.is_holding());
+    assert_eq!(run.target(&state), Some(ActionTarget::Hole(other, 1)));
+    assert_eq!(state.boxes[&other].widget_in_hole(1), None);
+
+    let outcome = run.step(&mut state);
+    assert!(matches!(
+        outcome,
+        Some(RobotOutcome::Ran {
+            iterations: 1,
+            stopped: StopReason::PatternFailed,
+            ..
+        })
+    ));
+    assert_eq!(state.boxes[&other].widget_in_hole(1), Some(m));
+    assert_eq!(robot(&state, rid).state(), RobotState::Idle);
+}
+
+#[test]
+fn test_stepped_run_matches_run_team() {
+    let mut state = EngineState::new();
+    let lead = countdown_team(&mut state);
+    let (box_id, n) = box_with_number(&mut state, 3);
+    let mut copy = state.clone();
+
+    let stepped = robot_exec::RobotRun::start(&mut state, lead, Some(box_id))
+        .unwrap()
+        .finish_all(&mut state);
+    let ran = robot_exec::run_team(&mut copy, lead, box_id);
+    assert_eq!(stepped, ran);
+    assert!(!state.widgets.contains_key(&n));
+    assert_eq!(
+        state.boxes[&box_id].widget_in_hole(0),
+        copy.boxes[&box_id].widget_in_hole(0)
+    );
+}
+
+#[test]
+fn test_retrain_from_step_replays_kept_actions() {
+    let mut state = EngineState::new();
+    let rid = state.add_widget(WidgetItem::Robot(Robot::new()), origin());
+    let (trained_on, n) = box_with_number(&mut state, 1);
+    robot_exec::give_box_to_robot(&mut state, rid, trained_on);
+    for value in [1, 10] {
+        let add = state.add_widget(WidgetItem::Number(Number::new(value)), origin());
+        ops::apply_number(&mut state, add, n);
+    }
+    robot_exec::click_robot(&mut state, rid);
+    assert_eq!(robot(&state, rid).actions().len(), 2);
+
+    assert!(robot_exec::retrain_from(&mut state, rid, 1));
+    assert_eq!(robot(&state, rid).retrain_at(), Some(1));
+    assert_eq!(robot(&state, rid).state(), RobotState::Idle);
+
+    let (box_id, m) = box_with_number(&mut state, 20);
+    assert_eq!(
+        robot_exec::give_box_to_robot(&mut state, rid, box_id),
+        Some(RobotOutcome::Training)
+    );
+    assert_eq!(robot(&state, rid).state(), RobotState::Training);
+    assert_eq!(robot(&state, rid).retrain_at(), None);
+    let five = state.add_widget(WidgetItem::Number(Number::new(5)), origin());
+    ops::apply_number(&mut state, five, m);
+    robot_exec::click_robot(&mut state, rid);
+
+    match robot(&state, rid).actions() {
+        [Action::ApplyArithmetic { numerator: 1, .. }, Action::ApplyArithmetic { numerator: 5, .. }] =>
+            {}
+        other => panic!("unexpected actions: {:?}", other),
+    }
+    match state.widgets.get(&m) {
+        Some(WidgetItem::Number(num)) => assert_eq!(num.numerator(), 26),
+        _ => panic!("number missing"),
+    }
+}
+
+#[test]
+fn test_vacuumed_pattern_matches_more_boxes() {
+    let mut state = EngineState::new();
+    let rid = state.add_widget(WidgetItem::Robot(Robot::new()), origin());
+    let (trained_on, n) = box_with_number(&mut state, 3);
+    robot_exec::give_box_to_robot(&mut state, rid, trained_on);
+    let one = state.add_widget(WidgetItem::Number(Number::new(1)), origin());
+    ops::apply_number(&mut state, one, n);
+    robot_exec::click_robot(&mut state, rid);
+
+    let (box_id, _) = box_with_number(&mut state, 7);
+    assert_eq!(
+        robot_exec::give_box_to_robot(&mut state, rid, box_id),
+        Some(RobotOutcome::NoMatch)
+    );
+
+    assert!(ops::erase_pattern(&mut state, rid, &[0]));
+    state.step_budget = 3;
+    assert!(matches!(
+        robot_exec::give_box_to_robot(&mut state, rid, box_id),
+        Some(RobotOutcome::Ran {
+            iterations: 3,
+            stopped: StopReason::BudgetExhausted,
+            ..
+        })
+    ));
+    assert!(!ops::erase_pattern(&mut state, rid, &[1]));
+}
+
+#[test]
+fn test_run_without_progress_stops_early() {
+    let mut state = EngineState::new();
+    let mut r = Robot::new();
+    r.set_pattern(ToonBox::erased_with_size(1));
+    r.set_actions(vec![
+        Action::PickUp {
+            path: "input/hole:0".to_string(),
+        },
+        Action::Drop {
+            path: "input/hole:0".to_string(),
+        },
+    ]);
+    let rid = state.add_widget(WidgetItem::Robot(r), origin());
+    let (box_id, n) = box_with_number(&mut state, 4);
+
+    // Each pass puts the number back where it was
+    assert_eq!(
+        robot_exec::give_box_to_robot(&mut state, rid, box_id),
+        Some(RobotOutcome::Ran {
+            robot: rid,
+            iterations: 1,
+            stopped: StopReason::NoProgress,
+        })
+    );
+    assert_eq!(
+        robot(&state, rid).stop_reason(),
+        Some(StopReason::NoProgress)
+    );
+    assert_eq!(state.widget_in_box.get(&n), Some(&(box_id, 0)));
+}
+
+#[test]
+fn test_feeding_a_bird_is_progress() {
+    let mut state = EngineState::new();
+    let mut r = Robot::new();
+    r.set_pattern(ToonBox::erased_with_size(2));
+    r.set_actions(vec![
+        Action::Copy {
+            path: "input/hole:0".to_string(),
+        },
+        Action::PickUp {
+            path: "new:0".to_string(),
+        },
+        Action::Drop {
+            path: "input/hole:1".to_string(),
+        },
+    ]);
+    let rid = state.add_widget(WidgetItem::Robot(r), origin());
+    let nest = Nest::new();
+    let bird = state.add_widget(WidgetItem::Bird(Bird::hatch(&nest)), origin());
+    state.add_widget(WidgetItem::Nest(nest), origin());
+    let box_id = state.add_box(BoxState::new(2), origin());
+    let n = state.add_widget(WidgetItem::Number(Number::new(4)), origin());
+    ops::drop_in_hole(&mut state, n, box_id, 0, origin());
+    ops::drop_in_hole(&mut state, bird, box_id, 1, origin());
+
+    // Only the bird's load changes from pass to pass
+    state.step_budget = 9;
+    assert_eq!(
+        robot_exec::give_box_to_robot(&mut state, rid, box_id),
+        Some(RobotOutcome::Ran {
+            robot: rid,
+            iterations: 3,
+            stopped: StopReason::BudgetExhausted,
+        })
+    );
+    match state.widgets.get(&bird) {
+        Some(WidgetItem::Bird(b)) => assert_eq!(b.queued_count(), 2),
+        _ => panic!("bird missing"),
+    }
+}
+
+#[test]
+fn test_step_many_spreads_a_run() {
+    let mut state = EngineState::new();
+    let rid = state.add_widget(WidgetItem::Robot(Robot::new()), origin());
+    let (box_id, n) = box_with_number(&mut state, 0);
+    robot_exec::give_box_to_robot(&mut state, rid, box_id);
+    let one = state.add_widget(WidgetItem::Number(Number::new(1)), origin());
+    ops::apply_number(&mut state, one, n);
+    robot_exec::click_robot(&mut state, rid);
+    if let Some(WidgetItem::Robot(r)) = state.widgets.get_mut(&rid) {
+        r.set_pattern(ToonBox::erased_with_size(1));
+    }
+
+    state.step_budget = 10;
+    let mut run = match robot_exec::give_box(&mut state, rid, box_id) {
+        Some(robot_exec::Given::Running(run)) => run,
+        other => panic!("expected a run, got {:?}", other),
+    };
+    assert_eq!(run.step_many(&mut state, 4), None);
+    assert_eq!(robot(&state, rid).state(), RobotState::Working);
+    assert!(matches!(
+        run.step_many(&mut state, 100),
+        Some(RobotOutcome::Ran {
+            iterations: 10,
+            stopped: StopReason::BudgetExhausted,
+            ..
+        })
+    ));
+    match state.widgets.get(&n) {
+        Some(WidgetItem::Number(num)) => assert_eq!(num.numerator(), 11),
+        _ => panic!("number missing"),
+    }
+}
+
+#[test]
+fn test_robot_picks_the_message_from_a_nest_in_a_hole() {
+    let mut state = EngineState::new();
+    let mut r = Robot::new();
+    let mut pattern = ToonBox::new(2);
+    pattern.set_hole(0, Box::new(Number::erased()));
+    r.set_pattern(pattern);
+    r.set_actions(vec![
+        Action::PickUp {
+            path: "input/hole:0".to_string(),
+        },
+        Action::Drop {
+            path: "input/hole:1".to_string(),
+        },
+    ]);
+    let rid = state.add_widget(WidgetItem::Robot(r), origin());
+    let box_id = state.add_box(BoxState::new(2), origin());
+    let nest = state.add_widget(WidgetItem::Nest(Nest::new()), origin());
+    ops::drop_in_hole(&mut state, nest, box_id, 0, origin());
+
+    // An empty nest makes the robot wait
+    assert!(matches!(
+        robot_exec::give_box_to_robot(&mut state, rid, box_id),
+        Some(RobotOutcome::Suspended { nest: n, .. }) if n == nest
+    ));
+
+    let message = state.add_widget(WidgetItem::Number(Number::new(3)), origin());
+    assert!(ops::give_to_nest(&mut state, message, nest));
+    robot_exec::give_box_to_robot(&mut state, rid, box_id);
+
+    // The robot took the message; the nest stays in its hole
+    assert_eq!(state.boxes[&box_id].widget_in_hole(0), Some(nest));
+    let moved = state.boxes[&box_id].widget_in_hole(1).expect("message");
+    assert!(matches!(
+        state.widgets.get(&moved),
+        Some(WidgetItem::Number(n)) if n.numerator() == 3
+    ));
+    match state.widgets.get(&nest) {
+        Some(WidgetItem::Nest(n)) => assert!(!n.has_messages()),
+        _ => panic!("nest missing"),
+    }
+}
+
+#[test]
+fn test_robot_sends_through_the_bird_in_its_box() {
+    let mut state = EngineState::new();
+    let rid = state.add_widget(WidgetItem::Robot(Robot::new()), origin());
+    let nest = state.add_widget(WidgetItem::Nest(Nest::new()), origin());
+    let bird = state.add_widget(
+        WidgetItem::Bird(Bird::with_nest(nest, Channel::default())),
+        origin(),
+    );
+    let box_id = state.add_box(BoxState::new(2), origin());
+    let n = state.add_widget(WidgetItem::Number(Number::new(1)), origin());
+    ops::drop_in_hole(&mut state, n, box_id, 0, origin());
+    ops::drop_in_hole(&mut state, bird, box_id, 1, origin());
+    let copy = ops::copy_box(&mut state, box_id, 30.0, 30.0).unwrap();
+
+    // Train: give the number to the bird in hole 1
+    robot_exec::give_box_to_robot(&mut state, rid, box_id);
+    let taken = ops::take_from_hole(&mut state, box_id, 0, origin()).unwrap();
+    assert!(ops::give_in_hole(&mut state, taken, box_id, 1));
+    robot_exec::click_robot(&mut state, rid);
+    match robot(&state, rid).actions() {
+        [Action::PickUp { path: from }, Action::Drop { path: to }] => {
+            assert_eq!(from, "input/hole:0");
+            assert_eq!(to, "input/hole:1");
+        }
+        other => panic!("unexpected actions: {:?}", other),
+    }
+
+    // The copied bird in the copied box flies to the same nest
+    robot_exec::give_box_to_robot(&mut state, rid, copy);
+    assert_eq!(state.boxes[&copy].widget_in_hole(0), None);
+    assert!(state.boxes[&copy].widget_in_hole(1).is_some());
+    while ops::fly_birds(&mut state) {}
+    match state.widgets.get(&nest) {
+        Some(WidgetItem::Nest(n)) => assert_eq!(n.message_count(), 2),
+        _ => panic!("nest missing"),
+    }
+}
+
+#[test]
+fn test_programs_read_in_plain_language() {
+    let actions = [
+        Action::NewBox { holes: 2 },
+        Action::PickUp {
+            path: "input/hole:1".to_string(),
+        },
+        Action::Drop {
+            path: "new:0/hole:0".to_string(),
+        },
+        Action::ApplyArithmetic {
+            operator: '+',
+            numerator: 1,
+            denominator: 2,
+            target_path: "input/hole:0/hole:2".to_string(),
+        },
+        Action::Copy {
+            path: "new:1".to_string(),
+        },
+        Action::Drop {
+            path: "box:3:hole:0".to_string(),
+        },
+    ];
+    assert_eq!(
+        robot_exec::describe_program(&actions),
+        [
+            "Make a box with 2 holes",
+            "Pick up the thing in hole 2 of the box I was given",
+            "Put it in hole 1 of the new box I made",
+            "Add 1/2 to the thing in hole 3 of the box in hole 1 of the box I was given",
+            "Copy the thing I took out with the wand",
+            "Put it in hole 1 of box #3",
+        ]
+    );
+}
@@ -0,0 +1,179 @@
+//! Native tests for the robot process scheduler.
+
+use tt_rs_box::ToonBox;
+use tt_rs_core::WidgetId;
+use tt_rs_engine::robot_exec::RobotOutcome;
+use tt_rs_engine::scheduler::{ProcessState, Scheduler};
+use tt_rs_engine::{ops, BoxState, EngineState, Position, WidgetItem};
+use tt_rs_nest::Nest;
+use tt_rs_number::Number;
+use tt_rs_robot::{Action, Robot, StopReason};
+
+fn origin() -> Position {
+    Position::new(0.0, 0.0)
+}
+
+fn number(state: &EngineState, id: WidgetId) -> i64 {
+    match state.widgets.get(&id) {
+        Some(WidgetItem::Number(n)) => n.numerator(),
+        _ => panic!("not a number"),
+    }
+}
+
+/// A robot that adds 1 to hole 0 of any one-hole box.
+fn counter_robot(state: &mut EngineState) -> WidgetId {
+    let mut r = Robot::new();
+    r.set_pattern(ToonBox::erased_with_size(1));
+    r.set_actions(vec![Action::ApplyArithmetic {
+        operator: '+',
+        numerator: 1,
+        denominator: 1,
+        target_path: "input/hole:0".to_string(),
+    }]);
+    state.add_widget(WidgetItem::Robot(r), origin())
+}
+
+fn box_with(state: &mut EngineState, item: WidgetItem) -> (WidgetId, WidgetId) {
+    let box_id = state.add_box(BoxState::new(1), origin());
+    let id = state.add_widget(item, origin());
+    ops::drop_in_hole(state, id, box_id, 0, origin());
+    (box_id, id)
+}
+
+#[test]
+fn test_running_processes_take_turns() {
+    let mut state = EngineState::new();
+    let (a, b) = (counter_robot(&mut state), counter_robot(&mut state));
+    let (box_a, n_a) = box_with(&mut state, WidgetItem::Number(Number::new(0)));
+    let (box_b, n_b) = box_with(&mut state, WidgetItem::Number(Number::new(10)));
+
+    let mut scheduler = Scheduler::new();
+    let pa = scheduler.spawn(&mut state, a, Some(box_a));
+    let pb = scheduler.spawn(&mut state, b, Some(box_b));
+    scheduler.tick(&mut state, 3);
+
+    assert_eq!((number(&state, n_a), number(&state, n_b)), (3, 13));
+    assert_eq!(
+        scheduler.process(pa).map(|p| p.state()),
+        Some(ProcessState::Running)
+    );
+    assert_eq!(
+        scheduler.process(pb).map(|p| p.state()),
+        Some(ProcessState::Running)
+    );
+}
+
+#[test]
+fn test_finished_processes_are_removed() {
+    let mut state = EngineState::new();
+    state.step_budget = 2;
+    let a = counter_robot(&mut state);
+    let (box_a, n_a) = box_with(&mut state, WidgetItem::Number(Number::new(0)));
+    let unmatched = state.add_box(BoxState::new(2), origin());
+
+    let mut scheduler = Scheduler::new();
+    let pa = scheduler.spawn(&mut state, a, Some(box_a));
+    let stray = scheduler.spawn(&mut state, a, Some(unmatched));
+    assert_eq!(
+        scheduler.process(stray).map(|p| p.state()),
+        Some(ProcessState::Finished)
+    );
+
+    scheduler.tick(&mut state, 5);
+    assert_eq!(number(&state, n_a), 2);
+    assert!(!scheduler.is_running());
+    let finished = scheduler.remove_finished();
+    assert_eq!(finished.len(), 2);
+    assert!(matches!(
+        finished
+            .iter()
+            .find(|p| p.id() == pa)
+            .and_then(|p| p.outcome()),
+        Some(RobotOutcome::Ran {
+            stopped: StopReason::BudgetExhausted,
+            ..
+        })
+    ));
+    assert!(scheduler.processes().is_empty());
+}
+
+#[test]
+fn test_waiting_process_wakes_on_delivery() {
+    let mut state = EngineState::new();
+    let mut r = Robot::new();
+    let mut pattern = ToonBox::new(1);
+    pattern.set_hole(0, Box::new(Number::erased()));
+    r.set_pattern(pattern);
+    r.set_actions(vec![
+        Action::TakeFromNest {
+            path: "input/hole:0".to_string(),
+        },
+        Action::Remove {
+            path: "new:0".to_string(),
+        },
+    ]);
+    let rid = state.add_widget(WidgetItem::Robot(r), origin());
+    let (box_id, nest) = box_with(&mut state, WidgetItem::Nest(Nest::new()));
+
+    let mut scheduler = Scheduler::new();
+    let pid = scheduler.spawn(&mut state, rid, Some(box_id));
+    assert_eq!(
+        scheduler.process(pid).map(|p| p.state()),
+        Some(ProcessState::Waiting { nest })
+    );
+    scheduler.tick(&mut state, 4);
+    assert!(!scheduler.is_running());
+
+    let message = state.add_widget(WidgetItem::Number(Number::new(7)), origin());
+    assert!(ops::give_to_nest(&mut state, message, nest));
+    scheduler.tick(&mut state, 1);
+    assert_eq!(
+        scheduler.process(pid).map(|p| p.state()),
+        Some(ProcessState::Running)
+    );
+
+    // Taking the message and vacuuming it leaves the nest empty again
+    scheduler.tick(&mut state, 2);
+    assert_eq!(
+        scheduler.process(pid).map(|p| p.state()),
+        Some(ProcessState::Waiting { nest })
+    );
+    match state.widgets.get(&nest) {
+        Some(WidgetItem::Nest(n)) => assert!(!n.has_messages()),
+        _ => panic!("nest missing"),
+    }
+    assert_eq!(scheduler.live_process_of(rid).map(|p| p.id()), Some(pid));
+}
+
+#[test]
+fn test_waiting_process_stops_when_its_nest_is_gone() {
+    let mut state = EngineState::new();
+    let mut r = Robot::new();
+    let mut pattern = ToonBox::new(1);
+    pattern.set_hole(0, Box::new(Number::erased()));
+    r.set_pattern(pattern);
+    r.set_actions(vec![Action::TakeFromNest {
+        path: "input/hole:0".to_string(),
+    }]);
+    let rid = state.add_widget(WidgetItem::Robot(r), origin());
+    let (box_id, nest) = box_with(&mut state, WidgetItem::Nest(Nest::new()));
+
+    let mut scheduler = Scheduler::new();
+    let pid = scheduler.spawn(&mut state, rid, Some(box_id));
+    assert_eq!(
+        scheduler.process(pid).map(|p| p.state()),
+        Some(ProcessState::Waiting { nest })
+    );
+
+    ops::take_from_hole(&mut state, box_id, 0, origin());
+    assert!(ops::vacuum_widget(&mut state, nest));
+    assert!(scheduler.wake(&mut state));
+    assert_eq!(
+        scheduler.process(pid).map(|p| p.state()),
+        Some(ProcessState::Finished)
+    );
+    match state.widgets.get(&rid) {
+        Some(WidgetItem::Robot(r)) => assert_eq!(r.stop_reason(), Some(StopReason::NestGone)),
+        _ => panic!("robot missing"),
+    }
+}
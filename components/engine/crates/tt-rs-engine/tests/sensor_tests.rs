@@ -0,0 +1,221 @@
+//! Native tests for sensors: key presses and clicks reported to nests.
+
+use tt_rs_box::ToonBox;
+use tt_rs_core::WidgetId;
+use tt_rs_engine::scheduler::{ProcessState, Scheduler};
+use tt_rs_engine::{ops, BoxState, EngineState, Position, WidgetItem};
+use tt_rs_number::Number;
+use tt_rs_robot::{Action, Robot};
+use tt_rs_sensors::SensorKind;
+use tt_rs_text::Text;
+
+fn origin() -> Position {
+    Position::new(0.0, 0.0)
+}
+
+fn nest_of(state: &EngineState, sensor: WidgetId) -> WidgetId {
+    match state.widgets.get(&sensor) {
+        Some(WidgetItem::Sensor(s)) => s.nest_id().expect("sensor has a nest"),
+        _ => panic!("not a sensor"),
+    }
+}
+
+fn messages(state: &EngineState, nest: WidgetId) -> Vec<String> {
+    let mut nest = match state.widgets.get(&nest) {
+        Some(WidgetItem::Nest(n)) => n.clone(),
+        _ => panic!("not a nest"),
+    };
+    std::iter::from_fn(|| nest.take())
+        .map(|m| m.description())
+        .collect()
+}
+
+#[test]
+fn test_key_presses_reach_the_sensors_nest() {
+    let mut state = EngineState::new();
+    let sensor = ops::hatch_sensor(&mut state, SensorKind::Keyboard, origin());
+    let nest = nest_of(&state, sensor);
+
+    assert_eq!(ops::sense_key(&mut state, "a"), 1);
+    assert_eq!(ops::sense_key(&mut state, "Enter"), 1);
+    assert_eq!(messages(&state, nest), ["text \"a\"", "text \"Enter\""]);
+}
+
+#[test]
+fn test_paused_sensors_and_switched_off_workspaces_sense_nothing() {
+    let mut state = EngineState::new();
+    let sensor = ops::hatch_sensor(&mut state, SensorKind::Keyboard, origin());
+    let nest = nest_of(&state, sensor);
+
+    assert!(ops::toggle_sensor(&mut state, sensor));
+    assert_eq!(ops::sense_key(&mut state, "a"), 0);
+    assert!(ops::toggle_sensor(&mut state, sensor));
+    state.sensors_on = false;
+    assert_eq!(ops::sense_key(&mut state, "b"), 0);
+    state.sensors_on = true;
+    assert_eq!(ops::sense_key(&mut state, "c"), 1);
+    assert_eq!(messages(&state, nest), ["text \"c\""]);
+}
+
+#[test]
+fn test_copied_sensors_report_to_the_right_nest() {
+    let mut state = EngineState::new();
+    let sensor = ops::hatch_sensor(&mut state, SensorKind::Keyboard, origin());
+    let nest = nest_of(&state, sensor);
+
+    // A wand copy is another sensor on the same nest
+    let copy = ops::wand_copy_widget(&mut state, sensor).expect("copied");
+    assert_eq!(nest_of(&state, copy), nest);
+
+    // Copied in a box along with its nest, it reports to the new nest
+    let box_id = state.add_box(BoxState::new(2), origin());
+    ops::drop_in_hole(&mut state, sensor, box_id, 0, origin());
+    ops::drop_in_hole(&mut state, nest, box_id, 1, origin());
+    let src = state.boxes[&box_id].clone();
+    let copied = ops::deep_copy_box(&mut state, &src);
+    let copied_sensor = copied.widget_in_hole(0).expect("sensor copied");
+    let copied_nest = copied.widget_in_hole(1).expect("nest copied");
+    assert_eq!(nest_of(&state, copied_sensor), copied_nest);
+
+    assert_eq!(ops::sense_key(&mut state, "x"), 3);
+    assert_eq!(messages(&state, nest), ["text \"x\"", "text \"x\""]);
+    assert_eq!(messages(&state, copied_nest), ["text \"x\""]);
+}
+
+#[test]
+fn test_key_press_for_a_nest_elsewhere_goes_to_the_outbox() {
+    let mut state = EngineState::new();
+    let sensor = ops::hatch_sensor(&mut state, SensorKind::Keyboard, origin());
+    let nest = nest_of(&state, sensor);
+    state.remove_widget(nest);
+
+    assert_eq!(ops::sense_key(&mut state, "q"), 1);
+    assert!(matches!(
+        state.outbox.as_slice(),
+        [mail] if mail.nest == nest && mail.message.description() == "text \"q\""
+    ));
+}
+
+#[test]
+fn test_robot_waiting_on_a_sensor_reacts_to_typing() {
+    let mut state = EngineState::new();
+    let sensor = ops::hatch_sensor(&mut state, SensorKind::Keyboard, origin());
+    let nest = nest_of(&state, sensor);
+    let box_id = state.add_box(BoxState::new(1), origin());
+    ops::drop_in_hole(&mut state, nest, box_id, 0, origin());
+
+    let mut robot = Robot::new();
+    let mut pattern = ToonBox::new(1);
+    pattern.set_hole(0, Box::new(Text::erased()));
+    robot.set_pattern(pattern);
+    robot.set_actions(vec![Action::TakeFromNest {
+        path: "input/hole:0".to_string(),
+    }]);
+    let rid = state.add_widget(WidgetItem::Robot(robot), origin());
+    let mut scheduler = Scheduler::new();
+    let pid = scheduler.spawn(&mut state, rid, Some(box_id));
+    assert_eq!(
+        scheduler.process(pid).map(|p| p.state()),
+        Some(ProcessState::Waiting { nest })
+    );
+
+    ops::sense_key(&mut state, "k");
+    assert!(scheduler.wake(&mut state));
+    scheduler.tick(&mut state, 4);
+    assert!(state
+        .widgets
+        .values()
+        .any(|w| matches!(w, WidgetItem::Text(t) if t.value() == "k")));
+}
+
+#[test]
+fn test_reclaimed_sensor_leaves_its_nest_to_be_checked() {
+    let mut state = EngineState::new();
+    let sensor = ops::hatch_sensor(&mut state, SensorKind::Keyboard, origin());
+    let nest = nest_of(&state, sensor);
+
+    assert_eq!(ops::reclaim(&mut state, &[sensor]), vec![nest]);
+    assert!(!state.widgets.contains_key(&sensor));
+}
+
+/// Take the top message out of a nest: a click comes out as a box.
+fn take_click(state: &mut EngineState, nest: WidgetId) -> (i64, i64, String) {
+    let id = ops::take_from_nest(state, nest, origin()).expect("a message");
+    let b = state.boxes.get(&id).expect("a box");
+    let hole = |i| b.widget_in_hole(i).and_then(|w| state.widgets.get(&w));
+    match (hole(0), hole(1), hole(2)) {
+        (Some(WidgetItem::Number(x)), Some(WidgetItem::Number(y)), Some(WidgetItem::Text(t))) => {
+            (x.numerator(), y.numerator(), t.value().to_string())
+        }
+        _ => panic!("click should be [x, y, target]"),
+    }
+}
+
+#[test]
+fn test_clicks_reach_pointer_sensors_as_boxes() {
+    let mut state = EngineState::new();
+    let pointer = ops::hatch_sensor(&mut state, SensorKind::Pointer, origin());
+    let keyboard = ops::hatch_sensor(&mut state, SensorKind::Keyboard, origin());
+    let nest = nest_of(&state, pointer);
+    let n = state.add_widget(WidgetItem::Number(Number::new(4)), origin());
+    let box_id = state.add_box(BoxState::new(1), origin());
+
+    let at = Position::new(12.4, 30.6);
+    assert_eq!(ops::sense_click(&mut state, at, Some(n)), 1);
+    assert_eq!(ops::sense_click(&mut state, at, Some(box_id)), 1);
+    assert_eq!(ops::sense_click(&mut state, at, None), 1);
+    assert_eq!(ops::sense_key(&mut state, "a"), 1);
+
+    assert_eq!(take_click(&mut state, nest), (12, 31, "number".to_string()));
+    assert_eq!(take_click(&mut state, nest), (12, 31, "box".to_string()));
+    assert_eq!(
+        take_click(&mut state, nest),
+        (12, 31, "workspace".to_string())
+    );
+    assert!(messages(&state, nest).is_empty());
+    assert_eq!(messages(&state, nest_of(&state, keyboard)), ["text \"a\""]);
+}
+
+#[test]
+fn test_clicks_for_a_nest_elsewhere_go_to_the_outbox() {
+    let mut state = EngineState::new();
+    let sensor = ops::hatch_sensor(&mut state, SensorKind::Pointer, origin());
+    let nest = nest_of(&state, sensor);
+    state.remove_widget(nest);
+
+    assert_eq!(ops::sense_click(&mut state, origin(), None), 1);
+    assert!(matches!(
+        state.outbox.as_slice(),
+        [mail] if mail.nest == nest && mail.message.type_name() == "box"
+    ));
+}
+
+#[test]
+fn test_robot_waiting_on_a_pointer_sensor_gets_the_click() {
+    let mut state = EngineState::new();
+    let sensor = ops::hatch_sensor(&mut state, SensorKind::Pointer, origin());
+    let nest = nest_of(&state, sensor);
+    let box_id = state.add_box(BoxState::new(1), origin());
+    ops::drop_in_hole(&mut state, nest, box_id, 0, origin());
+
+    let mut robot = Robot::new();
+    let mut pattern = ToonBox::new(1);
+    pattern.set_hole(0, Box::new(ToonBox::erased_with_size(3)));
+    robot.set_pattern(pattern);
+    robot.set_actions(vec![Action::TakeFromNest {
+        path: "input/hole:0".to_string(),
+    }]);
+    let rid = state.add_widget(WidgetItem::Robot(robot), origin());
+    let mut scheduler = Scheduler::new();
+    let pid = scheduler.spawn(&mut state, rid, Some(box_id));
+    assert_eq!(
+        scheduler.process(pid).map(|p| p.state()),
+        Some(ProcessState::Waiting { nest })
+    );
+
+    ops::sense_click(&mut state, Position::new(5.0, 6.0), None);
+    assert!(scheduler.wake(&mut state));
+    scheduler.tick(&mut state, 4);
+    assert!(messages(&state, nest).is_empty());
+    assert_eq!(state.boxes.len(), 2);
+}
@@ -0,0 +1,24 @@
+//! tt-rs-sensors: Sensor widgets that turn user input into messages.
+//!
+//! A sensor watches for something happening outside the workspace and
+//! reports each occurrence as a message in its nest, so robots waiting
+//! on the nest can react. Like a bird, a sensor is bonded to a nest and
+//! wears its channel. Unlike ToonTalk, where a bird carries what a
+//! sensor senses, the message lands in the nest at once, with no flight.
+//! A paused sensor reports nothing.
+//!
+//! # Module Organization
+//!
+//! - [`Sensor`] - struct and constructors
+//! - [`SensorKind`] - what a sensor senses
+//! - `ops` - accessor methods
+//! - `mutators` - pause, bond and copy operations
+//! - `rendering` - HTML rendering
+
+mod mutators;
+mod ops;
+mod rendering;
+mod sensor;
+mod widget_impl;
+
+pub use sensor::{Sensor, SensorKind};
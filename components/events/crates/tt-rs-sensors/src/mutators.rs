@@ -0,0 +1,47 @@
+//! Mutator methods for Sensor.
+
+use tt_rs_core::{Widget, WidgetId};
+use tt_rs_nest::{Channel, Nest};
+
+use crate::Sensor;
+
+impl Sensor {
+    /// Marks this sensor as a copy source (for palette).
+    pub fn as_copy_source(mut self) -> Self {
+        self.is_copy_source = true;
+        self
+    }
+
+    /// Returns this sensor, paused or not.
+    pub fn with_paused(mut self, paused: bool) -> Self {
+        self.paused = paused;
+        self
+    }
+
+    /// Pauses a sensor that is sensing, or resumes a paused one.
+    pub fn toggle_paused(&mut self) {
+        self.paused = !self.paused;
+    }
+
+    /// Bonds this sensor to a nest: from now on it reports there, and
+    /// wears the nest's channel.
+    pub fn bond(&mut self, nest: &Nest) {
+        self.bond_to(nest.id(), nest.channel());
+    }
+
+    /// Bonds this sensor to the nest with this ID and channel.
+    pub fn bond_to(&mut self, nest_id: WidgetId, channel: Channel) {
+        self.nest_id = Some(nest_id);
+        self.channel = Some(channel);
+    }
+
+    /// Creates a copy of this sensor with a new ID: another sensor
+    /// reporting to the same nest.
+    pub fn copy_sensor(&self) -> Self {
+        Self {
+            id: WidgetId::new(),
+            is_copy_source: false,
+            ..self.clone()
+        }
+    }
+}
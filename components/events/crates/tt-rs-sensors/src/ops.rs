@@ -0,0 +1,39 @@
+//! Accessor methods for Sensor.
+
+use tt_rs_core::WidgetId;
+use tt_rs_nest::Channel;
+
+use crate::{Sensor, SensorKind};
+
+impl Sensor {
+    /// Returns what the sensor senses.
+    pub fn kind(&self) -> SensorKind {
+        self.kind
+    }
+
+    /// Returns the channel of the sensor's nest, if it has one.
+    pub fn channel(&self) -> Option<Channel> {
+        self.channel
+    }
+
+    /// Returns the ID of the nest the sensor reports to, if any.
+    pub fn nest_id(&self) -> Option<WidgetId> {
+        self.nest_id
+    }
+
+    /// Returns true if the sensor is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Returns true if the sensor reports what it senses: it is not a
+    /// palette item, is not paused and has a nest to report to.
+    pub fn is_active(&self) -> bool {
+        !self.is_copy_source && !self.paused && self.nest_id.is_some()
+    }
+
+    /// Returns true if this is a copy source (palette item).
+    pub fn is_copy_source(&self) -> bool {
+        self.is_copy_source
+    }
+}
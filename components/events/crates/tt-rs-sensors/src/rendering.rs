@@ -0,0 +1,32 @@
+//! Rendering functions for Sensor.
+
+use crate::Sensor;
+use yew::prelude::*;
+
+/// Renders a Sensor using the tt-sensor.svg asset, labelled with what
+/// it senses. A paused sensor is dimmed.
+pub fn render(sensor: &Sensor) -> Html {
+    let is_copy_source = sensor.is_copy_source();
+    let kind = sensor.kind().name();
+    let class = classes!(
+        "widget",
+        "sensor",
+        kind,
+        is_copy_source.then_some("copy-source"),
+        sensor.is_paused().then_some("paused"),
+    );
+
+    html! {
+        <div class={class}
+             data-widget-id={sensor.id.to_string()}
+             data-copy-source={is_copy_source.to_string()}>
+            <img src="images/tt-sensor.svg" alt="Sensor" class="sensor-img"/>
+            <span class="sensor-kind">{ kind }</span>
+            if !is_copy_source {
+                if let Some(channel) = sensor.channel() {
+                    { channel.render_tag() }
+                }
+            }
+        </div>
+    }
+}
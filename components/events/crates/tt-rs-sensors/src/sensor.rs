@@ -0,0 +1,67 @@
+//! Sensor struct and constructors.
+
+use tt_rs_core::WidgetId;
+use tt_rs_nest::{Channel, Nest};
+
+/// What a sensor senses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SensorKind {
+    /// Key presses, each reported as a text message naming the key.
+    #[default]
+    Keyboard,
+    /// Clicks, each reported as a box of the x and y clicked and the
+    /// name of what was clicked.
+    Pointer,
+}
+
+impl SensorKind {
+    /// Returns the name shown on the sensor and used when saving it.
+    pub fn name(&self) -> &'static str {
+        match self {
+            SensorKind::Keyboard => "keyboard",
+            SensorKind::Pointer => "pointer",
+        }
+    }
+
+    /// Returns the kind with this name, if any.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "keyboard" => Some(SensorKind::Keyboard),
+            "pointer" => Some(SensorKind::Pointer),
+            _ => None,
+        }
+    }
+}
+
+/// A sensor widget that reports what it senses to its nest.
+#[derive(Debug, Clone)]
+pub struct Sensor {
+    pub(crate) id: WidgetId,
+    pub(crate) kind: SensorKind,
+    /// The channel of the nest the sensor reports to (None: no nest).
+    pub(crate) channel: Option<Channel>,
+    pub(crate) nest_id: Option<WidgetId>,
+    pub(crate) paused: bool,
+    pub(crate) is_copy_source: bool,
+}
+
+impl Sensor {
+    /// Creates a new sensor of this kind with no nest.
+    pub fn new(kind: SensorKind) -> Self {
+        Self {
+            id: WidgetId::new(),
+            kind,
+            channel: None,
+            nest_id: None,
+            paused: false,
+            is_copy_source: false,
+        }
+    }
+
+    /// Creates a new sensor bonded to a nest.
+    pub fn for_nest(kind: SensorKind, nest: &Nest) -> Self {
+        let mut sensor = Self::new(kind);
+        sensor.bond(nest);
+        sensor
+    }
+}
@@ -0,0 +1,41 @@
+//! Widget trait implementation for Sensor.
+
+use crate::rendering;
+use crate::sensor::Sensor;
+use std::any::Any;
+use tt_rs_core::{MatchResult, Widget, WidgetId};
+use yew::Html;
+
+impl Widget for Sensor {
+    fn type_name(&self) -> &'static str {
+        "sensor"
+    }
+
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn copy(&self) -> Box<dyn Widget> {
+        Box::new(self.copy_sensor())
+    }
+
+    fn matches(&self, other: &dyn Widget) -> MatchResult {
+        if other.type_name() != "sensor" {
+            return MatchResult::NoMatch;
+        }
+        MatchResult::matched(other.id())
+    }
+
+    fn render(&self) -> Html {
+        rendering::render(self)
+    }
+
+    fn description(&self) -> String {
+        let paused = if self.paused { " (paused)" } else { "" };
+        format!("{} sensor{}", self.kind.name(), paused)
+    }
+}
@@ -36,3 +36,24 @@ fn try_find_box_hole(element: &Element) -> Option<(WidgetId, usize)> {
         .ok()?;
     Some((box_id, hole_index))
 }
+
+/// Find which hole of a given box is under the position.
+/// Unlike [`find_box_hole_at`] this also works for the box being dragged,
+/// so a click on one of its holes can be detected.
+pub fn find_hole_in_box_at(box_id: WidgetId, x: f64, y: f64) -> Option<usize> {
+    let document = web_sys::window()?.document()?;
+    let selector = format!(".box-hole[data-box-id=\"{}\"]", box_id);
+    let holes = document.query_selector_all(&selector).ok()?;
+
+    for i in 0..holes.length() {
+        let Some(hole) = holes.item(i).and_then(|n| n.dyn_into::<Element>().ok()) else {
+            continue;
+        };
+        let r = hole.get_bounding_client_rect();
+        if x >= r.left() && x <= r.right() && y >= r.top() && y <= r.bottom() {
+            return hole.get_attribute("data-hole-index")?.parse().ok();
+        }
+    }
+
+    None
+}
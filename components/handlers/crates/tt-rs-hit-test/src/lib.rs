@@ -4,11 +4,13 @@ mod box_hole;
 mod dom_helpers;
 mod dropzone;
 mod number;
+mod pattern;
 mod scales;
 mod widget;
 
-pub use box_hole::find_box_hole_at;
+pub use box_hole::{find_box_hole_at, find_hole_in_box_at};
 pub use dropzone::find_dropzone_at;
 pub use number::find_number_at;
+pub use pattern::find_pattern_part_at;
 pub use scales::{ScalesPan, find_scales_pan_at};
 pub use widget::find_widget_at_excluding;
@@ -0,0 +1,38 @@
+//! Robot thought-bubble hit testing.
+
+use tt_rs_core::WidgetId;
+use wasm_bindgen::JsCast;
+use web_sys::Element;
+
+use crate::dom_helpers::{
+    elements_at_point, find_ancestor_with_class, is_inside_dragging, parse_widget_id_attr,
+};
+
+/// Find the part of a robot's pattern under the given position.
+/// Returns the robot and the part's hole path (empty for the whole box).
+pub fn find_pattern_part_at(x: f64, y: f64) -> Option<(WidgetId, Vec<usize>)> {
+    let elements = elements_at_point(x, y)?;
+
+    for i in 0..elements.length() {
+        if let Ok(element) = elements.get(i).dyn_into::<Element>()
+            && !is_inside_dragging(&element)
+            && let Some(result) = try_find_pattern_part(&element)
+        {
+            return Some(result);
+        }
+    }
+    None
+}
+
+fn try_find_pattern_part(element: &Element) -> Option<(WidgetId, Vec<usize>)> {
+    let part = find_ancestor_with_class(element, "thought-part")?;
+    let robot = find_ancestor_with_class(&part, "robot")?;
+    let robot_id = parse_widget_id_attr(&robot, "data-widget-id")?;
+    let path = part.get_attribute("data-pattern-path")?;
+    let path = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(|s| s.parse().ok())
+        .collect::<Option<Vec<usize>>>()?;
+    Some((robot_id, path))
+}
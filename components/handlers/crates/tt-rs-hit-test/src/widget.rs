@@ -12,12 +12,11 @@ pub fn find_widget_at_excluding(x: f64, y: f64, skip_id: WidgetId) -> Option<(Wi
     let elements = elements_at_point(x, y)?;
 
     for i in 0..elements.length() {
-        if let Ok(element) = elements.get(i).dyn_into::<Element>() {
-            if let Some(widget_element) = find_widget_element(&element) {
-                if let Some(result) = try_extract_widget_id(&widget_element, skip_id) {
-                    return Some(result);
-                }
-            }
+        if let Ok(element) = elements.get(i).dyn_into::<Element>()
+            && let Some(widget_element) = find_widget_element(&element)
+            && let Some(result) = try_extract_widget_id(&widget_element, skip_id)
+        {
+            return Some(result);
         }
     }
     None
@@ -40,16 +39,16 @@ fn find_widget_element(element: &Element) -> Option<Element> {
 }
 
 fn try_extract_widget_id(element: &Element, skip_id: WidgetId) -> Option<(WidgetId, bool)> {
-    if let Some(id) = parse_widget_id_attr(element, "data-widget-id") {
-        if id != skip_id {
-            return Some((id, false));
-        }
+    if let Some(id) = parse_widget_id_attr(element, "data-widget-id")
+        && id != skip_id
+    {
+        return Some((id, false));
     }
 
-    if let Some(id) = parse_widget_id_attr(element, "data-box-id") {
-        if id != skip_id {
-            return Some((id, true));
-        }
+    if let Some(id) = parse_widget_id_attr(element, "data-box-id")
+        && id != skip_id
+    {
+        return Some((id, true));
     }
 
     None
@@ -0,0 +1,26 @@
+//! Bomb struct and constructors.
+
+use tt_rs_core::WidgetId;
+
+/// A bomb widget: when a robot sets it off, its process ends.
+#[derive(Debug, Clone)]
+pub struct Bomb {
+    pub(crate) id: WidgetId,
+    pub(crate) is_copy_source: bool,
+}
+
+impl Bomb {
+    /// Creates a new bomb.
+    pub fn new() -> Self {
+        Self {
+            id: WidgetId::new(),
+            is_copy_source: false,
+        }
+    }
+}
+
+impl Default for Bomb {
+    fn default() -> Self {
+        Self::new()
+    }
+}
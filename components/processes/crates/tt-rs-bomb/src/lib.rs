@@ -0,0 +1,21 @@
+//! tt-rs-bomb: Bomb widget for stopping processes.
+//!
+//! A robot that sets off a bomb ends the process it belongs to: the
+//! house it works in is torn down and everything inside is thrown away.
+//! Nests whose birds all went with it learn that no message will ever
+//! come, so robots waiting on them stop instead of waiting forever.
+//!
+//! # Module Organization
+//!
+//! - [`Bomb`] - struct and constructors
+//! - `ops` - accessor methods
+//! - `mutators` - copy operations
+//! - `rendering` - HTML rendering
+
+mod bomb;
+mod mutators;
+mod ops;
+mod rendering;
+mod widget_impl;
+
+pub use bomb::Bomb;
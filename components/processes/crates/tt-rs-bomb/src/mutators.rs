@@ -0,0 +1,16 @@
+//! Mutator methods for Bomb.
+
+use crate::Bomb;
+
+impl Bomb {
+    /// Marks this bomb as a copy source (for palette).
+    pub fn as_copy_source(mut self) -> Self {
+        self.is_copy_source = true;
+        self
+    }
+
+    /// Creates a new bomb.
+    pub fn copy_bomb(&self) -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,10 @@
+//! Accessor methods for Bomb.
+
+use crate::Bomb;
+
+impl Bomb {
+    /// Returns true if this is a copy source (palette item).
+    pub fn is_copy_source(&self) -> bool {
+        self.is_copy_source
+    }
+}
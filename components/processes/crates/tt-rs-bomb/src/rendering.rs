@@ -0,0 +1,22 @@
+//! Rendering functions for Bomb.
+
+use crate::Bomb;
+use yew::prelude::*;
+
+/// Renders a Bomb using the tt-bomb.svg asset.
+pub fn render(bomb: &Bomb) -> Html {
+    let is_copy_source = bomb.is_copy_source();
+    let class = if is_copy_source {
+        "widget bomb copy-source"
+    } else {
+        "widget bomb"
+    };
+
+    html! {
+        <div class={class}
+             data-widget-id={bomb.id.to_string()}
+             data-copy-source={is_copy_source.to_string()}>
+            <img src="images/tt-bomb.svg" alt="Bomb" class="bomb-img"/>
+        </div>
+    }
+}
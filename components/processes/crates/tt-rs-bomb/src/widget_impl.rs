@@ -0,0 +1,40 @@
+//! Widget trait implementation for Bomb.
+
+use crate::bomb::Bomb;
+use crate::rendering;
+use std::any::Any;
+use tt_rs_core::{MatchResult, Widget, WidgetId};
+use yew::Html;
+
+impl Widget for Bomb {
+    fn type_name(&self) -> &'static str {
+        "bomb"
+    }
+
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn copy(&self) -> Box<dyn Widget> {
+        Box::new(self.copy_bomb())
+    }
+
+    fn matches(&self, other: &dyn Widget) -> MatchResult {
+        if other.type_name() != "bomb" {
+            return MatchResult::NoMatch;
+        }
+        MatchResult::matched(other.id())
+    }
+
+    fn render(&self) -> Html {
+        rendering::render(self)
+    }
+
+    fn description(&self) -> String {
+        "bomb".to_string()
+    }
+}
@@ -0,0 +1,40 @@
+//! House struct and constructors.
+
+use tt_rs_core::WidgetId;
+
+/// A house widget: the outside of an isolated workspace.
+#[derive(Debug, Clone)]
+pub struct House {
+    pub(crate) id: WidgetId,
+    pub(crate) status: HouseStatus,
+    pub(crate) is_copy_source: bool,
+}
+
+/// What the robots inside a house are doing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HouseStatus {
+    /// No robot is working or waiting.
+    #[default]
+    Quiet,
+    /// At least one robot is working.
+    Running,
+    /// Robots are waiting on nests, none is working.
+    Waiting,
+}
+
+impl House {
+    /// Creates a new, quiet house.
+    pub fn new() -> Self {
+        Self {
+            id: WidgetId::new(),
+            status: HouseStatus::default(),
+            is_copy_source: false,
+        }
+    }
+}
+
+impl Default for House {
+    fn default() -> Self {
+        Self::new()
+    }
+}
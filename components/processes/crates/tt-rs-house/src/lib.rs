@@ -0,0 +1,21 @@
+//! tt-rs-house: House widget for isolated processes.
+//!
+//! A house is where robots work. Each house has its own workspace of
+//! widgets, boxes and running robots (kept by the engine's city), so
+//! what happens inside one house can't touch another. Houses talk to
+//! each other only through bird/nest pairs.
+//!
+//! # Module Organization
+//!
+//! - [`House`] - struct and constructors
+//! - `ops` - accessor methods
+//! - `mutators` - status and copy operations
+//! - `rendering` - HTML rendering
+
+mod house;
+mod mutators;
+mod ops;
+mod rendering;
+mod widget_impl;
+
+pub use house::{House, HouseStatus};
@@ -0,0 +1,27 @@
+//! Mutator methods for House.
+
+use tt_rs_core::WidgetId;
+
+use crate::{House, HouseStatus};
+
+impl House {
+    /// Sets what the robots inside are doing.
+    pub fn set_status(&mut self, status: HouseStatus) {
+        self.status = status;
+    }
+
+    /// Marks this house as a copy source (for palette).
+    pub fn as_copy_source(mut self) -> Self {
+        self.is_copy_source = true;
+        self
+    }
+
+    /// Creates a new, quiet house (the inside is not copied).
+    pub fn copy_house(&self) -> Self {
+        Self {
+            id: WidgetId::new(),
+            status: HouseStatus::Quiet,
+            is_copy_source: false,
+        }
+    }
+}
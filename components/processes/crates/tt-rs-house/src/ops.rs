@@ -0,0 +1,31 @@
+//! Accessor methods for House.
+
+use crate::{House, HouseStatus};
+
+impl House {
+    /// Returns what the robots inside are doing.
+    pub fn status(&self) -> HouseStatus {
+        self.status
+    }
+
+    /// Returns true if a robot inside is working.
+    pub fn is_running(&self) -> bool {
+        self.status == HouseStatus::Running
+    }
+
+    /// Returns true if this is a copy source (palette item).
+    pub fn is_copy_source(&self) -> bool {
+        self.is_copy_source
+    }
+}
+
+impl HouseStatus {
+    /// Display name for the status.
+    pub fn name(&self) -> &'static str {
+        match self {
+            HouseStatus::Quiet => "quiet",
+            HouseStatus::Running => "running",
+            HouseStatus::Waiting => "waiting",
+        }
+    }
+}
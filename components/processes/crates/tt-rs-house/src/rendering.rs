@@ -0,0 +1,26 @@
+//! Rendering functions for House.
+
+use crate::House;
+use yew::prelude::*;
+
+/// Renders a House using the tt-house.svg asset, with its status below.
+pub fn render(house: &House) -> Html {
+    let is_copy_source = house.is_copy_source();
+    let status = house.status().name();
+    let class = if is_copy_source {
+        "widget house copy-source".to_string()
+    } else {
+        format!("widget house {}", status)
+    };
+
+    html! {
+        <div class={class}
+             data-widget-id={house.id.to_string()}
+             data-copy-source={is_copy_source.to_string()}>
+            <img src="images/tt-house.svg" alt="House" class="house-img"/>
+            if !is_copy_source {
+                <span class="house-status">{ status }</span>
+            }
+        </div>
+    }
+}
@@ -0,0 +1,40 @@
+//! Widget trait implementation for House.
+
+use crate::house::House;
+use crate::rendering;
+use std::any::Any;
+use tt_rs_core::{MatchResult, Widget, WidgetId};
+use yew::Html;
+
+impl Widget for House {
+    fn type_name(&self) -> &'static str {
+        "house"
+    }
+
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn copy(&self) -> Box<dyn Widget> {
+        Box::new(self.copy_house())
+    }
+
+    fn matches(&self, other: &dyn Widget) -> MatchResult {
+        if other.type_name() != "house" {
+            return MatchResult::NoMatch;
+        }
+        MatchResult::matched(other.id())
+    }
+
+    fn render(&self) -> Html {
+        rendering::render(self)
+    }
+
+    fn description(&self) -> String {
+        format!("house ({})", self.status.name())
+    }
+}
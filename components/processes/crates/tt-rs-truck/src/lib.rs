@@ -0,0 +1,22 @@
+//! tt-rs-truck: Truck widget for spawning processes.
+//!
+//! Load a robot and a box onto a truck and it drives off to build a new
+//! house, where the robot works on the box on its own. Birds in the box
+//! still fly to their nests back home, so the new process can report
+//! what it finds. This is how ToonTalk spawns (and, with nests, joins)
+//! processes.
+//!
+//! # Module Organization
+//!
+//! - [`Truck`] - struct and constructors
+//! - `ops` - accessor methods
+//! - `mutators` - loading and copy operations
+//! - `rendering` - HTML rendering
+
+mod mutators;
+mod ops;
+mod rendering;
+mod truck;
+mod widget_impl;
+
+pub use truck::Truck;
@@ -0,0 +1,44 @@
+//! Mutator methods for Truck.
+
+use tt_rs_core::WidgetId;
+
+use crate::Truck;
+
+impl Truck {
+    /// Loads a robot. Returns false if one is already loaded.
+    pub fn load_robot(&mut self, robot: WidgetId) -> bool {
+        if self.robot.is_some() {
+            return false;
+        }
+        self.robot = Some(robot);
+        true
+    }
+
+    /// Loads a box. Returns false if one is already loaded.
+    pub fn load_box(&mut self, cargo: WidgetId) -> bool {
+        if self.cargo.is_some() {
+            return false;
+        }
+        self.cargo = Some(cargo);
+        true
+    }
+
+    /// Takes the robot and box off a full truck, leaving it empty.
+    pub fn unload(&mut self) -> Option<(WidgetId, WidgetId)> {
+        if !self.is_full() {
+            return None;
+        }
+        Some((self.robot.take()?, self.cargo.take()?))
+    }
+
+    /// Marks this truck as a copy source (for palette).
+    pub fn as_copy_source(mut self) -> Self {
+        self.is_copy_source = true;
+        self
+    }
+
+    /// Creates a new, empty truck.
+    pub fn copy_truck(&self) -> Self {
+        Self::new()
+    }
+}
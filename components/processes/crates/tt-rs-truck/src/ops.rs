@@ -0,0 +1,27 @@
+//! Accessor methods for Truck.
+
+use tt_rs_core::WidgetId;
+
+use crate::Truck;
+
+impl Truck {
+    /// Returns the robot loaded, if any.
+    pub fn robot(&self) -> Option<WidgetId> {
+        self.robot
+    }
+
+    /// Returns the box loaded, if any.
+    pub fn cargo(&self) -> Option<WidgetId> {
+        self.cargo
+    }
+
+    /// Returns true if both a robot and a box are loaded.
+    pub fn is_full(&self) -> bool {
+        self.robot.is_some() && self.cargo.is_some()
+    }
+
+    /// Returns true if this is a copy source (palette item).
+    pub fn is_copy_source(&self) -> bool {
+        self.is_copy_source
+    }
+}
@@ -0,0 +1,28 @@
+//! Rendering functions for Truck.
+
+use crate::Truck;
+use yew::prelude::*;
+
+/// Renders a Truck using the tt-truck.svg asset, with what it carries.
+pub fn render(truck: &Truck) -> Html {
+    let is_copy_source = truck.is_copy_source();
+    let mut class = String::from("widget truck");
+    if is_copy_source {
+        class.push_str(" copy-source");
+    }
+    let loaded: Vec<&str> = [truck.robot().map(|_| "robot"), truck.cargo().map(|_| "box")]
+        .into_iter()
+        .flatten()
+        .collect();
+
+    html! {
+        <div class={class}
+             data-widget-id={truck.id.to_string()}
+             data-copy-source={is_copy_source.to_string()}>
+            <img src="images/tt-truck.svg" alt="Truck" class="truck-img"/>
+            if !loaded.is_empty() {
+                <span class="truck-load">{ loaded.join(" + ") }</span>
+            }
+        </div>
+    }
+}
@@ -0,0 +1,32 @@
+//! Truck struct and constructors.
+
+use tt_rs_core::WidgetId;
+
+/// A truck widget: carries a robot and a box off to a new house.
+#[derive(Debug, Clone)]
+pub struct Truck {
+    pub(crate) id: WidgetId,
+    /// The robot loaded, if any.
+    pub(crate) robot: Option<WidgetId>,
+    /// The box loaded, if any.
+    pub(crate) cargo: Option<WidgetId>,
+    pub(crate) is_copy_source: bool,
+}
+
+impl Truck {
+    /// Creates a new, empty truck.
+    pub fn new() -> Self {
+        Self {
+            id: WidgetId::new(),
+            robot: None,
+            cargo: None,
+            is_copy_source: false,
+        }
+    }
+}
+
+impl Default for Truck {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,45 @@
+//! Widget trait implementation for Truck.
+
+use crate::rendering;
+use crate::truck::Truck;
+use std::any::Any;
+use tt_rs_core::{MatchResult, Widget, WidgetId};
+use yew::Html;
+
+impl Widget for Truck {
+    fn type_name(&self) -> &'static str {
+        "truck"
+    }
+
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn copy(&self) -> Box<dyn Widget> {
+        Box::new(self.copy_truck())
+    }
+
+    fn matches(&self, other: &dyn Widget) -> MatchResult {
+        if other.type_name() != "truck" {
+            return MatchResult::NoMatch;
+        }
+        MatchResult::matched(other.id())
+    }
+
+    fn render(&self) -> Html {
+        rendering::render(self)
+    }
+
+    fn description(&self) -> String {
+        match (self.robot, self.cargo) {
+            (None, None) => "truck".to_string(),
+            (Some(_), None) => "truck with a robot".to_string(),
+            (None, Some(_)) => "truck with a box".to_string(),
+            (Some(_), Some(_)) => "truck with a robot and a box".to_string(),
+        }
+    }
+}
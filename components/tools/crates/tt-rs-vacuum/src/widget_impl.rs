@@ -1,6 +1,7 @@
 //! Widget trait implementation for Vacuum.
 
 use crate::vacuum::Vacuum;
+use std::any::Any;
 use tt_rs_core::{MatchResult, Widget, WidgetId};
 use yew::prelude::*;
 
@@ -13,13 +14,17 @@ impl Widget for Vacuum {
         self.id
     }
 
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
     fn copy(&self) -> Box<dyn Widget> {
         Box::new(self.copy_vacuum())
     }
 
     fn matches(&self, other: &dyn Widget) -> MatchResult {
         if other.type_name() == "vacuum" {
-            MatchResult::Match
+            MatchResult::matched(other.id())
         } else {
             MatchResult::NoMatch
         }
@@ -1,6 +1,7 @@
 //! Widget trait implementation for Wand.
 
 use crate::wand::Wand;
+use std::any::Any;
 use tt_rs_core::{MatchResult, Widget, WidgetId};
 use yew::prelude::*;
 
@@ -13,13 +14,17 @@ impl Widget for Wand {
         self.id
     }
 
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
     fn copy(&self) -> Box<dyn Widget> {
         Box::new(self.copy_wand())
     }
 
     fn matches(&self, other: &dyn Widget) -> MatchResult {
         if other.type_name() == "wand" {
-            MatchResult::Match
+            MatchResult::matched(other.id())
         } else {
             MatchResult::NoMatch
         }
@@ -11,6 +11,16 @@ pub enum ErasureLevel {
     Value,
 }
 
+impl ErasureLevel {
+    /// The next, more general level, if any.
+    pub fn next(self) -> Option<Self> {
+        match self {
+            ErasureLevel::None => Some(ErasureLevel::Value),
+            ErasureLevel::Value => None,
+        }
+    }
+}
+
 /// A number widget representing a rational value.
 ///
 /// # Operations
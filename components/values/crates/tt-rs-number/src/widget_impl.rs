@@ -2,6 +2,7 @@
 
 use crate::number::{ErasureLevel, Number};
 use crate::rendering;
+use std::any::Any;
 use tt_rs_core::{MatchResult, Widget, WidgetId};
 use yew::Html;
 
@@ -14,16 +15,34 @@ impl Widget for Number {
         self.id
     }
 
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
     fn copy(&self) -> Box<dyn Widget> {
         Box::new(self.copy_number())
     }
 
+    fn erase(&self) -> Option<Box<dyn Widget>> {
+        let erasure = self.erasure.next()?;
+        Some(Box::new(Number {
+            erasure,
+            ..self.clone()
+        }))
+    }
+
     fn matches(&self, other: &dyn Widget) -> MatchResult {
-        if other.type_name() != "number" {
-            return MatchResult::NoMatch;
-        }
-        if self.erasure == ErasureLevel::Value || other.description() == self.description() {
-            MatchResult::Match
+        let other = match other.as_any().downcast_ref::<Number>() {
+            Some(n) => n,
+            None => return MatchResult::NoMatch,
+        };
+        // Rationals are kept reduced, so equal values have equal parts
+        let same_value = other.erasure == ErasureLevel::None
+            && self.numerator == other.numerator
+            && self.denominator == other.denominator
+            && self.operator == other.operator;
+        if self.erasure == ErasureLevel::Value || same_value {
+            MatchResult::matched(other.id())
         } else {
             MatchResult::NoMatch
         }
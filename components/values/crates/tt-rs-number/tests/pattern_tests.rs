@@ -1,20 +1,20 @@
 //! Pattern matching tests for Number widget.
 
-use tt_rs_core::{MatchResult, Widget};
-use tt_rs_number::Number;
+use tt_rs_core::{MatchResult, Widget, WidgetId};
+use tt_rs_number::{ArithOperator, Number};
 
 #[test]
 fn test_erased_matches_any() {
     let pattern = Number::erased();
     let value = Number::new(42);
-    assert_eq!(pattern.matches(&value), MatchResult::Match);
+    assert!(pattern.matches(&value).is_match());
 }
 
 #[test]
 fn test_exact_match() {
     let a = Number::new(42);
     let b = Number::new(42);
-    assert_eq!(a.matches(&b), MatchResult::Match);
+    assert!(a.matches(&b).is_match());
 }
 
 #[test]
@@ -28,5 +28,63 @@ fn test_no_match_different_values() {
 fn test_rational_exact_match() {
     let a = Number::rational(1, 2);
     let b = Number::rational(2, 4);
-    assert_eq!(a.matches(&b), MatchResult::Match);
+    assert!(a.matches(&b).is_match());
+}
+
+#[test]
+fn test_operator_must_match() {
+    let plus = Number::new(2);
+    let times = Number::new(2).with_operator(ArithOperator::Multiply);
+    assert_eq!(plus.matches(&times), MatchResult::NoMatch);
+    assert!(times.matches(&times.copy_number()).is_match());
+}
+
+#[test]
+fn test_erased_target_needs_erased_pattern() {
+    let erased = Number::erased();
+    assert_eq!(Number::new(0).matches(&erased), MatchResult::NoMatch);
+    assert!(Number::erased().matches(&erased).is_match());
+}
+
+#[test]
+fn test_no_match_other_type() {
+    let pattern = Number::erased();
+    let impostor = Impostor;
+    assert_eq!(pattern.matches(&impostor), MatchResult::NoMatch);
+}
+
+/// A non-number widget whose description mimics a number.
+#[derive(Debug)]
+struct Impostor;
+
+impl Widget for Impostor {
+    fn type_name(&self) -> &'static str {
+        "number"
+    }
+    fn id(&self) -> WidgetId {
+        WidgetId::from_u64(0)
+    }
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+    fn copy(&self) -> Box<dyn Widget> {
+        Box::new(Impostor)
+    }
+    fn matches(&self, _other: &dyn Widget) -> MatchResult {
+        MatchResult::NoMatch
+    }
+    fn render(&self) -> yew::Html {
+        yew::Html::default()
+    }
+    fn description(&self) -> String {
+        "number +0".to_string()
+    }
+}
+
+#[test]
+fn test_erase_steps_to_any_number() {
+    let three = Number::new(3);
+    let erased = three.erase().unwrap();
+    assert!(erased.matches(&Number::new(42)).is_match());
+    assert!(erased.erase().is_none());
 }
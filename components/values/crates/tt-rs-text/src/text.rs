@@ -11,6 +11,16 @@ pub enum ErasureLevel {
     Value,
 }
 
+impl ErasureLevel {
+    /// The next, more general level, if any.
+    pub fn next(self) -> Option<Self> {
+        match self {
+            ErasureLevel::None => Some(ErasureLevel::Value),
+            ErasureLevel::Value => None,
+        }
+    }
+}
+
 /// A text widget representing a string value.
 #[derive(Debug, Clone)]
 pub struct Text {
@@ -1,6 +1,7 @@
 //! Widget trait implementation for Text.
 
 use crate::text::{ErasureLevel, Text};
+use std::any::Any;
 use tt_rs_core::{MatchResult, Widget, WidgetId};
 use yew::prelude::*;
 
@@ -13,6 +14,10 @@ impl Widget for Text {
         self.id
     }
 
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
     fn copy(&self) -> Box<dyn Widget> {
         Box::new(Text {
             id: WidgetId::new(),
@@ -21,14 +26,23 @@ impl Widget for Text {
         })
     }
 
+    fn erase(&self) -> Option<Box<dyn Widget>> {
+        let erasure = self.erasure.next()?;
+        Some(Box::new(Text {
+            erasure,
+            ..self.clone()
+        }))
+    }
+
     fn matches(&self, other: &dyn Widget) -> MatchResult {
-        if other.type_name() != "text" {
-            return MatchResult::NoMatch;
-        }
-        let matches =
-            self.erasure == ErasureLevel::Value || other.description() == self.description();
+        let other = match other.as_any().downcast_ref::<Text>() {
+            Some(t) => t,
+            None => return MatchResult::NoMatch,
+        };
+        let matches = self.erasure == ErasureLevel::Value
+            || (other.erasure == ErasureLevel::None && other.value == self.value);
         if matches {
-            MatchResult::Match
+            MatchResult::matched(other.id())
         } else {
             MatchResult::NoMatch
         }
@@ -7,7 +7,7 @@ use tt_rs_text::Text;
 fn test_exact_match() {
     let pattern = Text::new("hello");
     let target = Text::new("hello");
-    assert_eq!(pattern.matches(&target), MatchResult::Match);
+    assert!(pattern.matches(&target).is_match());
 }
 
 #[test]
@@ -24,9 +24,9 @@ fn test_erased_matches_any() {
     let target2 = Text::new("world");
     let target3 = Text::new("");
 
-    assert_eq!(pattern.matches(&target1), MatchResult::Match);
-    assert_eq!(pattern.matches(&target2), MatchResult::Match);
-    assert_eq!(pattern.matches(&target3), MatchResult::Match);
+    assert!(pattern.matches(&target1).is_match());
+    assert!(pattern.matches(&target2).is_match());
+    assert!(pattern.matches(&target3).is_match());
 }
 
 #[test]
@@ -35,3 +35,18 @@ fn test_case_sensitive() {
     let target = Text::new("hello");
     assert_eq!(pattern.matches(&target), MatchResult::NoMatch);
 }
+
+#[test]
+fn test_erased_target_needs_erased_pattern() {
+    let erased = Text::erased();
+    assert_eq!(Text::new("").matches(&erased), MatchResult::NoMatch);
+    assert!(Text::erased().matches(&erased).is_match());
+}
+
+#[test]
+fn test_erase_steps_to_any_text() {
+    let hello = Text::new("hello");
+    let erased = hello.erase().unwrap();
+    assert!(erased.matches(&Text::new("bye")).is_match());
+    assert!(erased.erase().is_none());
+}